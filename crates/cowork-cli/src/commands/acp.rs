@@ -0,0 +1,38 @@
+//! Serve Cowork as an ACP agent over stdio
+
+use anyhow::Result;
+
+/// Run the ACP agent until the client closes stdin
+pub async fn execute() -> Result<()> {
+    let outgoing = protocol_stdout()?;
+    cowork_core::acp::server::serve(outgoing, tokio::io::stdin()).await
+}
+
+/// The protocol owns stdout, and anything else printed there would corrupt
+/// it (some tools still use `println!`). Point fd 1 at stderr and give the
+/// protocol a duplicate of the original stdout.
+#[cfg(unix)]
+fn protocol_stdout() -> Result<tokio::fs::File> {
+    use std::io::Write;
+    use std::os::fd::FromRawFd;
+
+    std::io::stdout().flush()?;
+    // SAFETY: plain descriptor calls; the duplicate is owned by the File
+    unsafe {
+        let fd = libc::dup(libc::STDOUT_FILENO);
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        if libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) < 0 {
+            let error = std::io::Error::last_os_error();
+            libc::close(fd);
+            return Err(error.into());
+        }
+        Ok(tokio::fs::File::from_std(std::fs::File::from_raw_fd(fd)))
+    }
+}
+
+#[cfg(not(unix))]
+fn protocol_stdout() -> Result<tokio::io::Stdout> {
+    Ok(tokio::io::stdout())
+}
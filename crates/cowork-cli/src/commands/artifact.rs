@@ -0,0 +1,98 @@
+//! Browse, diff and restore artifact revisions
+
+use anyhow::Result;
+use cowork_core::persistence::{resolve_artifact_name, ArtifactHistory, IterationStore};
+use similar::TextDiff;
+
+use crate::output::print_json;
+
+/// Characters of triggering feedback shown per revision
+const MAX_FEEDBACK_CHARS: usize = 60;
+
+fn open_history(iteration_id: &str, artifact: &str) -> Result<ArtifactHistory> {
+    if !IterationStore::new().exists(iteration_id) {
+        anyhow::bail!("Iteration '{}' not found", iteration_id);
+    }
+    ArtifactHistory::open(iteration_id, resolve_artifact_name(artifact)?)
+}
+
+/// List the revisions of an artifact
+pub async fn history(iteration_id: String, artifact: String, as_json: bool) -> Result<()> {
+    let history = open_history(&iteration_id, &artifact)?;
+    let revisions = history.revisions()?;
+
+    if as_json {
+        return print_json(&revisions);
+    }
+
+    if revisions.is_empty() {
+        println!("No revisions of {} recorded for iteration '{}'.", history.filename(), iteration_id);
+        return Ok(());
+    }
+
+    println!("📜 {} revisions ({})\n", history.filename(), iteration_id);
+    for r in &revisions {
+        let restored = r.restored_from.map(|n| format!(" (restored from r{})", n)).unwrap_or_default();
+        println!(
+            "  r{:<3} {}  {:<16} {:>7} bytes{}",
+            r.revision,
+            r.created_at.format("%Y-%m-%d %H:%M:%S"),
+            r.author,
+            r.size,
+            restored
+        );
+        if let Some(feedback) = &r.feedback {
+            let feedback = feedback.replace('\n', " ");
+            if feedback.chars().count() > MAX_FEEDBACK_CHARS {
+                let head: String = feedback.chars().take(MAX_FEEDBACK_CHARS - 3).collect();
+                println!("        ↳ {}...", head);
+            } else {
+                println!("        ↳ {}", feedback);
+            }
+        }
+    }
+    println!("\nCompare with: cowork artifact diff {} {} --from <n> --to <n>", iteration_id, artifact);
+    Ok(())
+}
+
+/// Unified diff between two revisions (default: the last two)
+pub async fn diff(iteration_id: String, artifact: String, from: Option<u32>, to: Option<u32>) -> Result<()> {
+    let history = open_history(&iteration_id, &artifact)?;
+    let revisions = history.revisions()?;
+    let latest = revisions
+        .last()
+        .map(|r| r.revision)
+        .ok_or_else(|| anyhow::anyhow!("No revisions of {} recorded", history.filename()))?;
+
+    let to = to.unwrap_or(latest);
+    let from = from.unwrap_or(to.saturating_sub(1));
+    if from == 0 {
+        anyhow::bail!("{} has only one revision", history.filename());
+    }
+
+    let old = history.load(from)?;
+    let new = history.load(to)?;
+    let diff = TextDiff::from_lines(&old, &new);
+
+    let from_label = format!("{}@r{}", history.filename(), from);
+    let to_label = format!("{}@r{}", history.filename(), to);
+    if diff.ratio() == 1.0 {
+        println!("No changes between {} and {}.", from_label, to_label);
+        return Ok(());
+    }
+    print!("{}", diff.unified_diff().context_radius(3).header(&from_label, &to_label));
+    Ok(())
+}
+
+/// Make an earlier revision the current artifact again
+pub async fn restore(iteration_id: String, artifact: String, revision: u32) -> Result<()> {
+    let history = open_history(&iteration_id, &artifact)?;
+    let restored = history.restore(revision)?;
+    println!(
+        "✅ Restored {} to revision {} (now r{}).",
+        history.filename(),
+        revision,
+        restored.revision
+    );
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! Shell completion scripts and man pages
+
+use anyhow::{Context, Result};
+use clap_complete::Shell;
+use std::path::PathBuf;
+
+/// Write a completion script for `shell` to stdout
+pub fn completions(mut command: clap::Command, shell: Shell) -> Result<()> {
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+    Ok(())
+}
+
+/// Write the man page to stdout, or one page per subcommand into `out_dir`
+pub fn man(command: clap::Command, out_dir: Option<PathBuf>) -> Result<()> {
+    match out_dir {
+        Some(dir) => {
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            clap_mangen::generate_to(command, &dir)
+                .with_context(|| format!("Failed to write man pages to {}", dir.display()))?;
+            println!("✅ Man pages written to {}", dir.display());
+        }
+        None => {
+            clap_mangen::Man::new(command).render(&mut std::io::stdout())?;
+        }
+    }
+    Ok(())
+}
@@ -1,42 +1,130 @@
 //! Configure LLM settings command
 
 use anyhow::{Context, Result};
-use cowork_core::llm::config::{get_config_path, load_config, save_config, ModelConfig};
+use cowork_core::llm::config::{
+    active_profile, config_entries, get_config_path, get_config_value, is_secret_key,
+    list_profiles, load_config, save_config, set_config_value, ModelConfig, DEFAULT_PROFILE,
+};
 
 /// Configure LLM settings
 pub async fn execute() -> Result<()> {
     println!("⚙️  Cowork Configuration\n");
-    
+
     let config_path = get_config_path()
         .context("Failed to get config path")?;
-    
+
+    println!("Profile:              {}", active_profile().as_deref().unwrap_or(DEFAULT_PROFILE));
     println!("Config file location: {}", config_path.display());
-    
+
     let existing_config = load_config().ok();
-    
+
     if let Some(ref config) = existing_config {
         println!("\nCurrent LLM Configuration:");
         println!("  API Base URL: {}", config.llm.api_base_url);
         println!("  Model Name:   {}", config.llm.model_name);
         println!("  API Key:      {}...", &config.llm.api_key.chars().take(8).collect::<String>());
-        
+
         if config.coding_agent.enabled {
             println!("\n  Coding Agent: enabled ({})", config.coding_agent.agent_type);
         }
     } else {
         println!("\nNo configuration found. Creating default config...");
     }
-    
+
     println!("\nTo edit the configuration, open the file in your editor:");
     println!("  {}", config_path.display());
-    
+    println!("or use 'cowork config set <key> <value>'.");
+
     if existing_config.is_none() {
         let default_config = ModelConfig::default();
         save_config(&default_config)?;
         println!("\n✅ Created default config file at: {}", config_path.display());
         println!("   Please edit the file to add your LLM API settings.");
     }
-    
+
+    Ok(())
+}
+
+/// Config of the active profile; a missing file reads as the defaults
+fn load_for_edit() -> Result<ModelConfig> {
+    let path = get_config_path()?;
+    if path.exists() {
+        ModelConfig::from_file(&path)
+    } else {
+        Ok(ModelConfig::default())
+    }
+}
+
+fn display_value(key: &str, value: &toml::Value, show_secrets: bool) -> String {
+    match value {
+        toml::Value::String(s) if is_secret_key(key) && !show_secrets && !s.is_empty() => {
+            format!("{}...", s.chars().take(4).collect::<String>())
+        }
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Print one setting
+pub async fn get(key: String, show_secrets: bool) -> Result<()> {
+    let config = load_for_edit()?;
+    let value = get_config_value(&config, &key)?;
+    println!("{}", display_value(&key, &value, show_secrets));
+    Ok(())
+}
+
+/// Change one setting and save the profile's config file
+pub async fn set(key: String, value: String) -> Result<()> {
+    let config = set_config_value(&load_for_edit()?, &key, &value)?;
+    let path = save_config(&config)?;
+    let shown = get_config_value(&config, &key)?;
+    println!("✅ {} = {}", key, display_value(&key, &shown, false));
+    println!("   Saved to {}", path.display());
     Ok(())
 }
 
+/// Print every setting of the active profile
+pub async fn list(show_secrets: bool, json: bool) -> Result<()> {
+    let config = load_for_edit()?;
+    let entries = config_entries(&config)?;
+
+    if json {
+        let map: serde_json::Map<String, serde_json::Value> = entries
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    toml::Value::String(_) => {
+                        serde_json::Value::String(display_value(key, value, show_secrets))
+                    }
+                    other => serde_json::to_value(other).unwrap_or(serde_json::Value::Null),
+                };
+                (key.clone(), value)
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&map)?);
+        return Ok(());
+    }
+
+    println!("# profile: {}", active_profile().as_deref().unwrap_or(DEFAULT_PROFILE));
+    println!("# file:    {}", get_config_path()?.display());
+    for (key, value) in &entries {
+        println!("{} = {}", key, display_value(key, value, show_secrets));
+    }
+    Ok(())
+}
+
+/// Print the profiles that have a config file
+pub async fn profiles() -> Result<()> {
+    let active = active_profile().unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    let profiles = list_profiles()?;
+
+    if profiles.is_empty() {
+        println!("No profiles yet. Create one with 'cowork --profile <name> config'.");
+        return Ok(());
+    }
+    for profile in profiles {
+        let marker = if profile == active { "*" } else { " " };
+        println!("{} {}", marker, profile);
+    }
+    Ok(())
+}
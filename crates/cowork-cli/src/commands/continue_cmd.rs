@@ -2,14 +2,21 @@
 
 use anyhow::{Context, Result};
 use cowork_core::domain::IterationStatus;
-use cowork_core::interaction::CliBackend;
 use cowork_core::llm::{create_llm_client, load_config};
 use cowork_core::persistence::{IterationStore, ProjectStore};
 use cowork_core::pipeline::IterationExecutor;
-use std::sync::Arc;
+use std::path::PathBuf;
+
+use crate::output::OutputFormat;
+use crate::run::RunBackend;
 
 /// Continue a paused iteration
-pub async fn execute(iteration_id: Option<String>) -> Result<()> {
+pub async fn execute(
+    iteration_id: Option<String>,
+    non_interactive: bool,
+    policy: Option<PathBuf>,
+    output: OutputFormat,
+) -> Result<()> {
     let project_store = ProjectStore::new();
     let iteration_store = IterationStore::new();
 
@@ -51,12 +58,15 @@ pub async fn execute(iteration_id: Option<String>) -> Result<()> {
         anyhow::bail!("Iteration '{}' is not paused (status: {:?})", iteration_id, iteration.status);
     }
 
-    println!("🔄 Continuing iteration: {}", iteration.title);
-    println!("   Current stage: {:?}", iteration.current_stage);
-    println!();
+    let backend = RunBackend::select(non_interactive, policy, output)?;
+    backend.iteration_event("resumed", &iteration.id, &iteration.title);
+    if output.is_text() {
+        println!("🔄 Continuing iteration: {}", iteration.title);
+        println!("   Current stage: {:?}", iteration.current_stage);
+        println!();
+    }
 
-    let interaction = Arc::new(CliBackend::new());
-    let executor = IterationExecutor::new(interaction);
+    let executor = IterationExecutor::new(backend.interaction.clone());
 
     let model_config = load_config()
         .context("Failed to load LLM configuration. Run 'cowork config' to set up.")?;
@@ -64,14 +74,6 @@ pub async fn execute(iteration_id: Option<String>) -> Result<()> {
     let model = create_llm_client(&model_config.llm)
         .context("Failed to create LLM client")?;
 
-    match executor.continue_iteration(&mut project, &iteration_id, Some(model)).await {
-        Ok(_) => {
-            println!("\n✅ Iteration completed!");
-            Ok(())
-        }
-        Err(e) => {
-            println!("\n❌ Iteration failed: {}", e);
-            Err(e)
-        }
-    }
+    let result = executor.continue_iteration(&mut project, &iteration_id, Some(model)).await;
+    backend.finish(&iteration_id, result, "✅ Iteration completed!")
 }
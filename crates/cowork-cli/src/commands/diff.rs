@@ -0,0 +1,70 @@
+//! Diff structured data between two iterations
+
+use anyhow::Result;
+use cowork_core::data::IterationDataDiff;
+use cowork_core::persistence::IterationStore;
+use serde_json::Value;
+
+/// Maximum characters shown for a field value
+const MAX_VALUE_CHARS: usize = 80;
+
+/// Show added/removed/modified requirements, features, design and plan items
+pub async fn execute(from: String, to: String) -> Result<()> {
+    let iteration_store = IterationStore::new();
+    for id in [&from, &to] {
+        if !iteration_store.exists(id) {
+            anyhow::bail!("Iteration '{}' not found", id);
+        }
+    }
+
+    let diff = IterationDataDiff::between(&from, &to)?;
+
+    println!("🔍 Data diff: {} → {}\n", from, to);
+    if diff.is_empty() {
+        println!("No changes in requirements, features, design or plan.");
+        return Ok(());
+    }
+
+    for collection in &diff.collections {
+        println!(
+            "📂 {} (+{} -{} ~{})",
+            collection.collection,
+            collection.added.len(),
+            collection.removed.len(),
+            collection.modified.len()
+        );
+        for item in &collection.added {
+            println!("  + {}  {}", item.id, item.label);
+        }
+        for item in &collection.removed {
+            println!("  - {}  {}", item.id, item.label);
+        }
+        for item in &collection.modified {
+            println!("  ~ {}  {}", item.id, item.label);
+            for change in &item.changes {
+                println!(
+                    "      {}: {} → {}",
+                    change.field,
+                    preview(&change.before),
+                    preview(&change.after)
+                );
+            }
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+fn preview(value: &Value) -> String {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "∅".to_string(),
+        other => other.to_string(),
+    };
+    if text.chars().count() > MAX_VALUE_CHARS {
+        format!("{}...", text.chars().take(MAX_VALUE_CHARS - 3).collect::<String>())
+    } else {
+        text
+    }
+}
@@ -0,0 +1,177 @@
+//! Check that the configuration, LLM endpoint and integrations work
+
+use anyhow::Result;
+use cowork_core::config_definition::{global_registry, initialize_config_registry, ConfigValidator};
+use cowork_core::llm::config::{
+    active_profile, check_llm_endpoint, get_config_path, ModelConfig, DEFAULT_PROFILE,
+};
+use cowork_core::sandbox::{Isolation, Sandbox};
+use cowork_core::tools::mcp_tools::{create_mcp_configs_from_config, McpManager};
+use std::path::{Path, PathBuf};
+
+#[derive(Default)]
+struct Report {
+    failures: usize,
+    warnings: usize,
+}
+
+impl Report {
+    fn ok(&self, check: &str, detail: impl AsRef<str>) {
+        println!("  ✅ {:<14} {}", check, detail.as_ref());
+    }
+
+    fn warn(&mut self, check: &str, detail: impl AsRef<str>) {
+        self.warnings += 1;
+        println!("  ⚠️  {:<14} {}", check, detail.as_ref());
+    }
+
+    fn fail(&mut self, check: &str, detail: impl AsRef<str>) {
+        self.failures += 1;
+        println!("  ❌ {:<14} {}", check, detail.as_ref());
+    }
+}
+
+/// Run every check and fail if any of them did
+pub async fn execute() -> Result<()> {
+    println!("🩺 Cowork Doctor\n");
+    let mut report = Report::default();
+
+    let profile = active_profile().unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    let config_path = get_config_path()?;
+    let config = if config_path.exists() {
+        match ModelConfig::from_file(&config_path) {
+            Ok(config) => {
+                report.ok("config", format!("{} (profile '{}')", config_path.display(), profile));
+                Some(config)
+            }
+            Err(e) => {
+                report.fail("config", format!("{:#}", e));
+                None
+            }
+        }
+    } else {
+        match ModelConfig::from_env() {
+            Ok(config) => {
+                report.warn("config", format!("{} not found, using LLM_* environment variables", config_path.display()));
+                Some(config)
+            }
+            Err(_) => {
+                report.fail("config", format!("{} not found; run 'cowork config'", config_path.display()));
+                None
+            }
+        }
+    };
+
+    if let Some(config) = &config {
+        match check_llm_endpoint(&config.llm).await {
+            Ok(detail) => report.ok("llm", format!("{} — {}", config.llm.api_base_url, detail)),
+            Err(e) => report.fail("llm", format!("{:#}", e)),
+        }
+
+        if config.coding_agent.enabled {
+            let command = &config.coding_agent.command;
+            match find_command(command) {
+                Some(path) => report.ok(
+                    "coding agent",
+                    format!("{} ({})", config.coding_agent.agent_type, path.display()),
+                ),
+                None => report.fail(
+                    "coding agent",
+                    format!("command '{}' not found on PATH", command),
+                ),
+            }
+        } else {
+            report.ok("coding agent", "disabled (built-in agents)");
+        }
+
+        check_mcp(config, &mut report).await;
+    }
+
+    if cowork_core::git::git_available() {
+        report.ok("git", "available");
+    } else {
+        report.warn("git", "not found; iteration history and branch delivery are disabled");
+    }
+
+    check_sandbox(&mut report);
+
+    match initialize_config_registry() {
+        Ok(()) => {
+            let registry = global_registry();
+            let result = ConfigValidator::new(&registry).validate_all();
+            let stats = registry.stats();
+            if result.is_valid {
+                report.ok(
+                    "registry",
+                    format!("{} agents, {} stages, {} flows", stats.agents, stats.stages, stats.flows),
+                );
+            } else {
+                report.fail("registry", format!("{} errors", result.errors.len()));
+            }
+            for error in &result.errors {
+                println!("       - {}", error);
+            }
+            for warning in &result.warnings {
+                report.warn("registry", warning);
+            }
+        }
+        Err(e) => report.fail("registry", format!("{:#}", e)),
+    }
+
+    println!();
+    if report.failures > 0 {
+        anyhow::bail!("{} checks failed, {} warnings", report.failures, report.warnings);
+    }
+    println!("All checks passed ({} warnings).", report.warnings);
+    Ok(())
+}
+
+async fn check_mcp(config: &ModelConfig, report: &mut Report) {
+    let servers = create_mcp_configs_from_config(&config.mcp);
+    if servers.is_empty() {
+        report.ok("mcp", "no servers configured");
+        return;
+    }
+
+    for server in servers {
+        let name = server.name.clone();
+        let mut manager = McpManager::new();
+        manager.add_config(server);
+        match manager.connect_all().await {
+            Ok(_) => report.ok("mcp", format!("{} connected", name)),
+            Err(e) => report.fail("mcp", format!("{}: {:#}", name, e)),
+        }
+    }
+}
+
+fn check_sandbox(report: &mut Report) {
+    let sandbox = match Sandbox::for_workspace(".") {
+        Ok(sandbox) => sandbox,
+        Err(e) => return report.fail("sandbox", format!("{:#}", e)),
+    };
+    let network = if sandbox.policy().network { "network on" } else { "network off" };
+    match sandbox.isolation() {
+        Ok(Isolation::Bubblewrap) => report.ok("sandbox", format!("bubblewrap, {}", network)),
+        Ok(Isolation::Off) => report.warn("sandbox", "disabled in .cowork-v2/sandbox.json"),
+        Ok(isolation) if cfg!(target_os = "linux") => report.warn(
+            "sandbox",
+            format!("{}; install bubblewrap (bwrap) to confine writes to the workspace", isolation.as_str()),
+        ),
+        Ok(isolation) => report.ok("sandbox", format!("{} (isolation is Linux only)", isolation.as_str())),
+        Err(e) => report.fail("sandbox", e.to_string()),
+    }
+}
+
+/// Resolve a command the way a shell would: paths as given, bare names on PATH
+fn find_command(command: &str) -> Option<PathBuf> {
+    let is_runnable = |path: &Path| path.is_file();
+    if command.contains(std::path::MAIN_SEPARATOR) || command.contains('/') {
+        let path = PathBuf::from(command);
+        return is_runnable(&path).then_some(path);
+    }
+
+    let extensions: &[&str] = if cfg!(windows) { &["", ".exe", ".cmd", ".bat"] } else { &[""] };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .flat_map(|dir| extensions.iter().map(move |ext| dir.join(format!("{}{}", command, ext))))
+        .find(|path| is_runnable(path))
+}
@@ -1,19 +1,24 @@
 //! Iteration creation and execution command
 
 use anyhow::{Context, Result};
-use cowork_core::interaction::CliBackend;
 use cowork_core::llm::{create_llm_client, load_config};
 use cowork_core::persistence::{IterationStore, ProjectStore};
 use cowork_core::pipeline::IterationExecutor;
-use std::sync::Arc;
+use std::path::PathBuf;
 use tracing::info;
 
+use crate::output::OutputFormat;
+use crate::run::RunBackend;
+
 /// Create and execute a new iteration
 pub async fn execute(
     title: String,
     description: Option<String>,
     base: Option<String>,
     inherit: String,
+    non_interactive: bool,
+    policy: Option<PathBuf>,
+    output: OutputFormat,
 ) -> Result<()> {
     let project_store = ProjectStore::new();
     let iteration_store = IterationStore::new();
@@ -27,8 +32,8 @@ pub async fn execute(
 
     let description = description.unwrap_or_else(|| title.clone());
 
-    let interaction = Arc::new(CliBackend::new());
-    let executor = IterationExecutor::new(interaction);
+    let backend = RunBackend::select(non_interactive, policy, output)?;
+    let executor = IterationExecutor::new(backend.interaction.clone());
 
     let iteration = if let Some(base_id) = base {
         info!("Creating evolution iteration based on: {}", base_id);
@@ -62,18 +67,21 @@ pub async fn execute(
     iteration_store.save(&iteration)?;
     project_store.add_iteration(&mut project, iteration.to_summary())?;
 
-    println!("✨ Created iteration: {}", iteration.id);
-    println!("   Title: {}", iteration.title);
-    println!("   Number: {}", iteration.number);
-    if iteration.base_iteration_id.is_some() {
-        println!("   Base: {:?}", iteration.base_iteration_id);
-        println!("   Inheritance: {:?}", iteration.inheritance);
-    }
-    println!("   Start Stage: {}", iteration.determine_start_stage());
-    println!();
+    backend.iteration_event("created", &iteration.id, &iteration.title);
+    if output.is_text() {
+        println!("✨ Created iteration: {}", iteration.id);
+        println!("   Title: {}", iteration.title);
+        println!("   Number: {}", iteration.number);
+        if iteration.base_iteration_id.is_some() {
+            println!("   Base: {:?}", iteration.base_iteration_id);
+            println!("   Inheritance: {:?}", iteration.inheritance);
+        }
+        println!("   Start Stage: {}", iteration.determine_start_stage());
+        println!();
 
-    println!("🚀 Starting iteration execution...");
-    println!();
+        println!("🚀 Starting iteration execution...");
+        println!();
+    }
 
     let model_config = load_config()
         .context("Failed to load LLM configuration. Run 'cowork config' to set up.")?;
@@ -81,15 +89,13 @@ pub async fn execute(
     let model = create_llm_client(&model_config.llm)
         .context("Failed to create LLM client")?;
 
-    match executor.execute(&mut project, &iteration.id, None, Some(model)).await {
-        Ok(_) => {
-            println!("\n✅ Iteration '{}' completed successfully!", iteration.title);
-            println!("   Iteration ID: {}", iteration.id);
-            Ok(())
-        }
-        Err(e) => {
-            println!("\n❌ Iteration failed: {}", e);
-            Err(e)
-        }
-    }
+    let result = executor.execute(&mut project, &iteration.id, None, Some(model)).await;
+    backend.finish(
+        &iteration.id,
+        result,
+        &format!(
+            "✅ Iteration '{}' completed successfully!\n   Iteration ID: {}",
+            iteration.title, iteration.id
+        ),
+    )
 }
@@ -3,13 +3,40 @@
 use anyhow::Result;
 use cowork_core::domain::IterationStatus;
 use cowork_core::persistence::{IterationStore, ProjectStore};
+use serde_json::json;
+use crate::output::print_json;
 use crate::utils::truncate;
 
 /// List all iterations
-pub async fn execute(all: bool) -> Result<()> {
+pub async fn execute(all: bool, as_json: bool) -> Result<()> {
     let project_store = ProjectStore::new();
     let iteration_store = IterationStore::new();
 
+    if as_json {
+        let project = project_store
+            .load()?
+            .ok_or_else(|| anyhow::anyhow!("No project found. Run 'cowork init' first."))?;
+        let iterations: Vec<_> = iteration_store
+            .load_all()?
+            .into_iter()
+            .filter(|i| all || matches!(i.status, IterationStatus::Running | IterationStatus::Paused))
+            .map(|i| {
+                json!({
+                    "id": i.id,
+                    "number": i.number,
+                    "title": i.title,
+                    "status": i.status,
+                    "current_stage": i.current_stage,
+                    "completed_stages": i.completed_stages,
+                    "base_iteration_id": i.base_iteration_id,
+                    "started_at": i.started_at,
+                    "completed_at": i.completed_at,
+                })
+            })
+            .collect();
+        return print_json(&json!({ "project": project.name, "iterations": iterations }));
+    }
+
     match project_store.load()? {
         Some(project) => {
             println!("📊 Project: {}\n", project.name);
@@ -0,0 +1,91 @@
+//! Serve the project's requirements, design, plan, memory and artifacts over MCP
+
+use anyhow::{Context, Result};
+use axum::extract::Request;
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use clap::ValueEnum;
+use cowork_core::mcp_server::{serve_stdio, CoworkMcpServer};
+use cowork_core::persistence::ProjectStore;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::streamable_http_server::{StreamableHttpServerConfig, StreamableHttpService};
+use std::sync::Arc;
+
+use super::serve::SERVE_TOKEN_ENV;
+
+/// Path of the streamable HTTP endpoint
+const MCP_PATH: &str = "/mcp";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum McpTransport {
+    /// JSON-RPC over stdin/stdout, for clients that spawn the server
+    Stdio,
+    /// Streamable HTTP at /mcp
+    Http,
+}
+
+/// Serve until the client disconnects (stdio) or the process is stopped (HTTP)
+pub async fn execute(transport: McpTransport, host: String, port: u16, token: Option<String>) -> Result<()> {
+    if ProjectStore::new().load()?.is_none() {
+        anyhow::bail!("No project found. Run 'cowork init' first.");
+    }
+
+    match transport {
+        McpTransport::Stdio => serve_stdio().await,
+        McpTransport::Http => serve_http(host, port, token).await,
+    }
+}
+
+async fn serve_http(host: String, port: u16, token: Option<String>) -> Result<()> {
+    let token = token.or_else(|| std::env::var(SERVE_TOKEN_ENV).ok()).filter(|t| !t.is_empty());
+
+    let mut config = StreamableHttpServerConfig::default();
+    if !config.allowed_hosts.contains(&host) {
+        config.allowed_hosts.push(host.clone());
+    }
+    let service = StreamableHttpService::new(
+        || Ok(CoworkMcpServer),
+        Arc::new(LocalSessionManager::default()),
+        config,
+    );
+
+    let required = token.clone();
+    let app = Router::new()
+        .nest_service(MCP_PATH, service)
+        .layer(middleware::from_fn(move |request: Request, next: Next| {
+            let required = required.clone();
+            async move { require_token(required.as_deref(), request, next).await }
+        }));
+
+    let addr = format!("{}:{}", host, port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind {}", addr))?;
+
+    println!("🔌 Cowork MCP server listening on http://{}{}", addr, MCP_PATH);
+    if token.is_some() {
+        println!("   Authentication: Bearer token required");
+    } else if host != "127.0.0.1" && host != "localhost" {
+        println!("⚠️  No token set; anyone who can reach {} can read this project", addr);
+    }
+
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn require_token(token: Option<&str>, request: Request, next: Next) -> Response {
+    if let Some(token) = token {
+        let authorized = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|v| v == token);
+        if !authorized {
+            return (StatusCode::UNAUTHORIZED, "Missing or invalid bearer token").into_response();
+        }
+    }
+    next.run(request).await
+}
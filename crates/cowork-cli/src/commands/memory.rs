@@ -0,0 +1,98 @@
+//! Global (cross-project) memory commands
+
+use anyhow::Result;
+use cowork_core::persistence::{is_global_memory_enabled, MemoryStore};
+use crate::utils::truncate;
+
+/// List project decisions/patterns (candidates for promotion)
+pub async fn list_project() -> Result<()> {
+    let store = MemoryStore::new();
+    let memory = store.load_project_memory()?;
+
+    if memory.decisions.is_empty() && memory.patterns.is_empty() {
+        println!("No project decisions or patterns yet.");
+        return Ok(());
+    }
+
+    print_entries("📁 Project Memory", &memory.decisions, &memory.patterns);
+    println!("\nPromote an entry with: cowork memory promote <id>");
+    Ok(())
+}
+
+/// List global memory entries
+pub async fn list_global() -> Result<()> {
+    let store = MemoryStore::new();
+    let memory = store.load_global_memory()?;
+
+    if !is_global_memory_enabled() {
+        println!("⚠️  Global memory is disabled; agents will not see these entries.");
+        println!("   Set `global_enabled = true` under [memory] in config.toml to enable it.\n");
+    }
+
+    if memory.decisions.is_empty() && memory.patterns.is_empty() {
+        println!("Global memory is empty. Promote project entries with 'cowork memory promote <id>'.");
+        return Ok(());
+    }
+
+    print_entries("🌐 Global Memory", &memory.decisions, &memory.patterns);
+    Ok(())
+}
+
+/// Promote a project decision or pattern into global memory
+pub async fn promote(id: String) -> Result<()> {
+    let store = MemoryStore::new();
+
+    if id.starts_with("pat-") {
+        let pattern = store.promote_pattern_to_global(&id)?;
+        println!("✅ Pattern '{}' promoted to global memory.", pattern.name);
+    } else if id.starts_with("dec-") {
+        let decision = store.promote_decision_to_global(&id)?;
+        println!("✅ Decision '{}' promoted to global memory.", decision.title);
+    } else {
+        anyhow::bail!("Unknown memory ID '{}'. Expected a pattern (pat-...) or decision (dec-...) ID.", id);
+    }
+
+    if !is_global_memory_enabled() {
+        println!("   Note: global memory is disabled; enable it with `global_enabled = true` under [memory] in config.toml.");
+    }
+    Ok(())
+}
+
+/// Remove an entry from global memory
+pub async fn forget(id: String) -> Result<()> {
+    let store = MemoryStore::new();
+
+    if store.remove_global_entry(&id)? {
+        println!("✅ Removed '{}' from global memory.", id);
+    } else {
+        anyhow::bail!("Global memory entry '{}' not found", id);
+    }
+    Ok(())
+}
+
+fn print_entries(
+    title: &str,
+    decisions: &[cowork_core::domain::Decision],
+    patterns: &[cowork_core::domain::Pattern],
+) {
+    println!("{}\n", title);
+
+    if !decisions.is_empty() {
+        println!("Decisions:");
+        for d in decisions {
+            let source = d.source_project.as_deref().map(|p| format!(" [{}]", p)).unwrap_or_default();
+            println!("  {}  {}{}", d.id, truncate(&d.title, 60), source);
+        }
+    }
+
+    if !patterns.is_empty() {
+        if !decisions.is_empty() {
+            println!();
+        }
+        println!("Patterns:");
+        for p in patterns {
+            let source = p.source_project.as_deref().map(|p| format!(" [{}]", p)).unwrap_or_default();
+            println!("  {}  {}{}", p.id, truncate(&p.name, 60), source);
+        }
+    }
+}
@@ -12,6 +12,17 @@ pub mod delete;
 pub mod knowledge;
 pub mod import;
 pub mod config;
+pub mod memory;
+pub mod diff;
+pub mod serve;
+pub mod rewind;
+pub mod artifact;
+pub mod rollback_delivery;
+pub mod watch;
+pub mod doctor;
+pub mod completions;
+pub mod acp;
+pub mod mcp_serve;
 
 // Re-export command execution functions with clean names
 pub use iter::execute as iter;
@@ -24,3 +35,11 @@ pub use delete::execute as delete;
 pub use knowledge::execute as regenerate_knowledge;
 pub use import::execute as import;
 pub use config::execute as config;
+pub use diff::execute as diff;
+pub use serve::execute as serve;
+pub use rewind::execute as rewind;
+pub use rollback_delivery::execute as rollback_delivery;
+pub use watch::execute as watch;
+pub use doctor::execute as doctor;
+pub use acp::execute as acp;
+pub use mcp_serve::execute as mcp_serve;
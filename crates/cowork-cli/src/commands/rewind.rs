@@ -0,0 +1,90 @@
+//! Rewind an iteration to an earlier stage and re-run from there
+
+use anyhow::{Context, Result};
+use cowork_core::llm::{create_llm_client, load_config};
+use cowork_core::persistence::{IterationStore, ProjectStore};
+use cowork_core::pipeline::{IterationExecutor, RewindPlan};
+use std::path::PathBuf;
+
+use crate::output::{print_json, OutputFormat};
+use crate::run::RunBackend;
+
+/// Options for `cowork rewind`
+pub struct RewindOptions {
+    pub iteration_id: String,
+    pub to: String,
+    pub feedback: Option<String>,
+    pub dry_run: bool,
+    pub discard: bool,
+    pub non_interactive: bool,
+    pub policy: Option<PathBuf>,
+}
+
+/// Rewind an iteration to a stage and re-run it
+pub async fn execute(options: RewindOptions, output: OutputFormat) -> Result<()> {
+    let project_store = ProjectStore::new();
+    let iteration_store = IterationStore::new();
+
+    let mut project = project_store
+        .load()?
+        .ok_or_else(|| anyhow::anyhow!("No project found. Run 'cowork init' first."))?;
+
+    if !iteration_store.exists(&options.iteration_id) {
+        anyhow::bail!("Iteration '{}' not found", options.iteration_id);
+    }
+    let mut iteration = iteration_store.load(&options.iteration_id)?;
+    let plan = RewindPlan::prepare(&iteration, &options.to)?;
+
+    if options.dry_run {
+        if output.is_jsonl() {
+            return print_json(&plan);
+        }
+        print_plan(&plan, options.discard);
+        println!("\nDry run: nothing was changed.");
+        return Ok(());
+    }
+
+    // Fail on configuration problems before anything is discarded
+    let model_config = load_config()
+        .context("Failed to load LLM configuration. Run 'cowork config' to set up.")?;
+    let model = create_llm_client(&model_config.llm)
+        .context("Failed to create LLM client")?;
+
+    if output.is_text() {
+        print_plan(&plan, options.discard);
+    }
+    let archive_dir = plan.apply(&mut iteration, options.feedback.as_deref(), !options.discard)?;
+    if output.is_text() {
+        if let Some(dir) = archive_dir {
+            println!("📦 Archived to: {}", dir.display());
+        }
+        println!("\n⏪ Rewound to stage '{}'. Re-running...\n", plan.target_stage);
+    }
+
+    let backend = RunBackend::select(options.non_interactive, options.policy, output)?;
+    backend.iteration_event("rewound", &iteration.id, &iteration.title);
+
+    let executor = IterationExecutor::new(backend.interaction.clone());
+    let result = executor
+        .execute(&mut project, &iteration.id, Some(plan.target_stage.clone()), Some(model))
+        .await;
+    backend.finish(&iteration.id, result, "✅ Iteration completed!")
+}
+
+fn print_plan(plan: &RewindPlan, discard: bool) {
+    println!("⏪ Rewind '{}' to stage '{}'", plan.iteration_id, plan.target_stage);
+    println!("   Stages to re-run: {}", plan.stages.join(" → "));
+    if plan.discarded_stages.is_empty() {
+        println!("   No completed stages are discarded");
+    } else {
+        println!("   Completed stages discarded: {}", plan.discarded_stages.join(", "));
+    }
+    if plan.files.is_empty() {
+        println!("   No output files to {}", if discard { "delete" } else { "archive" });
+    } else {
+        println!("   Files to {}:", if discard { "delete" } else { "archive" });
+        for file in &plan.files {
+            println!("     - {}", file);
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Undo a delivery from its rollback snapshot
+
+use anyhow::Result;
+use cowork_core::delivery::DeliverySnapshot;
+use cowork_core::persistence::IterationStore;
+
+/// Restore the project files saved before the iteration's latest delivery
+pub async fn execute(iteration_id: String) -> Result<()> {
+    let store = IterationStore::new();
+    if !store.exists(&iteration_id) {
+        anyhow::bail!("Iteration '{}' not found", iteration_id);
+    }
+
+    let iteration_dir = store.iteration_path(&iteration_id)?;
+    let dir = DeliverySnapshot::latest(&iteration_dir)
+        .ok_or_else(|| anyhow::anyhow!("Iteration '{}' has no delivery snapshot", iteration_id))?;
+    let snapshot = DeliverySnapshot::load(&dir)?;
+
+    snapshot.restore()?;
+    println!(
+        "⏪ Rolled back the delivery of {} ({}): {} files restored, {} created files removed.",
+        iteration_id,
+        snapshot.created_at.format("%Y-%m-%d %H:%M:%S"),
+        snapshot.saved.len(),
+        snapshot.created.len()
+    );
+    println!("   Snapshot: {}", dir.display());
+    Ok(())
+}
@@ -0,0 +1,354 @@
+//! Local HTTP + SSE server for driving iterations remotely
+//!
+//! Events use the `--output jsonl` schema and are streamed from
+//! `GET /api/events`; input requests are listed at `GET /api/requests` and
+//! answered with `POST /api/requests/{request_id}`. Only one iteration runs
+//! at a time because the executor keeps per-process iteration state.
+
+use adk_core::Llm;
+use anyhow::{Context, Result};
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use cowork_core::domain::{InheritanceMode, IterationStatus};
+use cowork_core::interaction::{InteractiveBackend, JsonlEvent, RemoteBackend};
+use cowork_core::llm::{create_llm_client, load_config};
+use cowork_core::persistence::{IterationStore, ProjectStore};
+use cowork_core::pipeline::IterationExecutor;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Environment variable holding the bearer token (alternative to `--token`)
+pub const SERVE_TOKEN_ENV: &str = "COWORK_SERVE_TOKEN";
+
+#[derive(Debug, Clone, Serialize)]
+struct RunningIteration {
+    iteration_id: String,
+    action: &'static str,
+    started_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+struct ServerState {
+    backend: Arc<RemoteBackend>,
+    running: Arc<Mutex<Option<RunningIteration>>>,
+    token: Option<String>,
+}
+
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn not_found(message: impl Into<String>) -> Self {
+        Self(StatusCode::NOT_FOUND, message.into())
+    }
+
+    fn conflict(message: impl Into<String>) -> Self {
+        Self(StatusCode::CONFLICT, message.into())
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<T, ApiError>;
+
+#[derive(Debug, Deserialize)]
+struct CreateIterationRequest {
+    title: String,
+    description: Option<String>,
+    base: Option<String>,
+    /// none | full | partial
+    inherit: Option<String>,
+}
+
+/// Start the server and block until it stops
+pub async fn execute(host: String, port: u16, token: Option<String>) -> Result<()> {
+    if ProjectStore::new().load()?.is_none() {
+        anyhow::bail!("No project found. Run 'cowork init' first.");
+    }
+
+    let backend = Arc::new(RemoteBackend::new());
+    backend.install_tool_notifier();
+    // ask_user and review tools reach remote clients through the global backend
+    cowork_core::tools::set_interaction_backend(backend.clone());
+
+    let token = token.or_else(|| std::env::var(SERVE_TOKEN_ENV).ok()).filter(|t| !t.is_empty());
+    let state = ServerState {
+        backend,
+        running: Arc::new(Mutex::new(None)),
+        token: token.clone(),
+    };
+
+    let app = Router::new()
+        .route("/api/status", get(status))
+        .route("/api/iterations", get(list_iterations).post(create_iteration))
+        .route("/api/iterations/{id}", get(show_iteration))
+        .route("/api/iterations/{id}/continue", post(continue_iteration))
+        .route("/api/iterations/{id}/retry", post(retry_iteration))
+        .route("/api/events", get(events))
+        .route("/api/requests", get(list_requests))
+        .route("/api/requests/{request_id}", post(answer_request))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state);
+
+    let addr = format!("{}:{}", host, port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind {}", addr))?;
+
+    println!("🌐 Cowork server listening on http://{}", addr);
+    println!("   Events: GET /api/events (text/event-stream)");
+    if token.is_some() {
+        println!("   Authentication: Bearer token required");
+    } else if host != "127.0.0.1" && host != "localhost" {
+        println!("⚠️  No token set; anyone who can reach {} can drive this project", addr);
+    }
+
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn require_token(State(state): State<ServerState>, request: Request, next: Next) -> Response {
+    if let Some(token) = &state.token {
+        let authorized = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|v| v == token);
+        if !authorized {
+            return ApiError(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token".to_string()).into_response();
+        }
+    }
+    next.run(request).await
+}
+
+async fn status(State(state): State<ServerState>) -> ApiResult<Json<Value>> {
+    let project = ProjectStore::new()
+        .load()?
+        .ok_or_else(|| ApiError::not_found("No project found"))?;
+    let running = state.running.lock().map(|r| r.clone()).unwrap_or_default();
+
+    Ok(Json(json!({
+        "project": { "id": project.id, "name": project.name },
+        "running": running,
+        "pending_requests": state.backend.pending_requests().len(),
+    })))
+}
+
+async fn list_iterations() -> ApiResult<Json<Value>> {
+    let summaries = IterationStore::new().load_summaries()?;
+    Ok(Json(json!({ "iterations": summaries })))
+}
+
+async fn show_iteration(Path(id): Path<String>) -> ApiResult<Json<Value>> {
+    let store = IterationStore::new();
+    if !store.exists(&id) {
+        return Err(ApiError::not_found(format!("Iteration '{}' not found", id)));
+    }
+    Ok(Json(serde_json::to_value(store.load(&id)?).map_err(anyhow::Error::from)?))
+}
+
+async fn create_iteration(
+    State(state): State<ServerState>,
+    Json(request): Json<CreateIterationRequest>,
+) -> ApiResult<(StatusCode, Json<Value>)> {
+    let mut project = ProjectStore::new()
+        .load()?
+        .ok_or_else(|| ApiError::not_found("No project found"))?;
+    let executor = IterationExecutor::new(state.backend.clone());
+    let description = request.description.unwrap_or_else(|| request.title.clone());
+
+    // Checked before creating anything so a busy or misconfigured server
+    // leaves no trace
+    ensure_idle(&state)?;
+    let model = load_model()?;
+
+    let iteration = match request.base {
+        Some(base_id) => {
+            if !IterationStore::new().exists(&base_id) {
+                return Err(ApiError::not_found(format!("Base iteration '{}' not found", base_id)));
+            }
+            let inheritance = match request.inherit.as_deref().unwrap_or("full") {
+                "none" => InheritanceMode::None,
+                "full" => InheritanceMode::Full,
+                _ => InheritanceMode::Partial,
+            };
+            executor.create_evolution_iteration(&mut project, request.title, description, base_id, inheritance)?
+        }
+        None => executor.create_genesis_iteration(&mut project, request.title, description)?,
+    };
+
+    state.backend.publish(JsonlEvent::Iteration {
+        event: "created".to_string(),
+        iteration_id: iteration.id.clone(),
+        title: Some(iteration.title.clone()),
+    });
+    spawn_run(&state, iteration.id.clone(), "execute", model)?;
+
+    Ok((StatusCode::ACCEPTED, Json(json!({ "iteration_id": iteration.id }))))
+}
+
+async fn continue_iteration(State(state): State<ServerState>, Path(id): Path<String>) -> ApiResult<(StatusCode, Json<Value>)> {
+    start_existing(&state, id, IterationStatus::Paused, "continue")
+}
+
+async fn retry_iteration(State(state): State<ServerState>, Path(id): Path<String>) -> ApiResult<(StatusCode, Json<Value>)> {
+    start_existing(&state, id, IterationStatus::Failed, "retry")
+}
+
+fn start_existing(
+    state: &ServerState,
+    id: String,
+    expected: IterationStatus,
+    action: &'static str,
+) -> ApiResult<(StatusCode, Json<Value>)> {
+    let store = IterationStore::new();
+    if !store.exists(&id) {
+        return Err(ApiError::not_found(format!("Iteration '{}' not found", id)));
+    }
+    let iteration = store.load(&id)?;
+    if iteration.status != expected {
+        return Err(ApiError::conflict(format!(
+            "Iteration '{}' cannot {} (status: {:?})",
+            id, action, iteration.status
+        )));
+    }
+
+    ensure_idle(state)?;
+    let model = load_model()?;
+    state.backend.publish(JsonlEvent::Iteration {
+        event: if action == "continue" { "resumed" } else { "retrying" }.to_string(),
+        iteration_id: id.clone(),
+        title: Some(iteration.title),
+    });
+    spawn_run(state, id.clone(), action, model)?;
+
+    Ok((StatusCode::ACCEPTED, Json(json!({ "iteration_id": id }))))
+}
+
+fn ensure_idle(state: &ServerState) -> ApiResult<()> {
+    let running = state.running.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+    match running.as_ref() {
+        Some(r) => Err(ApiError::conflict(format!("Iteration '{}' is already running", r.iteration_id))),
+        None => Ok(()),
+    }
+}
+
+fn load_model() -> ApiResult<Arc<dyn Llm>> {
+    let model_config = load_config().context("Failed to load LLM configuration. Run 'cowork config' to set up.")?;
+    Ok(create_llm_client(&model_config.llm).context("Failed to create LLM client")?)
+}
+
+/// Run the executor in the background and publish the outcome
+fn spawn_run(state: &ServerState, iteration_id: String, action: &'static str, model: Arc<dyn Llm>) -> ApiResult<()> {
+    let mut project = ProjectStore::new()
+        .load()?
+        .ok_or_else(|| ApiError::not_found("No project found"))?;
+
+    {
+        let mut running = state.running.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        if let Some(r) = running.as_ref() {
+            return Err(ApiError::conflict(format!("Iteration '{}' is already running", r.iteration_id)));
+        }
+        *running = Some(RunningIteration {
+            iteration_id: iteration_id.clone(),
+            action,
+            started_at: Utc::now(),
+        });
+    }
+
+    let state = state.clone();
+    tokio::spawn(async move {
+        let executor = IterationExecutor::new(state.backend.clone() as Arc<dyn InteractiveBackend>);
+        let result = match action {
+            "continue" => executor.continue_iteration(&mut project, &iteration_id, Some(model)).await,
+            "retry" => executor.retry_iteration(&mut project, &iteration_id, Some(model)).await,
+            _ => executor.execute(&mut project, &iteration_id, None, Some(model)).await,
+        };
+
+        let status = IterationStore::new().load(&iteration_id).map(|i| i.status).ok();
+        let outcome = match (&result, status) {
+            (Ok(_), Some(IterationStatus::Completed)) => "completed",
+            (_, Some(IterationStatus::Paused)) => "paused",
+            (Ok(_), _) => "completed",
+            (Err(_), _) => "failed",
+        };
+        if let Err(e) = &result {
+            tracing::error!("[Serve] {} of '{}' failed: {}", action, iteration_id, e);
+        }
+
+        state.backend.cancel_pending();
+        if let Ok(mut running) = state.running.lock() {
+            *running = None;
+        }
+        state.backend.publish(JsonlEvent::Result {
+            status: outcome.to_string(),
+            iteration_id: Some(iteration_id),
+            error: result.err().map(|e| e.to_string()),
+        });
+    });
+
+    Ok(())
+}
+
+async fn events(State(state): State<ServerState>) -> Sse<impl Stream<Item = std::result::Result<Event, Infallible>>> {
+    let receiver = state.backend.subscribe();
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    let event_type = serde_json::to_value(&record.event)
+                        .ok()
+                        .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(str::to_string))
+                        .unwrap_or_else(|| "message".to_string());
+                    let data = serde_json::to_string(&record).unwrap_or_default();
+                    let event = Event::default().event(event_type).data(data);
+                    return Some((Ok(event), receiver));
+                }
+                // A slow client misses events rather than stalling the run
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("[Serve] Event subscriber lagged, skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+async fn list_requests(State(state): State<ServerState>) -> Json<Value> {
+    Json(json!({ "requests": state.backend.pending_requests() }))
+}
+
+/// Body: `{"selection":"yes"}`, `{"text":"..."}` or `{"cancel":true}`
+async fn answer_request(
+    State(state): State<ServerState>,
+    Path(request_id): Path<String>,
+    Json(body): Json<Value>,
+) -> ApiResult<Json<Value>> {
+    let known = state.backend.pending_requests().iter().any(|r| r.request_id == request_id);
+    if !known {
+        return Err(ApiError::not_found(format!("Request '{}' not found", request_id)));
+    }
+    state.backend.submit_response(request_id.clone(), body.to_string()).await?;
+    Ok(Json(json!({ "request_id": request_id, "status": "answered" })))
+}
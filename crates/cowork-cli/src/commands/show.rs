@@ -2,9 +2,10 @@
 
 use anyhow::Result;
 use cowork_core::persistence::{IterationStore, ProjectStore};
+use crate::output::print_json;
 
 /// Show iteration details
-pub async fn execute(iteration_id: Option<String>) -> Result<()> {
+pub async fn execute(iteration_id: Option<String>, as_json: bool) -> Result<()> {
     let project_store = ProjectStore::new();
     let iteration_store = IterationStore::new();
 
@@ -29,6 +30,10 @@ pub async fn execute(iteration_id: Option<String>) -> Result<()> {
 
     let iteration = iteration_store.load(&iteration_id)?;
 
+    if as_json {
+        return print_json(&iteration);
+    }
+
     println!("📋 Iteration Details\n");
     println!("  ID:          {}", iteration.id);
     println!("  Number:      {}", iteration.number);
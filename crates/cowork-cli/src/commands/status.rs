@@ -3,12 +3,41 @@
 use anyhow::Result;
 use cowork_core::domain::IterationStatus;
 use cowork_core::persistence::{IterationStore, ProjectStore};
+use serde_json::json;
+use crate::output::print_json;
 
 /// Show project status
-pub async fn execute() -> Result<()> {
+pub async fn execute(as_json: bool) -> Result<()> {
     let project_store = ProjectStore::new();
     let iteration_store = IterationStore::new();
 
+    if as_json {
+        let project = project_store
+            .load()?
+            .ok_or_else(|| anyhow::anyhow!("No project found. Run 'cowork init' first."))?;
+        let iterations = iteration_store.load_all()?;
+        let count = |status: IterationStatus| iterations.iter().filter(|i| i.status == status).count();
+        let latest = project.get_latest_completed_iteration();
+
+        return print_json(&json!({
+            "project": {
+                "id": project.id,
+                "name": project.name,
+                "created_at": project.created_at,
+                "updated_at": project.updated_at,
+                "current_iteration_id": project.current_iteration_id,
+            },
+            "iterations": {
+                "total": iterations.len(),
+                "completed": count(IterationStatus::Completed),
+                "running": count(IterationStatus::Running),
+                "paused": count(IterationStatus::Paused),
+                "failed": count(IterationStatus::Failed),
+            },
+            "latest_completed": latest,
+        }));
+    }
+
     match project_store.load()? {
         Some(project) => {
             println!("📊 Project Status\n");
@@ -0,0 +1,144 @@
+//! Watch an iteration workspace and re-run tests and lint on every change
+
+use anyhow::{Context, Result};
+use cowork_core::interaction::jsonl::emit_event;
+use cowork_core::interaction::JsonlEvent;
+use cowork_core::llm::{create_llm_client, load_config};
+use cowork_core::persistence::{IterationStore, ProjectStore};
+use cowork_core::pipeline::{IterationExecutor, StageResult};
+use cowork_core::watch::{check_agent_feedback, CheckOptions, CheckRun, WorkspaceWatcher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::output::OutputFormat;
+use crate::run::RunBackend;
+
+/// Changed paths listed per batch in text output
+const MAX_LISTED_FILES: usize = 5;
+
+/// Options for `cowork watch`
+pub struct WatchOptions {
+    pub iteration_id: String,
+    pub debounce_ms: u64,
+    pub checks: CheckOptions,
+    pub run_check_agent: bool,
+    pub non_interactive: bool,
+    pub policy: Option<PathBuf>,
+}
+
+/// Watch the workspace until Ctrl+C
+pub async fn execute(options: WatchOptions, output: OutputFormat) -> Result<()> {
+    let iteration_store = IterationStore::new();
+    if !iteration_store.exists(&options.iteration_id) {
+        anyhow::bail!("Iteration '{}' not found", options.iteration_id);
+    }
+    let workspace = iteration_store.workspace_path(&options.iteration_id)?;
+    if !workspace.exists() {
+        anyhow::bail!("Iteration '{}' has no workspace yet", options.iteration_id);
+    }
+
+    // Resolve the agent's configuration up front so a bad setup fails now,
+    // not at the first change
+    let agent = if options.run_check_agent {
+        let project = ProjectStore::new()
+            .load()?
+            .ok_or_else(|| anyhow::anyhow!("No project found. Run 'cowork init' first."))?;
+        let model_config = load_config()
+            .context("Failed to load LLM configuration. Run 'cowork config' to set up.")?;
+        let model = create_llm_client(&model_config.llm).context("Failed to create LLM client")?;
+        let backend = RunBackend::select(options.non_interactive, options.policy.clone(), output)?;
+        Some((project, model, IterationExecutor::new(backend.interaction.clone())))
+    } else {
+        None
+    };
+
+    let mut watcher = WorkspaceWatcher::new(&workspace, Duration::from_millis(options.debounce_ms));
+    if output.is_text() {
+        println!("👀 Watching {} ({} files)", workspace.display(), watcher.file_count());
+        if agent.is_some() {
+            println!("   The check agent re-runs whenever results change");
+        }
+        println!("   Press Ctrl+C to stop\n");
+        println!("🧪 Running checks...");
+    }
+
+    let mut previous = CheckRun::run(&workspace, &options.checks).await;
+    report(&options.iteration_id, &[], &previous, None, output);
+
+    loop {
+        let changed = tokio::select! {
+            changed = watcher.next_change() => changed,
+            _ = tokio::signal::ctrl_c() => break,
+        };
+
+        if output.is_text() {
+            println!("\n📝 {} file(s) changed:", changed.len());
+            for path in changed.iter().take(MAX_LISTED_FILES) {
+                println!("   {}", path.display());
+            }
+            if changed.len() > MAX_LISTED_FILES {
+                println!("   ... and {} more", changed.len() - MAX_LISTED_FILES);
+            }
+            println!("🧪 Running checks...");
+        }
+
+        let current = CheckRun::run(&workspace, &options.checks).await;
+        report(&options.iteration_id, &changed, &current, Some(&previous), output);
+
+        if let Some((project, model, executor)) = &agent
+            && current.differs_from(&previous)
+        {
+            if output.is_text() {
+                println!("🤖 Results changed, running the check agent...");
+            }
+            let feedback = check_agent_feedback(&previous, &current, &changed);
+            let result = executor
+                .run_single_stage(project, &options.iteration_id, "check", Some(&feedback), Some(model.clone()))
+                .await;
+            if output.is_text() {
+                match result {
+                    Ok(StageResult::Success(_)) => println!("✅ Check agent finished"),
+                    Ok(StageResult::GotoStage(stage, reason)) => println!(
+                        "↪️  Check agent asked to go back to '{}': {}\n   Run: cowork rewind {} --to {}",
+                        stage, reason, options.iteration_id, stage
+                    ),
+                    Ok(other) => println!("⚠️  Check agent stopped: {:?}", other),
+                    Err(e) => println!("❌ Check agent failed: {}", e),
+                }
+            }
+        }
+        previous = current;
+    }
+
+    if output.is_text() {
+        println!("\n👋 Stopped watching");
+    }
+    Ok(())
+}
+
+fn report(iteration_id: &str, changed: &[PathBuf], run: &CheckRun, previous: Option<&CheckRun>, output: OutputFormat) {
+    let regressions = previous.map(|p| run.regressions(p)).unwrap_or_default();
+
+    if output.is_jsonl() {
+        emit_event(JsonlEvent::Checks {
+            iteration_id: iteration_id.to_string(),
+            changed_files: changed.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+            results: serde_json::to_value(run).unwrap_or_default(),
+            regressions,
+        });
+        return;
+    }
+
+    println!("   {}", run.summary());
+    for skipped in &run.skipped {
+        println!("   ⏭️  Skipped {}", skipped);
+    }
+    for regression in &regressions {
+        println!("   🔴 Regression: {}", regression);
+    }
+    if let Some(previous) = previous {
+        for improvement in run.improvements(previous) {
+            println!("   🟢 Improved: {}", improvement);
+        }
+    }
+}
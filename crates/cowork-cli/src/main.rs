@@ -4,9 +4,15 @@
 //! Supports project initialization, iteration management, and project import.
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use std::path::PathBuf;
+
+use output::OutputFormat;
 
 mod commands;
+mod output;
+mod run;
+mod tui;
 mod utils;
 
 #[derive(Parser)]
@@ -19,6 +25,14 @@ struct Cli {
     /// Enable verbose logging
     #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Output format: human-readable text, one JSON event per line, or a full-screen TUI
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    /// Configuration profile to use (also COWORK_PROFILE)
+    #[arg(long, global = true)]
+    profile: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -39,6 +53,14 @@ enum Commands {
         /// Inheritance mode: none, full, or partial
         #[arg(short, long, default_value = "full")]
         inherit: String,
+
+        /// Run without prompts (also enabled by COWORK_NON_INTERACTIVE=1)
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Policy file for non-interactive runs (defaults to COWORK_POLICY or .cowork-v2/policy.toml)
+        #[arg(long)]
+        policy: Option<PathBuf>,
     },
 
     /// List all iterations
@@ -46,18 +68,34 @@ enum Commands {
         /// Show all iterations including completed ones
         #[arg(short, long)]
         all: bool,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show iteration details
     Show {
         /// Iteration ID (defaults to current iteration)
         iteration_id: Option<String>,
+
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
     },
 
     /// Continue a paused iteration
     Continue {
         /// Iteration ID (defaults to current iteration)
         iteration_id: Option<String>,
+
+        /// Run without prompts (also enabled by COWORK_NON_INTERACTIVE=1)
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Policy file for non-interactive runs (defaults to COWORK_POLICY or .cowork-v2/policy.toml)
+        #[arg(long)]
+        policy: Option<PathBuf>,
     },
 
     /// Initialize a new project
@@ -68,7 +106,11 @@ enum Commands {
     },
 
     /// Show project status
-    Status,
+    Status {
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Delete an iteration
     Delete {
@@ -112,40 +154,302 @@ enum Commands {
         template_only: bool,
     },
 
-    /// Configure LLM settings
-    Config,
+    /// Configure LLM settings (interactive without a subcommand)
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+    },
+
+    /// Check configuration, LLM connectivity, coding agent, MCP servers and agent definitions
+    Doctor,
+
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// Print the man page, or write one page per command into a directory
+    Man {
+        /// Directory to write man pages into
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Compare requirements, features, design and plan between two iterations
+    Diff {
+        /// Iteration ID to compare from (e.g. the base iteration)
+        from: String,
+
+        /// Iteration ID to compare to
+        to: String,
+    },
+
+    /// Rewind an iteration to an earlier stage and re-run from there
+    Rewind {
+        /// Iteration ID
+        iteration_id: String,
+
+        /// Stage to restart from (idea, prd, design, plan, coding, check, delivery)
+        #[arg(long)]
+        to: String,
+
+        /// Feedback for the target stage
+        #[arg(short, long)]
+        feedback: Option<String>,
+
+        /// Show what would be discarded without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Delete downstream artifacts instead of archiving them
+        #[arg(long)]
+        discard: bool,
+
+        /// Run without prompts (also enabled by COWORK_NON_INTERACTIVE=1)
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Policy file for non-interactive runs
+        #[arg(long)]
+        policy: Option<PathBuf>,
+    },
+
+    /// Undo the latest delivery of an iteration into the project root
+    RollbackDelivery {
+        /// Iteration ID
+        iteration_id: String,
+    },
+
+    /// Watch an iteration's workspace and re-run tests and lint on changes
+    Watch {
+        /// Iteration ID
+        iteration_id: String,
+
+        /// Quiet period after the last change before checks run (milliseconds)
+        #[arg(long, default_value_t = 1500)]
+        debounce_ms: u64,
+
+        /// Override the auto-detected test command
+        #[arg(long)]
+        test_command: Option<String>,
+
+        /// Override the auto-detected lint command
+        #[arg(long)]
+        lint_command: Option<String>,
+
+        /// Do not run tests
+        #[arg(long)]
+        no_tests: bool,
+
+        /// Do not run the linter
+        #[arg(long)]
+        no_lint: bool,
+
+        /// Re-run the check agent whenever results change
+        #[arg(long)]
+        check_agent: bool,
+
+        /// Run the check agent without prompts
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Policy file for non-interactive runs
+        #[arg(long)]
+        policy: Option<PathBuf>,
+    },
+
+    /// Serve a local HTTP API (with an SSE event feed) for remote clients
+    Serve {
+        /// Address to bind
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 7878)]
+        port: u16,
+
+        /// Require `Authorization: Bearer <token>` (also COWORK_SERVE_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
+    },
+
+    /// Run as an ACP agent over stdio, for editors that speak the Agent Client Protocol
+    Acp,
+
+    /// Expose the project's requirements, design, plan, memory and artifacts as an MCP server
+    McpServe {
+        /// Transport: stdio for clients that spawn the server, or streamable HTTP
+        #[arg(long, value_enum, default_value = "stdio")]
+        transport: commands::mcp_serve::McpTransport,
+
+        /// Address to bind (HTTP)
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on (HTTP)
+        #[arg(short, long, default_value_t = 7879)]
+        port: u16,
+
+        /// Require `Authorization: Bearer <token>` (HTTP; also COWORK_SERVE_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
+    },
+
+    /// Browse, diff and restore revisions of an iteration's documents
+    Artifact {
+        #[command(subcommand)]
+        command: ArtifactCommands,
+    },
+
+    /// Manage cross-project global memory
+    Memory {
+        #[command(subcommand)]
+        command: MemoryCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ArtifactCommands {
+    /// List the revisions of an artifact
+    History {
+        /// Iteration ID
+        iteration_id: String,
+
+        /// Artifact (idea, prd, design, plan, check, delivery)
+        artifact: String,
+
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show what changed between two revisions (default: the last two)
+    Diff {
+        /// Iteration ID
+        iteration_id: String,
+
+        /// Artifact (idea, prd, design, plan, check, delivery)
+        artifact: String,
+
+        /// Older revision number
+        #[arg(long)]
+        from: Option<u32>,
+
+        /// Newer revision number
+        #[arg(long)]
+        to: Option<u32>,
+    },
+
+    /// Make an earlier revision the current artifact again
+    Restore {
+        /// Iteration ID
+        iteration_id: String,
+
+        /// Artifact (idea, prd, design, plan, check, delivery)
+        artifact: String,
+
+        /// Revision number to restore
+        revision: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print a setting, e.g. `llm.model_name`
+    Get {
+        /// Dotted setting key
+        key: String,
+
+        /// Print API keys and agent env values unmasked
+        #[arg(long)]
+        show_secrets: bool,
+    },
+
+    /// Change a setting and save it to the profile's config file
+    Set {
+        /// Dotted setting key
+        key: String,
+
+        /// New value (arrays accept `a,b,c`)
+        value: String,
+    },
+
+    /// Print every setting
+    List {
+        /// Print API keys and agent env values unmasked
+        #[arg(long)]
+        show_secrets: bool,
+
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List configuration profiles
+    Profiles,
+}
+
+#[derive(Subcommand)]
+enum MemoryCommands {
+    /// List project decisions and patterns
+    Project,
+
+    /// List global memory entries
+    Global,
+
+    /// Promote a project decision or pattern into global memory
+    Promote {
+        /// Decision (dec-...) or pattern (pat-...) ID
+        id: String,
+    },
+
+    /// Remove an entry from global memory
+    Forget {
+        /// Decision or pattern ID
+        id: String,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    // Initialize logging
-    let log_filter = if cli.verbose { "debug" } else { "info" };
+    // Initialize logging (the TUI owns the terminal, so logs are dropped there)
+    let log_filter = if cli.output.is_tui() {
+        "off"
+    } else if cli.verbose {
+        "debug"
+    } else {
+        "info"
+    };
     tracing_subscriber::fmt()
         .with_writer(std::io::stderr)
         .with_env_filter(log_filter)
         .init();
 
+    cowork_core::llm::config::set_active_profile(cli.profile.as_deref())?;
+
     // Dispatch to command handlers
     match cli.command {
-        Commands::Iter { title, description, base, inherit } => {
-            commands::iter(title, description, base, inherit).await?
+        Commands::Iter { title, description, base, inherit, non_interactive, policy } => {
+            commands::iter(title, description, base, inherit, non_interactive, policy, cli.output).await?
         }
-        Commands::List { all } => {
-            commands::list(all).await?
+        Commands::List { all, json } => {
+            commands::list(all, json || cli.output.is_jsonl()).await?
         }
-        Commands::Show { iteration_id } => {
-            commands::show(iteration_id).await?
+        Commands::Show { iteration_id, json } => {
+            commands::show(iteration_id, json || cli.output.is_jsonl()).await?
         }
-        Commands::Continue { iteration_id } => {
-            commands::continue_iteration(iteration_id).await?
+        Commands::Continue { iteration_id, non_interactive, policy } => {
+            commands::continue_iteration(iteration_id, non_interactive, policy, cli.output).await?
         }
         Commands::Init { name } => {
             commands::init(name).await?
         }
-        Commands::Status => {
-            commands::status().await?
+        Commands::Status { json } => {
+            commands::status(json || cli.output.is_jsonl()).await?
         }
         Commands::Delete { iteration_id } => {
             commands::delete(iteration_id).await?
@@ -156,9 +460,94 @@ async fn main() -> Result<()> {
         Commands::Import { path, name, idea, prd, design, plan, template_only } => {
             commands::import(path, name, idea, prd, design, plan, template_only).await?
         }
-        Commands::Config => {
-            commands::config().await?
+        Commands::Config { command } => match command {
+            None => commands::config().await?,
+            Some(ConfigCommands::Get { key, show_secrets }) => {
+                commands::config::get(key, show_secrets).await?
+            }
+            Some(ConfigCommands::Set { key, value }) => commands::config::set(key, value).await?,
+            Some(ConfigCommands::List { show_secrets, json }) => {
+                commands::config::list(show_secrets, json || cli.output.is_jsonl()).await?
+            }
+            Some(ConfigCommands::Profiles) => commands::config::profiles().await?,
+        },
+        Commands::Doctor => {
+            commands::doctor().await?
+        }
+        Commands::Completions { shell } => {
+            commands::completions::completions(Cli::command(), shell)?
+        }
+        Commands::Man { out_dir } => {
+            commands::completions::man(Cli::command(), out_dir)?
+        }
+        Commands::Diff { from, to } => {
+            commands::diff(from, to).await?
+        }
+        Commands::Rewind { iteration_id, to, feedback, dry_run, discard, non_interactive, policy } => {
+            let options = commands::rewind::RewindOptions {
+                iteration_id,
+                to,
+                feedback,
+                dry_run,
+                discard,
+                non_interactive,
+                policy,
+            };
+            commands::rewind(options, cli.output).await?
+        }
+        Commands::RollbackDelivery { iteration_id } => {
+            commands::rollback_delivery(iteration_id).await?
+        }
+        Commands::Watch {
+            iteration_id,
+            debounce_ms,
+            test_command,
+            lint_command,
+            no_tests,
+            no_lint,
+            check_agent,
+            non_interactive,
+            policy,
+        } => {
+            let options = commands::watch::WatchOptions {
+                iteration_id,
+                debounce_ms,
+                checks: cowork_core::watch::CheckOptions {
+                    tests: !no_tests,
+                    lint: !no_lint,
+                    test_command,
+                    lint_command,
+                },
+                run_check_agent: check_agent,
+                non_interactive,
+                policy,
+            };
+            commands::watch(options, cli.output).await?
+        }
+        Commands::Serve { host, port, token } => {
+            commands::serve(host, port, token).await?
+        }
+        Commands::Acp => commands::acp().await?,
+        Commands::McpServe { transport, host, port, token } => {
+            commands::mcp_serve(transport, host, port, token).await?
         }
+        Commands::Artifact { command } => match command {
+            ArtifactCommands::History { iteration_id, artifact, json } => {
+                commands::artifact::history(iteration_id, artifact, json || cli.output.is_jsonl()).await?
+            }
+            ArtifactCommands::Diff { iteration_id, artifact, from, to } => {
+                commands::artifact::diff(iteration_id, artifact, from, to).await?
+            }
+            ArtifactCommands::Restore { iteration_id, artifact, revision } => {
+                commands::artifact::restore(iteration_id, artifact, revision).await?
+            }
+        },
+        Commands::Memory { command } => match command {
+            MemoryCommands::Project => commands::memory::list_project().await?,
+            MemoryCommands::Global => commands::memory::list_global().await?,
+            MemoryCommands::Promote { id } => commands::memory::promote(id).await?,
+            MemoryCommands::Forget { id } => commands::memory::forget(id).await?,
+        },
     }
 
     Ok(())
@@ -0,0 +1,36 @@
+//! Output format selection (`--output text|jsonl|tui`, `--json`)
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// One JSON event per line (see cowork_core::interaction::jsonl)
+    Jsonl,
+    /// Full-screen terminal UI for `iter`/`continue`
+    Tui,
+}
+
+impl OutputFormat {
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+
+    pub fn is_jsonl(self) -> bool {
+        self == OutputFormat::Jsonl
+    }
+
+    pub fn is_tui(self) -> bool {
+        self == OutputFormat::Tui
+    }
+}
+
+/// Print a JSON document for `--json` output
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
@@ -0,0 +1,165 @@
+//! Interaction backend selection and outcome reporting shared by `iter`
+//! and `continue` (interactive, non-interactive, JSON Lines and TUI runs)
+
+use anyhow::Result;
+use cowork_core::domain::IterationStatus;
+use cowork_core::interaction::headless::{non_interactive_from_env, EXIT_PAUSED_FOR_HUMAN};
+use cowork_core::interaction::jsonl::emit_event;
+use cowork_core::interaction::{
+    CliBackend, HeadlessBackend, HeadlessPolicy, InteractiveBackend, JsonlBackend, JsonlEvent,
+};
+use cowork_core::persistence::IterationStore;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::output::OutputFormat;
+use crate::tui::TuiBackend;
+
+/// Interaction backend selected for a run
+pub struct RunBackend {
+    pub interaction: Arc<dyn InteractiveBackend>,
+    pub headless: Option<Arc<HeadlessBackend>>,
+    pub tui: Option<Arc<TuiBackend>>,
+    pub output: OutputFormat,
+}
+
+impl RunBackend {
+    /// Pick the output backend (text, JSON lines or TUI) and wrap it in the
+    /// headless backend when `--non-interactive` or `COWORK_NON_INTERACTIVE`
+    /// is set
+    pub fn select(non_interactive: bool, policy: Option<PathBuf>, output: OutputFormat) -> Result<Self> {
+        let non_interactive = non_interactive || non_interactive_from_env();
+        // Resolve the policy before the TUI takes over the terminal so that
+        // errors are readable
+        let policy = if non_interactive {
+            Some(HeadlessPolicy::resolve(policy.as_deref())?)
+        } else {
+            None
+        };
+
+        let mut tui = None;
+        let display: Arc<dyn InteractiveBackend> = match output {
+            OutputFormat::Jsonl => {
+                let backend = JsonlBackend::new();
+                backend.install_tool_notifier();
+                Arc::new(backend)
+            }
+            OutputFormat::Tui => {
+                let backend = TuiBackend::start()?;
+                tui = Some(backend.clone());
+                backend
+            }
+            OutputFormat::Text => Arc::new(CliBackend::new()),
+        };
+
+        let Some(policy) = policy else {
+            if let Some(tui) = &tui {
+                // Route ask_user and review tools into the TUI as well
+                cowork_core::tools::set_interaction_backend(tui.clone());
+            }
+            return Ok(Self {
+                interaction: display,
+                headless: None,
+                tui,
+                output,
+            });
+        };
+
+        if output.is_text() {
+            println!(
+                "🤖 Non-interactive mode (gates: {:?}, {} pre-supplied answer(s))",
+                policy.gates,
+                policy.answers.len()
+            );
+        }
+
+        let backend = Arc::new(HeadlessBackend::with_output(policy, display));
+        // ask_user and review tools reach the user through the global backend
+        cowork_core::tools::set_interaction_backend(backend.clone());
+
+        Ok(Self {
+            interaction: backend.clone(),
+            headless: Some(backend),
+            tui,
+            output,
+        })
+    }
+
+    /// Announce an iteration lifecycle event (JSON Lines event or TUI title)
+    pub fn iteration_event(&self, event: &str, iteration_id: &str, title: &str) {
+        if let Some(tui) = &self.tui {
+            tui.set_title(format!("{} ({})", title, iteration_id));
+        }
+        if self.output.is_jsonl() {
+            emit_event(JsonlEvent::Iteration {
+                event: event.to_string(),
+                iteration_id: iteration_id.to_string(),
+                title: Some(title.to_string()),
+            });
+        }
+    }
+
+    /// Report the outcome of a run. Failures propagate as errors (exit 1);
+    /// non-interactive runs stopped at a gate exit with
+    /// `EXIT_PAUSED_FOR_HUMAN`.
+    pub fn finish(&self, iteration_id: &str, result: Result<()>, success_message: &str) -> Result<()> {
+        // The outcome is printed as text once the terminal is restored
+        if let Some(tui) = &self.tui {
+            tui.stop();
+        }
+
+        let jsonl = self.output.is_jsonl();
+        let status = IterationStore::new().load(iteration_id).map(|i| i.status).ok();
+
+        if let Some(headless) = &self.headless {
+            let unanswered = headless.unanswered_questions();
+            if !unanswered.is_empty() && !jsonl {
+                println!("\n⚠️  {} question(s) had no pre-supplied answer:", unanswered.len());
+                for question in &unanswered {
+                    println!("   - {}", question);
+                }
+            }
+
+            if status == Some(IterationStatus::Paused) {
+                let gate = headless.blocking_gate();
+                if jsonl {
+                    emit_event(JsonlEvent::Result {
+                        status: "paused".to_string(),
+                        iteration_id: Some(iteration_id.to_string()),
+                        error: gate.map(|g| g.reason),
+                    });
+                } else {
+                    println!("\n⏸️  Iteration '{}' is paused for human review.", iteration_id);
+                    if let Some(gate) = gate {
+                        println!("   Gate: {} ({})", gate.stage.as_deref().unwrap_or("unknown"), gate.reason);
+                    }
+                    println!("   Resume with: cowork continue {}", iteration_id);
+                }
+                std::process::exit(EXIT_PAUSED_FOR_HUMAN);
+            }
+        }
+
+        let result = result.and_then(|_| {
+            if self.headless.is_some() && status != Some(IterationStatus::Completed) {
+                anyhow::bail!("Iteration '{}' ended with status {:?}", iteration_id, status);
+            }
+            Ok(())
+        });
+
+        match &result {
+            Ok(_) if jsonl => emit_event(JsonlEvent::Result {
+                status: "completed".to_string(),
+                iteration_id: Some(iteration_id.to_string()),
+                error: None,
+            }),
+            Ok(_) => println!("\n{}", success_message),
+            Err(e) if jsonl => emit_event(JsonlEvent::Result {
+                status: if status == Some(IterationStatus::Paused) { "paused" } else { "failed" }.to_string(),
+                iteration_id: Some(iteration_id.to_string()),
+                error: Some(e.to_string()),
+            }),
+            Err(e) => println!("\n❌ Iteration failed: {}", e),
+        }
+        result
+    }
+}
@@ -0,0 +1,563 @@
+//! Full-screen terminal UI backend (`--output tui`)
+//!
+//! A dedicated thread owns the terminal: it redraws from shared state and
+//! handles keys. The `InteractiveBackend` methods only update that state;
+//! input requests are answered in place through a oneshot channel.
+
+mod review;
+mod ui;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use cowork_core::interaction::{
+    InputOption, InputResponse, InteractiveBackend, MessageContext, MessageLevel, MessageType, ProgressInfo,
+};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+use review::ArtifactReview;
+
+const MAX_TRANSCRIPT_LINES: usize = 5000;
+const MAX_TOOL_ENTRIES: usize = 500;
+const TICK: Duration = Duration::from_millis(100);
+/// Progress task id reported by the executor for stage transitions
+const STAGES_TASK_ID: &str = "stages";
+/// Transcript for messages without an agent
+const PIPELINE_AGENT: &str = "Pipeline";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    Text,
+    Thinking,
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl From<MessageLevel> for LineKind {
+    fn from(level: MessageLevel) -> Self {
+        match level {
+            MessageLevel::Info | MessageLevel::Debug => LineKind::Info,
+            MessageLevel::Success => LineKind::Success,
+            MessageLevel::Warning => LineKind::Warning,
+            MessageLevel::Error => LineKind::Error,
+        }
+    }
+}
+
+struct TranscriptLine {
+    kind: LineKind,
+    text: String,
+}
+
+/// Everything one agent said, in order
+struct Transcript {
+    agent: String,
+    lines: Vec<TranscriptLine>,
+    /// The last line still receives streaming chunks
+    open: bool,
+}
+
+impl Transcript {
+    fn new(agent: &str) -> Self {
+        Self {
+            agent: agent.to_string(),
+            lines: Vec::new(),
+            open: false,
+        }
+    }
+
+    fn push_stream(&mut self, chunk: &str, kind: LineKind) {
+        for (i, part) in chunk.split('\n').enumerate() {
+            if i > 0 {
+                self.open = false;
+            }
+            let continues = self.open && self.lines.last().is_some_and(|l| l.kind == kind);
+            if !continues {
+                self.lines.push(TranscriptLine { kind, text: String::new() });
+                self.open = true;
+            }
+            if let Some(line) = self.lines.last_mut() {
+                line.text.push_str(part);
+            }
+        }
+        self.trim();
+    }
+
+    fn push_message(&mut self, kind: LineKind, content: &str) {
+        if self.open && self.lines.last().is_some_and(|l| l.text.is_empty()) {
+            self.lines.pop();
+        }
+        self.open = false;
+        for line in content.lines() {
+            self.lines.push(TranscriptLine { kind, text: line.to_string() });
+        }
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        if self.lines.len() > MAX_TRANSCRIPT_LINES {
+            let excess = self.lines.len() - MAX_TRANSCRIPT_LINES;
+            self.lines.drain(..excess);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToolStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+struct ToolEntry {
+    agent: String,
+    tool: String,
+    detail: String,
+    status: ToolStatus,
+}
+
+/// An input request waiting for the user
+struct PendingInput {
+    prompt: String,
+    options: Vec<InputOption>,
+    /// Free text (feedback, ask_user) instead of an option list
+    text_mode: bool,
+    initial_content: Option<String>,
+    input: String,
+    selected: usize,
+    review: Option<ArtifactReview>,
+    show_diff: bool,
+    scroll: u16,
+    responder: Option<oneshot::Sender<InputResponse>>,
+}
+
+struct TuiState {
+    title: String,
+    stage: Option<String>,
+    stage_progress: Option<ProgressInfo>,
+    stages_seen: Vec<String>,
+    tasks: BTreeMap<String, ProgressInfo>,
+    transcripts: Vec<Transcript>,
+    selected: usize,
+    /// Lines scrolled back from the bottom; `None` follows new output
+    scroll_back: Option<usize>,
+    tools: VecDeque<ToolEntry>,
+    pending: Option<PendingInput>,
+    /// Artifact content shown at the last gate, by artifact type
+    revisions: HashMap<String, String>,
+}
+
+impl TuiState {
+    fn new() -> Self {
+        Self {
+            title: String::new(),
+            stage: None,
+            stage_progress: None,
+            stages_seen: Vec::new(),
+            tasks: BTreeMap::new(),
+            transcripts: vec![Transcript::new(PIPELINE_AGENT)],
+            selected: 0,
+            scroll_back: None,
+            tools: VecDeque::new(),
+            pending: None,
+            revisions: HashMap::new(),
+        }
+    }
+
+    fn transcript(&mut self, agent: &str) -> &mut Transcript {
+        let idx = match self.transcripts.iter().position(|t| t.agent == agent) {
+            Some(idx) => idx,
+            None => {
+                self.transcripts.push(Transcript::new(agent));
+                self.transcripts.len() - 1
+            }
+        };
+        &mut self.transcripts[idx]
+    }
+
+    fn enter_stage(&mut self, stage: &str) {
+        if !self.stages_seen.iter().any(|s| s == stage) {
+            self.stages_seen.push(stage.to_string());
+        }
+        self.stage = Some(stage.to_string());
+    }
+
+    fn tool_call(&mut self, agent: &str, tool: &str, arguments: &Value) {
+        self.tools.push_back(ToolEntry {
+            agent: agent.to_string(),
+            tool: tool.to_string(),
+            detail: summarize_arguments(arguments),
+            status: ToolStatus::Running,
+        });
+        if self.tools.len() > MAX_TOOL_ENTRIES {
+            self.tools.pop_front();
+        }
+    }
+
+    fn tool_result(&mut self, agent: &str, tool: &str, success: bool, result: &str) {
+        let status = if success { ToolStatus::Succeeded } else { ToolStatus::Failed };
+        let running = self
+            .tools
+            .iter_mut()
+            .rev()
+            .find(|t| t.tool == tool && t.status == ToolStatus::Running);
+        match running {
+            Some(entry) => entry.status = status,
+            None => {
+                self.tool_call(agent, tool, &Value::Null);
+                if let Some(entry) = self.tools.back_mut() {
+                    entry.status = status;
+                    entry.detail = clip(result.lines().next().unwrap_or_default(), 80);
+                }
+            }
+        }
+    }
+
+    /// Answer the pending request and return to the transcript view
+    fn respond(&mut self, response: InputResponse) {
+        if let Some(mut pending) = self.pending.take()
+            && let Some(responder) = pending.responder.take()
+        {
+            let _ = responder.send(response);
+        }
+    }
+}
+
+/// Short `key=value` rendering of tool arguments
+fn summarize_arguments(arguments: &Value) -> String {
+    let Some(obj) = arguments.as_object() else {
+        return String::new();
+    };
+    let parts: Vec<String> = obj
+        .iter()
+        .map(|(key, value)| match value {
+            Value::String(s) if s.chars().count() <= 40 && !s.contains('\n') => format!("{}={}", key, s),
+            Value::Number(_) | Value::Bool(_) => format!("{}={}", key, value),
+            _ => key.clone(),
+        })
+        .collect();
+    clip(&parts.join(" "), 80)
+}
+
+/// Char-safe truncation with an ellipsis
+fn clip(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(max_chars).collect::<String>())
+    }
+}
+
+pub struct TuiBackend {
+    state: Arc<Mutex<TuiState>>,
+    running: Arc<AtomicBool>,
+    render_thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl TuiBackend {
+    /// Take over the terminal and start the render thread
+    pub fn start() -> Result<Arc<Self>> {
+        let state = Arc::new(Mutex::new(TuiState::new()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let notify_state = state.clone();
+        // notify_tool_result passes `Null` arguments (see JsonlBackend)
+        cowork_core::tools::set_tool_notify_callback(
+            move |tool_name: &str, args: &Value, flag: bool, result: &str, agent_name: &str| {
+                if let Ok(mut state) = notify_state.lock() {
+                    if args.is_null() {
+                        state.tool_result(agent_name, tool_name, flag, result);
+                    } else {
+                        state.tool_call(agent_name, tool_name, args);
+                    }
+                }
+            },
+        );
+
+        let terminal = ratatui::try_init()?;
+        let handle = std::thread::spawn({
+            let state = state.clone();
+            let running = running.clone();
+            move || run_loop(terminal, state, running)
+        });
+
+        Ok(Arc::new(Self {
+            state,
+            running,
+            render_thread: Mutex::new(Some(handle)),
+        }))
+    }
+
+    pub fn set_title(&self, title: String) {
+        if let Ok(mut state) = self.state.lock() {
+            state.title = title;
+        }
+    }
+
+    /// Stop rendering and restore the terminal
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.render_thread.lock().ok().and_then(|mut h| h.take()) {
+            let _ = handle.join();
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut TuiState)) {
+        if let Ok(mut state) = self.state.lock() {
+            f(&mut state);
+        }
+    }
+}
+
+impl Drop for TuiBackend {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn run_loop(mut terminal: DefaultTerminal, state: Arc<Mutex<TuiState>>, running: Arc<AtomicBool>) {
+    while running.load(Ordering::SeqCst) {
+        if let Ok(state) = state.lock() {
+            let _ = terminal.draw(|frame| ui::draw(frame, &state));
+        }
+
+        if !matches!(event::poll(TICK), Ok(true)) {
+            continue;
+        }
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                handle_key(&mut terminal, &state, key);
+            }
+            Ok(Event::Resize(..)) => {
+                let _ = terminal.clear();
+            }
+            _ => {}
+        }
+    }
+    ratatui::restore();
+}
+
+fn handle_key(terminal: &mut DefaultTerminal, state: &Arc<Mutex<TuiState>>, key: KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    if ctrl && key.code == KeyCode::Char('l') {
+        let _ = terminal.clear();
+        return;
+    }
+
+    // The external editor needs the terminal, so it runs without the lock
+    let editor_request = {
+        let Ok(mut state) = state.lock() else { return };
+
+        if ctrl && key.code == KeyCode::Char('c') {
+            if state.pending.is_some() {
+                state.respond(InputResponse::Cancel);
+                return;
+            }
+            drop(state);
+            ratatui::restore();
+            eprintln!("Interrupted. Resume with: cowork continue");
+            std::process::exit(130);
+        }
+
+        if state.pending.is_some() {
+            handle_input_key(&mut state, key)
+        } else {
+            handle_transcript_key(&mut state, key);
+            None
+        }
+    };
+
+    if let Some(initial) = editor_request {
+        ratatui::restore();
+        let edited = dialoguer::Editor::new().require_save(true).edit(&initial);
+        if let Ok(new_terminal) = ratatui::try_init() {
+            *terminal = new_terminal;
+        }
+        if let Ok(Some(text)) = edited
+            && !text.trim().is_empty()
+            && let Ok(mut state) = state.lock()
+        {
+            state.respond(InputResponse::Text(text));
+        }
+    }
+}
+
+fn handle_transcript_key(state: &mut TuiState, key: KeyEvent) {
+    let count = state.transcripts.len();
+    let lines = state.transcripts.get(state.selected).map_or(0, |t| t.lines.len());
+    let scroll_by = |state: &mut TuiState, delta: isize| {
+        let back = state.scroll_back.unwrap_or(0) as isize + delta;
+        state.scroll_back = (back > 0).then(|| (back as usize).min(lines));
+    };
+
+    match key.code {
+        KeyCode::Tab => {
+            state.selected = (state.selected + 1) % count;
+            state.scroll_back = None;
+        }
+        KeyCode::BackTab => {
+            state.selected = (state.selected + count - 1) % count;
+            state.scroll_back = None;
+        }
+        KeyCode::Up | KeyCode::Char('k') => scroll_by(state, 1),
+        KeyCode::Down | KeyCode::Char('j') => scroll_by(state, -1),
+        KeyCode::PageUp => scroll_by(state, 10),
+        KeyCode::PageDown => scroll_by(state, -10),
+        KeyCode::Home => state.scroll_back = Some(lines),
+        KeyCode::End => state.scroll_back = None,
+        _ => {}
+    }
+}
+
+/// Returns the initial editor content when the external editor was requested
+fn handle_input_key(state: &mut TuiState, key: KeyEvent) -> Option<String> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let pending = state.pending.as_mut()?;
+
+    match key.code {
+        KeyCode::PageUp => pending.scroll = pending.scroll.saturating_sub(10),
+        KeyCode::PageDown => pending.scroll = pending.scroll.saturating_add(10),
+        KeyCode::Char('e') if ctrl => {
+            let initial = if pending.input.is_empty() {
+                pending.initial_content.clone().unwrap_or_default()
+            } else {
+                pending.input.clone()
+            };
+            return Some(initial);
+        }
+        _ if pending.text_mode => match key.code {
+            KeyCode::Enter if !pending.input.trim().is_empty() => {
+                let text = pending.input.trim().to_string();
+                state.respond(InputResponse::Text(text));
+            }
+            KeyCode::Esc => state.respond(InputResponse::Cancel),
+            KeyCode::Backspace => {
+                pending.input.pop();
+            }
+            KeyCode::Char(c) if !ctrl => pending.input.push(c),
+            _ => {}
+        },
+        KeyCode::Up | KeyCode::Char('k') => pending.selected = pending.selected.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => {
+            pending.selected = (pending.selected + 1).min(pending.options.len().saturating_sub(1));
+        }
+        KeyCode::Char('d') if pending.review.as_ref().is_some_and(|r| r.previous.is_some()) => {
+            pending.show_diff = !pending.show_diff;
+            pending.scroll = 0;
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            let n = c.to_digit(10).unwrap_or(0) as usize;
+            if let Some(option) = n.checked_sub(1).and_then(|i| pending.options.get(i)) {
+                let id = option.id.clone();
+                state.respond(InputResponse::Selection(id));
+            }
+        }
+        KeyCode::Enter => {
+            if let Some(option) = pending.options.get(pending.selected) {
+                let id = option.id.clone();
+                state.respond(InputResponse::Selection(id));
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+#[async_trait]
+impl InteractiveBackend for TuiBackend {
+    async fn show_message(&self, level: MessageLevel, content: String) {
+        self.update(|state| state.transcript(PIPELINE_AGENT).push_message(level.into(), &content));
+    }
+
+    async fn show_message_with_context(&self, level: MessageLevel, content: String, context: MessageContext) {
+        self.update(|state| {
+            if let Some(stage) = &context.stage_name {
+                state.enter_stage(stage);
+            }
+            match &context.message_type {
+                MessageType::ToolCall { tool_name, arguments } => {
+                    state.tool_call(&context.agent_name, tool_name, arguments);
+                }
+                MessageType::ToolResult { tool_name, success } => {
+                    state.tool_result(&context.agent_name, tool_name, *success, &content);
+                }
+                MessageType::Thinking => {
+                    state.transcript(&context.agent_name).push_message(LineKind::Thinking, &content);
+                }
+                MessageType::Streaming { .. } => {
+                    state.transcript(&context.agent_name).push_stream(&content, LineKind::Text);
+                }
+                MessageType::Normal => {
+                    state.transcript(&context.agent_name).push_message(level.into(), &content);
+                }
+            }
+        });
+    }
+
+    async fn send_streaming(&self, content: String, agent_name: &str, is_thinking: bool) {
+        let kind = if is_thinking { LineKind::Thinking } else { LineKind::Text };
+        self.update(|state| state.transcript(agent_name).push_stream(&content, kind));
+    }
+
+    async fn send_tool_call(&self, tool_name: &str, arguments: &Value, agent_name: &str) {
+        self.update(|state| state.tool_call(agent_name, tool_name, arguments));
+    }
+
+    async fn send_tool_result(&self, tool_name: &str, result: &str, success: bool, agent_name: &str) {
+        self.update(|state| state.tool_result(agent_name, tool_name, success, result));
+    }
+
+    async fn request_input(&self, prompt: &str, options: Vec<InputOption>, initial_content: Option<String>) -> Result<InputResponse> {
+        let (tx, rx) = oneshot::channel();
+
+        self.update(|state| {
+            let (prompt, artifact_type) = review::split_prompt(prompt);
+            let review = artifact_type.and_then(|t| ArtifactReview::load(&t, &mut state.revisions));
+            // The artifact is already shown in place
+            let options: Vec<InputOption> = options
+                .into_iter()
+                .filter(|o| review.is_none() || o.id != "view_artifact")
+                .collect();
+
+            state.pending = Some(PendingInput {
+                prompt,
+                text_mode: options.is_empty() || initial_content.is_some(),
+                options,
+                initial_content,
+                input: String::new(),
+                selected: 0,
+                show_diff: review.as_ref().is_some_and(|r| r.previous.is_some()),
+                review,
+                scroll: 0,
+                responder: Some(tx),
+            });
+        });
+
+        Ok(rx.await.unwrap_or(InputResponse::Cancel))
+    }
+
+    async fn show_progress(&self, task_id: String, progress: ProgressInfo) {
+        self.update(|state| {
+            if task_id == STAGES_TASK_ID {
+                state.enter_stage(&progress.message);
+                state.stage_progress = Some(progress);
+            } else {
+                state.tasks.insert(task_id, progress);
+            }
+        });
+    }
+
+    async fn submit_response(&self, _request_id: String, _response: String) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! In-place artifact review for confirmation gates
+//!
+//! Gate prompts carry an `[ARTIFACT_TYPE:...]` marker. The artifact is read
+//! from the current iteration and diffed against its previous recorded
+//! revision, the revision shown at the previous gate of this run, or the base
+//! iteration's copy for the first review of an evolution iteration.
+
+use cowork_core::persistence::{artifact_path, get_iteration_id, ArtifactHistory, IterationStore};
+use similar::{ChangeTag, TextDiff};
+use std::collections::HashMap;
+
+const ARTIFACT_MARKER: &str = "[ARTIFACT_TYPE:";
+
+/// Context lines kept around each change
+const DIFF_CONTEXT: usize = 3;
+
+/// Split a gate prompt into the visible text and its artifact type
+pub fn split_prompt(prompt: &str) -> (String, Option<String>) {
+    let Some(start) = prompt.find(ARTIFACT_MARKER) else {
+        return (prompt.to_string(), None);
+    };
+    let rest = &prompt[start + ARTIFACT_MARKER.len()..];
+    let artifact_type = rest.split(']').next().unwrap_or_default().trim().to_string();
+    let text = prompt[..start].trim_end().to_string();
+    (text, (!artifact_type.is_empty()).then_some(artifact_type))
+}
+
+/// Markdown artifact written for an artifact type (code has none)
+fn artifact_file(artifact_type: &str) -> Option<&'static str> {
+    match artifact_type {
+        "idea" => Some("idea.md"),
+        "requirements" => Some("prd.md"),
+        "design" => Some("design.md"),
+        "plan" => Some("plan.md"),
+        _ => None,
+    }
+}
+
+pub struct Revision {
+    pub label: String,
+    pub content: String,
+}
+
+pub struct ArtifactReview {
+    pub filename: String,
+    pub content: String,
+    pub previous: Option<Revision>,
+}
+
+impl ArtifactReview {
+    /// Load the artifact for a gate and remember it in `seen` as the
+    /// previous revision for the next gate of the same type
+    pub fn load(artifact_type: &str, seen: &mut HashMap<String, String>) -> Option<Self> {
+        let filename = artifact_file(artifact_type)?;
+        let content = std::fs::read_to_string(artifact_path(filename).ok()?).ok()?;
+
+        let shown_before = seen.insert(artifact_type.to_string(), content.clone());
+        let previous = recorded_revision_before(filename, &content)
+            .or_else(|| {
+                shown_before.map(|content| Revision {
+                    label: "previous revision".to_string(),
+                    content,
+                })
+            })
+            .or_else(|| {
+                base_iteration_artifact(filename).map(|(id, content)| Revision {
+                    label: format!("base iteration {}", id),
+                    content,
+                })
+            });
+
+        Some(Self {
+            filename: filename.to_string(),
+            content,
+            previous,
+        })
+    }
+
+    pub fn diff(&self) -> Vec<DiffLine> {
+        self.previous
+            .as_ref()
+            .map(|p| diff_lines(&p.content, &self.content))
+            .unwrap_or_default()
+    }
+}
+
+/// Latest recorded revision whose content differs from `content`
+fn recorded_revision_before(filename: &str, content: &str) -> Option<Revision> {
+    let history = ArtifactHistory::open(&get_iteration_id()?, filename).ok()?;
+    history.revisions().ok()?.iter().rev().find_map(|r| {
+        let previous = history.load(r.revision).ok()?;
+        (previous != content).then(|| Revision {
+            label: format!("r{} by {}", r.revision, r.author),
+            content: previous,
+        })
+    })
+}
+
+fn base_iteration_artifact(filename: &str) -> Option<(String, String)> {
+    let store = IterationStore::new();
+    let base_id = store.load(&get_iteration_id()?).ok()?.base_iteration_id?;
+    let path = store.iteration_path(&base_id).ok()?.join("artifacts").join(filename);
+    let content = std::fs::read_to_string(path).ok()?;
+    Some((base_id, content))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Context,
+    Added,
+    Removed,
+    /// Separator between hunks
+    Hunk,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// Line diff grouped into hunks; empty when both texts are equal
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let diff = TextDiff::from_lines(old, new);
+    let mut out = Vec::new();
+
+    for (i, group) in diff.grouped_ops(DIFF_CONTEXT).iter().enumerate() {
+        if i > 0 {
+            out.push(DiffLine {
+                kind: DiffKind::Hunk,
+                text: "…".to_string(),
+            });
+        }
+        for op in group {
+            for change in diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    ChangeTag::Equal => DiffKind::Context,
+                    ChangeTag::Insert => DiffKind::Added,
+                    ChangeTag::Delete => DiffKind::Removed,
+                };
+                out.push(DiffLine {
+                    kind,
+                    text: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                });
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_prompt() {
+        let (text, artifact) = split_prompt("Stage 'prd' completed. Please review.\n[ARTIFACT_TYPE:requirements]");
+        assert_eq!(text, "Stage 'prd' completed. Please review.");
+        assert_eq!(artifact.as_deref(), Some("requirements"));
+
+        let (text, artifact) = split_prompt("Which database?");
+        assert_eq!(text, "Which database?");
+        assert!(artifact.is_none());
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "# PRD\n\n- login\n- logout\n";
+        let new = "# PRD\n\n- login\n- export\n";
+
+        let diff = diff_lines(old, new);
+        let changed: Vec<_> = diff
+            .iter()
+            .filter(|l| l.kind != DiffKind::Context)
+            .map(|l| (l.kind, l.text.as_str()))
+            .collect();
+        assert_eq!(changed, vec![(DiffKind::Removed, "- logout"), (DiffKind::Added, "- export")]);
+        assert!(diff_lines(old, old).is_empty());
+    }
+}
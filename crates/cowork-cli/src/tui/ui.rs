@@ -0,0 +1,267 @@
+//! Layout and rendering of the TUI
+//!
+//! ┌ iteration / stage gauge ──────────────────────────┐
+//! │ stages   │ agent tabs + transcript  (or review)   │
+//! │ progress │ tool calls                             │
+//! └ key hints ────────────────────────────────────────┘
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Tabs, Wrap};
+
+use super::review::DiffKind;
+use super::{LineKind, PendingInput, ToolStatus, TuiState};
+
+pub(super) fn draw(frame: &mut Frame, state: &TuiState) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(8),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [sidebar, main] = Layout::horizontal([Constraint::Length(28), Constraint::Min(30)]).areas(body);
+
+    draw_header(frame, header, state);
+    draw_stages(frame, sidebar, state);
+    match &state.pending {
+        Some(pending) => draw_input(frame, main, pending),
+        None => {
+            let [transcript, tools] =
+                Layout::vertical([Constraint::Percentage(70), Constraint::Percentage(30)]).areas(main);
+            draw_transcript(frame, transcript, state);
+            draw_tools(frame, tools, state);
+        }
+    }
+    draw_footer(frame, footer, state);
+}
+
+fn draw_header(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let title = if state.title.is_empty() {
+        " Cowork Forge ".to_string()
+    } else {
+        format!(" Cowork Forge · {} ", state.title)
+    };
+    let (ratio, label) = match &state.stage_progress {
+        Some(p) if p.total > 0 => (
+            p.current as f64 / p.total as f64,
+            format!("[{}/{}] {}", p.current, p.total, p.message),
+        ),
+        _ => (0.0, state.stage.clone().unwrap_or_else(|| "starting…".to_string())),
+    };
+
+    let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .gauge_style(Style::default().fg(Color::Cyan))
+        .ratio(ratio.clamp(0.0, 1.0))
+        .label(label);
+    frame.render_widget(gauge, area);
+}
+
+fn draw_stages(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let mut items: Vec<ListItem> = state
+        .stages_seen
+        .iter()
+        .map(|stage| {
+            let current = state.stage.as_deref() == Some(stage.as_str());
+            let (marker, style) = if current {
+                ("▶", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            } else {
+                ("✓", Style::default().fg(Color::Green))
+            };
+            ListItem::new(Line::from(Span::styled(format!("{} {}", marker, stage), style)))
+        })
+        .collect();
+
+    if !state.tasks.is_empty() {
+        items.push(ListItem::new(""));
+        for (task_id, progress) in &state.tasks {
+            items.push(ListItem::new(format!("{} {}/{}", task_id, progress.current, progress.total)));
+            if !progress.message.is_empty() {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("  {}", progress.message),
+                    Style::default().fg(Color::DarkGray),
+                ))));
+            }
+        }
+    }
+
+    frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(" Stages ")), area);
+}
+
+fn line_style(kind: LineKind) -> Style {
+    match kind {
+        LineKind::Text => Style::default(),
+        LineKind::Thinking => Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+        LineKind::Info => Style::default().fg(Color::Cyan),
+        LineKind::Success => Style::default().fg(Color::Green),
+        LineKind::Warning => Style::default().fg(Color::Yellow),
+        LineKind::Error => Style::default().fg(Color::Red),
+    }
+}
+
+/// Scroll offset that shows the bottom of `lines`, `scroll_back` rows up.
+/// Wrapped rows are estimated from the display width of each line.
+fn bottom_scroll(lines: &[Line], area: Rect, scroll_back: usize) -> u16 {
+    let width = area.width.max(1) as usize;
+    let rows: usize = lines.iter().map(|l| l.width().max(1).div_ceil(width)).sum();
+    let height = area.height as usize;
+    rows.saturating_sub(height).saturating_sub(scroll_back).min(u16::MAX as usize) as u16
+}
+
+fn draw_transcript(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let block = Block::default().borders(Borders::ALL).title(" Transcript ");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [tabs_area, text_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(inner);
+
+    let titles: Vec<String> = state.transcripts.iter().map(|t| t.agent.clone()).collect();
+    let tabs = Tabs::new(titles)
+        .select(state.selected)
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(tabs, tabs_area);
+
+    let Some(transcript) = state.transcripts.get(state.selected) else {
+        return;
+    };
+    let lines: Vec<Line> = transcript
+        .lines
+        .iter()
+        .map(|l| Line::from(Span::styled(l.text.as_str(), line_style(l.kind))))
+        .collect();
+    let scroll = bottom_scroll(&lines, text_area, state.scroll_back.unwrap_or(0));
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).scroll((scroll, 0)),
+        text_area,
+    );
+}
+
+fn draw_tools(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = state
+        .tools
+        .iter()
+        .skip(state.tools.len().saturating_sub(visible))
+        .map(|t| {
+            let (marker, color) = match t.status {
+                ToolStatus::Running => ("…", Color::Yellow),
+                ToolStatus::Succeeded => ("✓", Color::Green),
+                ToolStatus::Failed => ("✗", Color::Red),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", marker), Style::default().fg(color)),
+                Span::styled(format!("[{}] ", t.agent), Style::default().fg(Color::DarkGray)),
+                Span::styled(t.tool.as_str(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!(" {}", t.detail)),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(" Tool calls ")), area);
+}
+
+fn draw_input(frame: &mut Frame, area: Rect, pending: &PendingInput) {
+    let prompt_rows = (pending.prompt.lines().count() as u16).clamp(1, 4) + 2;
+    let choice_rows = if pending.text_mode { 3 } else { pending.options.len() as u16 + 2 };
+    let [prompt_area, content_area, choice_area] = Layout::vertical([
+        Constraint::Length(prompt_rows),
+        Constraint::Min(3),
+        Constraint::Length(choice_rows),
+    ])
+    .areas(area);
+
+    frame.render_widget(
+        Paragraph::new(pending.prompt.as_str())
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(" Input required ")),
+        prompt_area,
+    );
+
+    draw_artifact(frame, content_area, pending);
+
+    if pending.text_mode {
+        let input = Line::from(vec![Span::raw(pending.input.as_str()), Span::styled("█", Style::default().fg(Color::Yellow))]);
+        frame.render_widget(
+            Paragraph::new(input).block(Block::default().borders(Borders::ALL).title(" Your answer ")),
+            choice_area,
+        );
+    } else {
+        let items: Vec<ListItem> = pending
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let style = if i == pending.selected {
+                    Style::default().fg(Color::Black).bg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                let description = option.description.as_deref().map(|d| format!(" - {}", d)).unwrap_or_default();
+                ListItem::new(Line::from(Span::styled(format!("{}. {}{}", i + 1, option.label, description), style)))
+            })
+            .collect();
+        frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(" Choose ")), choice_area);
+    }
+}
+
+fn draw_artifact(frame: &mut Frame, area: Rect, pending: &PendingInput) {
+    let Some(review) = &pending.review else {
+        let text = pending.initial_content.as_deref().unwrap_or_default();
+        frame.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: false }).block(Block::default().borders(Borders::ALL)),
+            area,
+        );
+        return;
+    };
+
+    let (title, lines): (String, Vec<Line>) = match (&review.previous, pending.show_diff) {
+        (Some(previous), true) => {
+            let diff = review.diff();
+            let lines = if diff.is_empty() {
+                vec![Line::from(Span::styled("No changes", Style::default().fg(Color::DarkGray)))]
+            } else {
+                diff.into_iter()
+                    .map(|l| {
+                        let (prefix, style) = match l.kind {
+                            DiffKind::Added => ("+ ", Style::default().fg(Color::Green)),
+                            DiffKind::Removed => ("- ", Style::default().fg(Color::Red)),
+                            DiffKind::Context => ("  ", Style::default()),
+                            DiffKind::Hunk => ("", Style::default().fg(Color::DarkGray)),
+                        };
+                        Line::from(Span::styled(format!("{}{}", prefix, l.text), style))
+                    })
+                    .collect()
+            };
+            (format!(" {} · diff against {} ", review.filename, previous.label), lines)
+        }
+        _ => (
+            format!(" {} ", review.filename),
+            review.content.lines().map(|l| Line::from(l.to_string())).collect(),
+        ),
+    };
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((pending.scroll, 0))
+            .block(Block::default().borders(Borders::ALL).title(title)),
+        area,
+    );
+}
+
+fn draw_footer(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let hints = match &state.pending {
+        Some(p) if p.text_mode => "Enter submit · Esc cancel · Ctrl-E editor · PgUp/PgDn scroll",
+        Some(p) if p.review.as_ref().is_some_and(|r| r.previous.is_some()) => {
+            "↑/↓ choose · Enter/1-9 select · d diff/full · PgUp/PgDn scroll · Ctrl-C cancel"
+        }
+        Some(_) => "↑/↓ choose · Enter/1-9 select · PgUp/PgDn scroll · Ctrl-C cancel",
+        None => "Tab/Shift-Tab agent · ↑/↓ PgUp/PgDn scroll · End follow · Ctrl-L redraw · Ctrl-C quit",
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(hints, Style::default().fg(Color::DarkGray)))),
+        area,
+    );
+}
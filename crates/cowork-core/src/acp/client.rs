@@ -6,6 +6,7 @@
 //! The agent-client-protocol SDK uses ?Send futures, which we handle by running
 //! in a dedicated thread with its own LocalSet and communicating via channels.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
@@ -15,6 +16,10 @@ use agent_client_protocol::{self as acp, Agent};
 use tokio::sync::{mpsc, oneshot};
 use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 
+use super::permission::{PermissionHandler, PermissionPrompter, PERMISSION_LOG_FILE};
+use super::plan::PlanStep;
+use super::terminal::TerminalManager;
+use crate::data::TaskStatus;
 use crate::llm::config::CodingAgentConfig;
 
 /// Timeout for a single ACP prompt turn.
@@ -34,6 +39,25 @@ pub enum AgentMessage {
     Status(String),
     /// Error message
     Error(String),
+    /// The agent started a tool (e.g. a terminal command)
+    ToolCall {
+        name: String,
+        arguments: serde_json::Value,
+    },
+    /// A tool started by the agent finished
+    ToolResult {
+        name: String,
+        result: String,
+        success: bool,
+    },
+    /// The agent changed a file
+    Diff {
+        path: PathBuf,
+        old_text: Option<String>,
+        new_text: String,
+    },
+    /// The agent's current plan, replacing any earlier one
+    Plan(Vec<PlanStep>),
     /// Task completed
     Completed,
 }
@@ -43,6 +67,10 @@ struct CoworkClient {
     output: Arc<std::sync::Mutex<String>>,
     message_tx: mpsc::UnboundedSender<AgentMessage>,
     workspace: PathBuf,
+    terminals: Arc<TerminalManager>,
+    permissions: PermissionHandler,
+    /// Titles of tool calls that have not finished, by tool call ID
+    tool_calls: std::sync::Mutex<HashMap<String, String>>,
 }
 
 impl CoworkClient {
@@ -91,41 +119,154 @@ fn strip_unc_prefix(path: &Path) -> PathBuf {
     }
 }
 
+impl CoworkClient {
+    fn tool_call_started(&self, call: acp::ToolCall) {
+        let name = tool_display_name(&call.title, &call.kind);
+        if let Ok(mut calls) = self.tool_calls.lock() {
+            calls.insert(call.tool_call_id.0.to_string(), name.clone());
+        }
+        let _ = self.message_tx.send(AgentMessage::ToolCall {
+            name: name.clone(),
+            arguments: serde_json::json!({
+                "kind": call.kind,
+                "locations": call.locations.iter().map(|l| l.path.display().to_string()).collect::<Vec<_>>(),
+                "input": call.raw_input,
+            }),
+        });
+        self.forward_tool_content(&call.content);
+        self.tool_call_status(&call.tool_call_id, call.status, &call.content, call.raw_output.as_ref());
+    }
+
+    fn tool_call_updated(&self, update: acp::ToolCallUpdate) {
+        let fields = update.fields;
+        // Updates may rename the call, or arrive for a call never announced
+        if let Ok(mut calls) = self.tool_calls.lock() {
+            let id = update.tool_call_id.0.to_string();
+            match &fields.title {
+                Some(title) => {
+                    calls.insert(id, title.clone());
+                }
+                None => {
+                    calls
+                        .entry(id)
+                        .or_insert_with(|| tool_display_name("", &fields.kind.unwrap_or_default()));
+                }
+            }
+        }
+        let content = fields.content.unwrap_or_default();
+        self.forward_tool_content(&content);
+        if let Some(status) = fields.status {
+            self.tool_call_status(&update.tool_call_id, status, &content, fields.raw_output.as_ref());
+        }
+    }
+
+    /// Forward diffs; terminals are mirrored by the terminal manager
+    fn forward_tool_content(&self, content: &[acp::ToolCallContent]) {
+        for item in content {
+            if let acp::ToolCallContent::Diff(diff) = item {
+                let path = self
+                    .validate_workspace_path(&diff.path)
+                    .ok()
+                    .and_then(|p| {
+                        let workspace = self.workspace.canonicalize().ok()?;
+                        p.strip_prefix(strip_unc_prefix(&workspace)).ok().map(Path::to_path_buf)
+                    })
+                    .unwrap_or_else(|| diff.path.clone());
+                let _ = self.message_tx.send(AgentMessage::Diff {
+                    path,
+                    old_text: diff.old_text.clone(),
+                    new_text: diff.new_text.clone(),
+                });
+            }
+        }
+    }
+
+    /// Report a finished tool call once
+    fn tool_call_status(
+        &self,
+        id: &acp::ToolCallId,
+        status: acp::ToolCallStatus,
+        content: &[acp::ToolCallContent],
+        raw_output: Option<&serde_json::Value>,
+    ) {
+        let success = match status {
+            acp::ToolCallStatus::Completed => true,
+            acp::ToolCallStatus::Failed => false,
+            _ => return,
+        };
+        let Some(name) = self.tool_calls.lock().ok().and_then(|mut c| c.remove(id.0.as_ref())) else {
+            return;
+        };
+
+        let mut result: Vec<String> = content
+            .iter()
+            .filter_map(|item| match item {
+                acp::ToolCallContent::Content(acp::Content {
+                    content: acp::ContentBlock::Text(text),
+                    ..
+                }) => Some(text.text.clone()),
+                _ => None,
+            })
+            .collect();
+        if let (true, Some(output)) = (result.is_empty(), raw_output) {
+            result.push(match output {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            });
+        }
+        let _ = self.message_tx.send(AgentMessage::ToolResult {
+            name,
+            result: result.join("\n"),
+            success,
+        });
+    }
+}
+
+fn tool_display_name(title: &str, kind: &acp::ToolKind) -> String {
+    if !title.is_empty() {
+        return title.to_string();
+    }
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| "tool".to_string())
+}
+
+fn plan_steps(plan: &acp::Plan) -> Vec<PlanStep> {
+    plan.entries
+        .iter()
+        .map(|entry| PlanStep {
+            content: entry.content.clone(),
+            status: match entry.status {
+                acp::PlanEntryStatus::Pending => TaskStatus::Pending,
+                acp::PlanEntryStatus::InProgress => TaskStatus::InProgress,
+                acp::PlanEntryStatus::Completed => TaskStatus::Completed,
+                _ => TaskStatus::Pending,
+            },
+        })
+        .collect()
+}
+
 #[async_trait::async_trait(?Send)]
 impl acp::Client for CoworkClient {
     async fn request_permission(
         &self,
         args: acp::RequestPermissionRequest,
     ) -> acp::Result<acp::RequestPermissionResponse> {
-        tracing::info!(
+        tracing::debug!(
             session_id = %args.session_id,
             "ACP permission request for tool call: {:?}",
             args.tool_call
         );
 
-        // Auto-approve the first "allow" option so the agent can keep working.
-        // In the future this can be wired to the InteractiveBackend for user consent.
-        let allow_option = args
-            .options
-            .into_iter()
-            .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowOnce | acp::PermissionOptionKind::AllowAlways));
-
-        match allow_option {
-            Some(option) => {
-                tracing::info!(option_id = %option.option_id.0, "Auto-approving ACP permission request");
-                Ok(acp::RequestPermissionResponse::new(
-                    acp::RequestPermissionOutcome::Selected(
-                        acp::SelectedPermissionOutcome::new(option.option_id),
-                    ),
-                ))
-            }
-            None => {
-                tracing::warn!("ACP permission request had no allow option; cancelling");
-                Ok(acp::RequestPermissionResponse::new(
-                    acp::RequestPermissionOutcome::Cancelled,
-                ))
-            }
+        let response = self.permissions.decide(args, &self.workspace).await;
+        if let acp::RequestPermissionOutcome::Selected(ref selected) = response.outcome {
+            let _ = self.message_tx.send(AgentMessage::Status(format!(
+                "Permission answered: {}",
+                selected.option_id.0
+            )));
         }
+        Ok(response)
     }
 
     async fn write_text_file(
@@ -177,37 +318,47 @@ impl acp::Client for CoworkClient {
 
     async fn create_terminal(
         &self,
-        _args: acp::CreateTerminalRequest,
+        args: acp::CreateTerminalRequest,
     ) -> Result<acp::CreateTerminalResponse, acp::Error> {
-        Err(acp::Error::method_not_found())
+        tracing::debug!(session_id = %args.session_id, command = %args.command, args = ?args.args, "ACP create_terminal");
+
+        let cwd = match &args.cwd {
+            Some(cwd) => {
+                let cwd = if cwd.is_absolute() { cwd.clone() } else { self.workspace.join(cwd) };
+                self.validate_workspace_path(&cwd)?
+            }
+            None => self.workspace.clone(),
+        };
+        self.terminals.create(args, cwd)
     }
 
     async fn terminal_output(
         &self,
-        _args: acp::TerminalOutputRequest,
+        args: acp::TerminalOutputRequest,
     ) -> acp::Result<acp::TerminalOutputResponse> {
-        Err(acp::Error::method_not_found())
+        self.terminals.output(&args.terminal_id)
     }
 
     async fn release_terminal(
         &self,
-        _args: acp::ReleaseTerminalRequest,
+        args: acp::ReleaseTerminalRequest,
     ) -> acp::Result<acp::ReleaseTerminalResponse> {
-        Err(acp::Error::method_not_found())
+        tracing::debug!(terminal_id = %args.terminal_id.0, "ACP release_terminal");
+        self.terminals.release(&args.terminal_id)
     }
 
     async fn wait_for_terminal_exit(
         &self,
-        _args: acp::WaitForTerminalExitRequest,
+        args: acp::WaitForTerminalExitRequest,
     ) -> acp::Result<acp::WaitForTerminalExitResponse> {
-        Err(acp::Error::method_not_found())
+        self.terminals.wait_for_exit(&args.terminal_id).await
     }
 
     async fn kill_terminal_command(
         &self,
-        _args: acp::KillTerminalCommandRequest,
+        args: acp::KillTerminalCommandRequest,
     ) -> acp::Result<acp::KillTerminalCommandResponse> {
-        Err(acp::Error::method_not_found())
+        self.terminals.kill(&args.terminal_id)
     }
 
     async fn session_notification(
@@ -234,6 +385,18 @@ impl acp::Client for CoworkClient {
                 tracing::debug!(len = text.len(), "ACP agent thought chunk");
                 let _ = self.message_tx.send(AgentMessage::Thinking(text));
             }
+            acp::SessionUpdate::ToolCall(call) => {
+                tracing::debug!(tool_call_id = %call.tool_call_id.0, title = %call.title, "ACP tool call");
+                self.tool_call_started(call);
+            }
+            acp::SessionUpdate::ToolCallUpdate(update) => {
+                tracing::debug!(tool_call_id = %update.tool_call_id.0, status = ?update.fields.status, "ACP tool call update");
+                self.tool_call_updated(update);
+            }
+            acp::SessionUpdate::Plan(plan) => {
+                tracing::debug!(entries = plan.entries.len(), "ACP plan");
+                let _ = self.message_tx.send(AgentMessage::Plan(plan_steps(&plan)));
+            }
             // Ignore other updates (different protocol versions may have different variants)
             _ => {}
         }
@@ -288,6 +451,7 @@ pub fn execute_with_external_agent(
     config: CodingAgentConfig,
     workspace: PathBuf,
     task: String,
+    prompter: Option<PermissionPrompter>,
 ) -> (mpsc::UnboundedReceiver<AgentMessage>, impl std::future::Future<Output = Result<Result<String>>>) {
     tracing::info!(
         command = %config.command,
@@ -302,9 +466,13 @@ pub fn execute_with_external_agent(
     // Create channel for final result
     let (tx, rx) = oneshot::channel();
 
+    // Resolved here: the iteration ID is a global of the calling context
+    let permission_log = crate::persistence::iteration_log_path(PERMISSION_LOG_FILE).ok();
+    let permissions = PermissionHandler::new(&config.permissions, prompter, permission_log);
+
     // Spawn a dedicated thread for the non-Send operations
     std::thread::spawn(move || {
-        let result = run_acp_in_thread(config, workspace, task, message_tx);
+        let result = run_acp_in_thread(config, workspace, task, message_tx, permissions);
         let _ = tx.send(result);
     });
 
@@ -324,6 +492,7 @@ fn run_acp_in_thread(
     workspace: PathBuf,
     task: String,
     message_tx: mpsc::UnboundedSender<AgentMessage>,
+    permissions: PermissionHandler,
 ) -> Result<String> {
     // Create a new tokio runtime for this thread
     let rt = tokio::runtime::Builder::new_current_thread()
@@ -437,6 +606,8 @@ fn run_acp_in_thread(
         // Clone for the completion notification sent after the local_set returns.
         // The original message_tx is moved into CoworkClient inside the local_set block.
         let completion_tx = message_tx.clone();
+        let terminals = Arc::new(TerminalManager::new(workspace.clone(), message_tx.clone()));
+        let terminals_clone = terminals.clone();
 
         let result: Result<String> = local_set
             .run_until(async move {
@@ -445,6 +616,9 @@ fn run_acp_in_thread(
                         output: output_clone,
                         message_tx,
                         workspace: workspace_clone,
+                        terminals: terminals_clone,
+                        permissions,
+                        tool_calls: std::sync::Mutex::new(HashMap::new()),
                     },
                     outgoing,
                     incoming,
@@ -472,11 +646,13 @@ fn run_acp_in_thread(
                                 .title("Cowork Forge".to_string()),
                             )
                             .client_capabilities(
-                                acp::ClientCapabilities::new().fs(
-                                    acp::FileSystemCapability::new()
-                                        .read_text_file(true)
-                                        .write_text_file(true),
-                                ),
+                                acp::ClientCapabilities::new()
+                                    .fs(
+                                        acp::FileSystemCapability::new()
+                                            .read_text_file(true)
+                                            .write_text_file(true),
+                                    )
+                                    .terminal(true),
                             ),
                     )
                     .await
@@ -573,6 +749,7 @@ fn run_acp_in_thread(
         // pipeline stage. Without this, the stderr task (spawned via tokio::spawn)
         // keeps message_tx alive and the channel never closes.
         tracing::info!("Cleaning up ACP agent process");
+        terminals.kill_all();
         let _ = child.start_kill();
         // Wait for the child to exit (with a timeout to avoid hanging)
         let _ = tokio::time::timeout(
@@ -626,7 +803,7 @@ impl AcpClient {
         self,
         task: String,
     ) -> (mpsc::UnboundedReceiver<AgentMessage>, impl std::future::Future<Output = Result<Result<String>>>) {
-        execute_with_external_agent(self.config, self.workspace, task)
+        execute_with_external_agent(self.config, self.workspace, task, None)
     }
 
     /// Execute a coding task (simpler API for backward compatibility)
@@ -635,6 +812,7 @@ impl AcpClient {
             self.config.clone(),
             self.workspace.clone(),
             task.to_string(),
+            None,
         );
         // Flatten the nested Result: Result<Result<String>> -> Result<String>
         result.await?
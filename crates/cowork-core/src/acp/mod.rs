@@ -2,4 +2,8 @@
 // This module provides functionality to connect to external coding agents via ACP
 
 pub mod client;
+pub mod permission;
+pub mod plan;
+pub mod server;
+pub mod terminal;
 pub use client::*;
@@ -0,0 +1,362 @@
+//! Permission decisions for tool calls requested by an external ACP agent
+//!
+//! Requests are matched against the `[coding_agent.permissions]` policy.
+//! "ask" is routed to the `InteractiveBackend`, and every decision is
+//! appended to the iteration's `logs/acp_permissions.jsonl`.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use agent_client_protocol as acp;
+use globset::{Glob, GlobMatcher};
+use serde::Serialize;
+
+use crate::interaction::{InputOption, InputResponse, InteractiveBackend};
+use crate::llm::config::{PermissionAction, PermissionPolicy};
+
+/// Log file under the iteration's `logs/` directory
+pub const PERMISSION_LOG_FILE: &str = "acp_permissions.jsonl";
+
+struct CompiledRule {
+    kind: Option<String>,
+    path: Option<GlobMatcher>,
+    action: PermissionAction,
+}
+
+/// Where an "ask" decision is sent: the backend, plus the runtime it lives on
+/// (the ACP client runs on its own thread and runtime)
+#[derive(Clone)]
+pub struct PermissionPrompter {
+    pub interaction: Arc<dyn InteractiveBackend>,
+    pub runtime: tokio::runtime::Handle,
+}
+
+/// A tool call that needs permission, reduced to what the policy looks at
+#[derive(Debug, Clone)]
+pub struct PermissionSubject {
+    pub kind: String,
+    pub title: String,
+    /// Paths relative to the workspace when inside it, absolute otherwise
+    pub paths: Vec<PathBuf>,
+}
+
+impl PermissionSubject {
+    pub fn from_request(args: &acp::RequestPermissionRequest, workspace: &Path) -> Self {
+        let fields = &args.tool_call.fields;
+        let kind = fields
+            .kind
+            .as_ref()
+            .and_then(|k| serde_json::to_value(k).ok())
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| "other".to_string());
+        let workspace = workspace.canonicalize().unwrap_or_else(|_| workspace.to_path_buf());
+        let paths = fields
+            .locations
+            .iter()
+            .flatten()
+            .map(|location| {
+                location
+                    .path
+                    .strip_prefix(&workspace)
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(|_| location.path.clone())
+            })
+            .collect();
+        Self {
+            kind,
+            title: fields
+                .title
+                .clone()
+                .unwrap_or_else(|| args.tool_call.tool_call_id.0.to_string()),
+            paths,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct LogEntry<'a> {
+    timestamp: String,
+    session_id: &'a str,
+    tool_call_id: &'a str,
+    kind: &'a str,
+    title: &'a str,
+    paths: &'a [PathBuf],
+    policy: PermissionAction,
+    /// "allowed", "denied" or "cancelled"
+    decision: &'a str,
+    option_id: Option<&'a str>,
+}
+
+pub struct PermissionHandler {
+    rules: Vec<CompiledRule>,
+    default: PermissionAction,
+    prompter: Option<PermissionPrompter>,
+    log_path: Option<PathBuf>,
+}
+
+impl PermissionHandler {
+    pub fn new(
+        policy: &PermissionPolicy,
+        prompter: Option<PermissionPrompter>,
+        log_path: Option<PathBuf>,
+    ) -> Self {
+        let rules = policy
+            .rules
+            .iter()
+            .filter_map(|rule| {
+                let path = match rule.path.as_deref() {
+                    Some(pattern) => match Glob::new(pattern) {
+                        Ok(glob) => Some(glob.compile_matcher()),
+                        Err(e) => {
+                            tracing::warn!(pattern, error = %e, "Ignoring permission rule with invalid path glob");
+                            return None;
+                        }
+                    },
+                    None => None,
+                };
+                Some(CompiledRule {
+                    kind: rule.kind.clone().filter(|k| k != "*" && !k.is_empty()),
+                    path,
+                    action: rule.action,
+                })
+            })
+            .collect();
+        Self {
+            rules,
+            default: policy.default,
+            prompter,
+            log_path,
+        }
+    }
+
+    /// Action the policy assigns to a tool call. A path rule only matches
+    /// when every path of the call matches it.
+    pub fn evaluate(&self, subject: &PermissionSubject) -> PermissionAction {
+        self.rules
+            .iter()
+            .find(|rule| {
+                let kind_matches = rule.kind.as_deref().is_none_or(|k| k == subject.kind);
+                let path_matches = rule.path.as_ref().is_none_or(|glob| {
+                    !subject.paths.is_empty() && subject.paths.iter().all(|p| glob.is_match(p))
+                });
+                kind_matches && path_matches
+            })
+            .map(|rule| rule.action)
+            .unwrap_or(self.default)
+    }
+
+    /// Answer a permission request and record the decision
+    pub async fn decide(
+        &self,
+        args: acp::RequestPermissionRequest,
+        workspace: &Path,
+    ) -> acp::RequestPermissionResponse {
+        let subject = PermissionSubject::from_request(&args, workspace);
+        let policy = self.evaluate(&subject);
+
+        let allow = find_option(&args.options, true);
+        let reject = find_option(&args.options, false);
+        let chosen = match policy {
+            PermissionAction::Allow => allow.or(reject),
+            PermissionAction::Deny => reject,
+            PermissionAction::Ask => self.ask(&subject, &args.options).await,
+        };
+
+        let decision = match chosen {
+            Some(option) if is_allow(option) => "allowed",
+            Some(_) => "denied",
+            None => "cancelled",
+        };
+        tracing::info!(
+            kind = %subject.kind,
+            title = %subject.title,
+            policy = ?policy,
+            decision,
+            "ACP permission request"
+        );
+        self.log(&args, &subject, policy, decision, chosen.map(|o| o.option_id.0.as_ref()));
+
+        match chosen {
+            Some(option) => acp::RequestPermissionResponse::new(acp::RequestPermissionOutcome::Selected(
+                acp::SelectedPermissionOutcome::new(option.option_id.clone()),
+            )),
+            None => acp::RequestPermissionResponse::new(acp::RequestPermissionOutcome::Cancelled),
+        }
+    }
+
+    async fn ask<'a>(
+        &self,
+        subject: &PermissionSubject,
+        options: &'a [acp::PermissionOption],
+    ) -> Option<&'a acp::PermissionOption> {
+        let Some(prompter) = self.prompter.clone() else {
+            tracing::warn!(title = %subject.title, "No interaction backend to ask for ACP permission; rejecting");
+            return find_option(options, false);
+        };
+
+        let mut prompt = format!("The coding agent wants to run {} tool: {}", subject.kind, subject.title);
+        if !subject.paths.is_empty() {
+            let paths: Vec<String> = subject.paths.iter().map(|p| p.display().to_string()).collect();
+            prompt.push_str(&format!("\nPaths: {}", paths.join(", ")));
+        }
+        let input_options: Vec<InputOption> = options
+            .iter()
+            .map(|o| InputOption {
+                id: o.option_id.0.to_string(),
+                label: o.name.clone(),
+                description: None,
+            })
+            .collect();
+
+        let interaction = prompter.interaction;
+        let response = prompter
+            .runtime
+            .spawn(async move { interaction.request_input(&prompt, input_options, None).await })
+            .await;
+
+        match response {
+            Ok(Ok(InputResponse::Selection(id))) | Ok(Ok(InputResponse::Text(id))) => {
+                let id = id.trim().to_string();
+                options
+                    .iter()
+                    .find(|o| *o.option_id.0 == *id || o.name.eq_ignore_ascii_case(&id))
+                    .or_else(|| find_option(options, false))
+            }
+            Ok(Ok(InputResponse::Cancel)) => None,
+            Ok(Err(e)) => {
+                tracing::warn!(error = %e, "ACP permission prompt failed; rejecting");
+                find_option(options, false)
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "ACP permission prompt task failed; rejecting");
+                find_option(options, false)
+            }
+        }
+    }
+
+    fn log(
+        &self,
+        args: &acp::RequestPermissionRequest,
+        subject: &PermissionSubject,
+        policy: PermissionAction,
+        decision: &str,
+        option_id: Option<&str>,
+    ) {
+        let Some(path) = &self.log_path else {
+            return;
+        };
+        let entry = LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            session_id: &args.session_id.0,
+            tool_call_id: &args.tool_call.tool_call_id.0,
+            kind: &subject.kind,
+            title: &subject.title,
+            paths: &subject.paths,
+            policy,
+            decision,
+            option_id,
+        };
+        let result = serde_json::to_string(&entry)
+            .map_err(std::io::Error::other)
+            .and_then(|line| {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", line)
+            });
+        if let Err(e) = result {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to log ACP permission decision");
+        }
+    }
+}
+
+fn is_allow(option: &acp::PermissionOption) -> bool {
+    matches!(
+        option.kind,
+        acp::PermissionOptionKind::AllowOnce | acp::PermissionOptionKind::AllowAlways
+    )
+}
+
+/// First "once" option of the wanted polarity, falling back to "always"
+fn find_option(options: &[acp::PermissionOption], allow: bool) -> Option<&acp::PermissionOption> {
+    let (once, always) = if allow {
+        (acp::PermissionOptionKind::AllowOnce, acp::PermissionOptionKind::AllowAlways)
+    } else {
+        (acp::PermissionOptionKind::RejectOnce, acp::PermissionOptionKind::RejectAlways)
+    };
+    options
+        .iter()
+        .find(|o| o.kind == once)
+        .or_else(|| options.iter().find(|o| o.kind == always))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::config::PermissionRule;
+
+    fn subject(kind: &str, paths: &[&str]) -> PermissionSubject {
+        PermissionSubject {
+            kind: kind.to_string(),
+            title: "test".to_string(),
+            paths: paths.iter().map(PathBuf::from).collect(),
+        }
+    }
+
+    #[test]
+    fn test_policy_evaluation() {
+        let mut policy = PermissionPolicy::default();
+        policy.rules.insert(
+            0,
+            PermissionRule {
+                kind: Some("edit".to_string()),
+                path: Some("secrets/**".to_string()),
+                action: PermissionAction::Deny,
+            },
+        );
+        policy.rules.push(PermissionRule {
+            kind: Some("*".to_string()),
+            path: Some("tmp/**".to_string()),
+            action: PermissionAction::Allow,
+        });
+        let handler = PermissionHandler::new(&policy, None, None);
+
+        assert_eq!(handler.evaluate(&subject("read", &["src/main.rs"])), PermissionAction::Allow);
+        assert_eq!(handler.evaluate(&subject("edit", &["src/main.rs"])), PermissionAction::Allow);
+        assert_eq!(handler.evaluate(&subject("edit", &["secrets/key.pem"])), PermissionAction::Deny);
+        assert_eq!(handler.evaluate(&subject("delete", &["tmp/a", "tmp/b"])), PermissionAction::Allow);
+        assert_eq!(handler.evaluate(&subject("delete", &["tmp/a", "src/b"])), PermissionAction::Ask);
+        assert_eq!(handler.evaluate(&subject("execute", &[])), PermissionAction::Ask);
+    }
+
+    #[tokio::test]
+    async fn test_decide_without_prompter_rejects_and_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("logs").join(PERMISSION_LOG_FILE);
+        let handler = PermissionHandler::new(&PermissionPolicy::default(), None, Some(log_path.clone()));
+
+        let options = vec![
+            acp::PermissionOption::new("allow", "Allow", acp::PermissionOptionKind::AllowOnce),
+            acp::PermissionOption::new("reject", "Reject", acp::PermissionOptionKind::RejectOnce),
+        ];
+        let tool_call = acp::ToolCallUpdate::new(
+            "call-1",
+            acp::ToolCallUpdateFields::new().kind(acp::ToolKind::Execute).title("npm test"),
+        );
+        let request = acp::RequestPermissionRequest::new("s1", tool_call, options);
+
+        let response = handler.decide(request, dir.path()).await;
+        match response.outcome {
+            acp::RequestPermissionOutcome::Selected(selected) => {
+                assert_eq!(&*selected.option_id.0, "reject")
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        assert!(log.contains("\"decision\":\"denied\""));
+        assert!(log.contains("\"kind\":\"execute\""));
+    }
+}
@@ -0,0 +1,116 @@
+//! Mirror an external agent's plan into `implementation_plan.json`
+//!
+//! ACP plans are free-text entries. An entry is matched to a task when it
+//! mentions the task ID (`TASK-003`) or contains the task title.
+
+use anyhow::Result;
+
+use crate::data::{ImplementationPlan, TaskStatus};
+use crate::persistence::{load_implementation_plan, save_implementation_plan};
+
+/// One entry of an agent's plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanStep {
+    pub content: String,
+    pub status: TaskStatus,
+}
+
+/// A task whose status changed because of the agent's plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskStatusChange {
+    pub task_id: String,
+    pub status: TaskStatus,
+}
+
+/// Apply plan steps to the tasks they refer to. Completed tasks are never
+/// moved back, since an agent may restate finished work as pending.
+pub fn apply_plan_steps(plan: &mut ImplementationPlan, steps: &[PlanStep]) -> Vec<TaskStatusChange> {
+    let mut changes = Vec::new();
+    for step in steps {
+        let content = step.content.to_lowercase();
+        let task = plan.tasks.iter_mut().find(|t| {
+            content.contains(&t.id.to_lowercase())
+                || (!t.title.is_empty() && content.contains(&t.title.to_lowercase()))
+        });
+        let Some(task) = task else {
+            continue;
+        };
+        if task.status == step.status || task.status == TaskStatus::Completed {
+            continue;
+        }
+
+        task.status = step.status;
+        match step.status {
+            TaskStatus::InProgress => task.started_at = Some(chrono::Utc::now()),
+            TaskStatus::Completed => task.completed_at = Some(chrono::Utc::now()),
+            _ => {}
+        }
+        changes.push(TaskStatusChange {
+            task_id: task.id.clone(),
+            status: step.status,
+        });
+    }
+    changes
+}
+
+/// Apply plan steps to the current iteration's implementation plan and save it
+pub fn sync_plan_to_tasks(steps: &[PlanStep]) -> Result<Vec<TaskStatusChange>> {
+    let mut plan = load_implementation_plan()?;
+    let changes = apply_plan_steps(&mut plan, steps);
+    if !changes.is_empty() {
+        save_implementation_plan(&plan)?;
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Task;
+
+    fn task(id: &str, title: &str, status: TaskStatus) -> Task {
+        Task {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            feature_id: "FEAT-001".to_string(),
+            component_id: "COMP-001".to_string(),
+            status,
+            dependencies: Vec::new(),
+            estimated_effort: None,
+            files_to_create: Vec::new(),
+            acceptance_criteria: Vec::new(),
+            created_at: chrono::Utc::now(),
+            started_at: None,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_plan_steps() {
+        let mut plan = ImplementationPlan {
+            schema_version: "1.0".to_string(),
+            milestones: Vec::new(),
+            tasks: vec![
+                task("TASK-001", "Create login form", TaskStatus::Pending),
+                task("TASK-002", "Add session storage", TaskStatus::Pending),
+                task("TASK-003", "Write API client", TaskStatus::Completed),
+            ],
+        };
+        let steps = vec![
+            PlanStep { content: "TASK-001: build the form".to_string(), status: TaskStatus::Completed },
+            PlanStep { content: "Add session storage in redis".to_string(), status: TaskStatus::InProgress },
+            PlanStep { content: "Write API client".to_string(), status: TaskStatus::Pending },
+            PlanStep { content: "Run the linter".to_string(), status: TaskStatus::InProgress },
+        ];
+
+        let changes = apply_plan_steps(&mut plan, &steps);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(plan.tasks[0].status, TaskStatus::Completed);
+        assert!(plan.tasks[0].completed_at.is_some());
+        assert_eq!(plan.tasks[1].status, TaskStatus::InProgress);
+        assert_eq!(plan.tasks[2].status, TaskStatus::Completed);
+
+        assert!(apply_plan_steps(&mut plan, &steps).is_empty());
+    }
+}
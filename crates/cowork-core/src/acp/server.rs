@@ -0,0 +1,415 @@
+//! Cowork as an ACP agent, served over stdio by `cowork acp`
+//!
+//! Editors that speak ACP drive Cowork through prompts:
+//! - `/iter <title>` creates an iteration and runs it
+//! - `/continue [iteration]` resumes a paused iteration
+//! - anything else goes to the PM agent of the latest iteration, or starts
+//!   the first iteration when the project has none
+//!
+//! Stage output streams back as `session/update` notifications and HITL
+//! confirmations become `session/request_permission` requests (see
+//! `AcpBackend`). The connection runs on a `LocalSet`; prompts run on the
+//! surrounding runtime so they can be cancelled.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use agent_client_protocol::{self as acp, Client as _};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
+
+use crate::agents::{execute_pm_agent_message_streaming, PMAgentAction, PMAgentStreamCallback};
+use crate::domain::{InheritanceMode, IterationStatus, Project};
+use crate::interaction::{AcpBackend, AcpOutbound, InputOption, InputResponse, InteractiveBackend};
+use crate::llm::{create_llm_client, load_config};
+use crate::persistence::{IterationStore, ProjectStore};
+use crate::pipeline::IterationExecutor;
+
+/// Longest iteration title taken from a prompt
+const MAX_TITLE_CHARS: usize = 80;
+
+/// What a prompt asks Cowork to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptCommand {
+    /// `/iter <title>`: create an iteration and run it
+    Iterate(String),
+    /// `/continue [iteration]`: resume a paused iteration
+    Continue(Option<String>),
+    /// Free text for the PM agent
+    Message(String),
+}
+
+impl PromptCommand {
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let (command, rest) = match text.split_once(char::is_whitespace) {
+            Some((command, rest)) => (command, rest.trim()),
+            None => (text, ""),
+        };
+        match command {
+            "/iter" | "/iteration" => Self::Iterate(rest.to_string()),
+            "/continue" => Self::Continue(Some(rest.to_string()).filter(|r| !r.is_empty())),
+            _ => Self::Message(text.to_string()),
+        }
+    }
+}
+
+/// Plain text of a prompt; resource links contribute their URI
+fn prompt_text(blocks: &[acp::ContentBlock]) -> String {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            acp::ContentBlock::Text(text) => Some(text.text.clone()),
+            acp::ContentBlock::ResourceLink(link) => Some(link.uri.clone()),
+            acp::ContentBlock::Resource(resource) => match &resource.resource {
+                acp::EmbeddedResourceResource::TextResourceContents(contents) => Some(contents.text.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn internal_error(e: impl std::fmt::Display) -> acp::Error {
+    acp::Error::internal_error().data(Value::String(e.to_string()))
+}
+
+/// Serve the agent side of ACP until the client disconnects
+pub async fn serve<W, R>(outgoing: W, incoming: R) -> Result<()>
+where
+    W: AsyncWrite + Unpin + 'static,
+    R: AsyncRead + Unpin + 'static,
+{
+    let (backend, mut outbound) = AcpBackend::new();
+    let backend = Arc::new(backend);
+    backend.install_tool_notifier();
+    // ask_user and review tools reach the client through the global backend
+    crate::tools::set_interaction_backend(backend.clone());
+
+    let local_set = tokio::task::LocalSet::new();
+    local_set
+        .run_until(async move {
+            let agent = CoworkAgent::new(backend);
+            let (conn, handle_io) = acp::AgentSideConnection::new(
+                agent,
+                outgoing.compat_write(),
+                incoming.compat(),
+                |fut| {
+                    tokio::task::spawn_local(fut);
+                },
+            );
+            let conn = Rc::new(conn);
+
+            tokio::task::spawn_local(async move {
+                while let Some(item) = outbound.recv().await {
+                    match item {
+                        // Awaited in order so chunks arrive in sequence
+                        AcpOutbound::Update(notification) => {
+                            if let Err(e) = conn.session_notification(notification).await {
+                                tracing::warn!(error = %e, "Failed to send ACP session update");
+                            }
+                        }
+                        AcpOutbound::Permission { request, reply } => {
+                            let conn = conn.clone();
+                            tokio::task::spawn_local(async move {
+                                let _ = reply.send(conn.request_permission(request).await);
+                            });
+                        }
+                        AcpOutbound::Flush(done) => {
+                            let _ = done.send(());
+                        }
+                    }
+                }
+            });
+
+            handle_io.await.map_err(|e| anyhow::anyhow!("ACP connection failed: {}", e))
+        })
+        .await
+}
+
+struct RunningPrompt {
+    session_id: acp::SessionId,
+    abort: tokio::task::AbortHandle,
+}
+
+pub struct CoworkAgent {
+    backend: Arc<AcpBackend>,
+    /// PM conversation history per session, in the GUI's `{type, content}` shape
+    sessions: RefCell<HashMap<acp::SessionId, Vec<Value>>>,
+    running: RefCell<Option<RunningPrompt>>,
+}
+
+impl CoworkAgent {
+    pub fn new(backend: Arc<AcpBackend>) -> Self {
+        Self {
+            backend,
+            sessions: RefCell::new(HashMap::new()),
+            running: RefCell::new(None),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl acp::Agent for CoworkAgent {
+    async fn initialize(&self, _args: acp::InitializeRequest) -> acp::Result<acp::InitializeResponse> {
+        Ok(acp::InitializeResponse::new(acp::ProtocolVersion::LATEST)
+            .agent_capabilities(acp::AgentCapabilities::new())
+            .agent_info(acp::Implementation::new("cowork-forge", env!("CARGO_PKG_VERSION"))))
+    }
+
+    async fn authenticate(&self, _args: acp::AuthenticateRequest) -> acp::Result<acp::AuthenticateResponse> {
+        Ok(acp::AuthenticateResponse::default())
+    }
+
+    async fn new_session(&self, args: acp::NewSessionRequest) -> acp::Result<acp::NewSessionResponse> {
+        if !args.cwd.is_dir() {
+            return Err(acp::Error::invalid_params()
+                .data(Value::String(format!("Not a directory: {}", args.cwd.display()))));
+        }
+        // Stores and tools resolve the project from the working directory
+        std::env::set_current_dir(&args.cwd).map_err(internal_error)?;
+        crate::persistence::set_workspace_path(args.cwd.clone());
+
+        let project_store = ProjectStore::new();
+        if !project_store.exists() {
+            let name = args
+                .cwd
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("my-project")
+                .to_string();
+            project_store.create(&name).map_err(internal_error)?;
+            tracing::info!(project = %name, "Created project for ACP session");
+        }
+
+        let session_id = acp::SessionId::new(uuid::Uuid::new_v4().to_string());
+        self.sessions.borrow_mut().insert(session_id.clone(), Vec::new());
+        Ok(acp::NewSessionResponse::new(session_id))
+    }
+
+    async fn prompt(&self, args: acp::PromptRequest) -> acp::Result<acp::PromptResponse> {
+        let Some(history) = self.sessions.borrow().get(&args.session_id).cloned() else {
+            return Err(acp::Error::invalid_params()
+                .data(Value::String(format!("Unknown session: {}", args.session_id.0))));
+        };
+        if self.running.borrow().is_some() {
+            return Err(acp::Error::invalid_request().data(Value::String("A prompt is already running".to_string())));
+        }
+
+        let text = prompt_text(&args.prompt);
+        self.backend.set_session(Some(args.session_id.clone()));
+        let task = tokio::spawn(run_prompt(self.backend.clone(), PromptCommand::parse(&text), history));
+        *self.running.borrow_mut() = Some(RunningPrompt {
+            session_id: args.session_id.clone(),
+            abort: task.abort_handle(),
+        });
+        let result = task.await;
+        self.running.borrow_mut().take();
+
+        let stop_reason = match result {
+            Ok(Ok(reply)) => {
+                if let Some(history) = self.sessions.borrow_mut().get_mut(&args.session_id) {
+                    history.push(serde_json::json!({"type": "user", "content": text}));
+                    history.push(serde_json::json!({"type": "assistant", "content": reply}));
+                }
+                acp::StopReason::EndTurn
+            }
+            Ok(Err(e)) => {
+                self.backend.send_text(format!("\n❌ {:#}\n", e));
+                acp::StopReason::EndTurn
+            }
+            Err(e) if e.is_cancelled() => acp::StopReason::Cancelled,
+            Err(e) => {
+                self.backend.set_session(None);
+                return Err(internal_error(e));
+            }
+        };
+
+        // Every update must reach the client before the prompt response
+        self.backend.flush().await;
+        self.backend.set_session(None);
+        Ok(acp::PromptResponse::new(stop_reason))
+    }
+
+    async fn cancel(&self, args: acp::CancelNotification) -> acp::Result<()> {
+        if let Some(running) = self.running.borrow().as_ref()
+            && running.session_id == args.session_id
+        {
+            tracing::info!(session = %args.session_id.0, "Cancelling ACP prompt");
+            running.abort.abort();
+        }
+        Ok(())
+    }
+}
+
+fn load_model() -> Result<Arc<dyn adk_core::Llm>> {
+    let model_config = load_config().context("Failed to load LLM configuration. Run 'cowork config' to set up.")?;
+    create_llm_client(&model_config.llm).context("Failed to create LLM client")
+}
+
+fn latest_iteration_id(project: &Project) -> Option<String> {
+    project
+        .current_iteration_id
+        .clone()
+        .or_else(|| project.iterations.last().map(|i| i.id.clone()))
+}
+
+async fn run_prompt(backend: Arc<AcpBackend>, command: PromptCommand, history: Vec<Value>) -> Result<String> {
+    let mut project = ProjectStore::new()
+        .load()?
+        .ok_or_else(|| anyhow::anyhow!("No project found"))?;
+
+    match command {
+        PromptCommand::Iterate(title) if title.is_empty() => anyhow::bail!("Usage: /iter <title>"),
+        PromptCommand::Iterate(text) => start_iteration(&backend, &mut project, &text).await,
+        PromptCommand::Continue(id) => {
+            let id = match id {
+                Some(id) => id,
+                None => project
+                    .iterations
+                    .iter()
+                    .rev()
+                    .find(|i| i.status == IterationStatus::Paused)
+                    .map(|i| i.id.clone())
+                    .ok_or_else(|| anyhow::anyhow!("No paused iteration to continue"))?,
+            };
+            let executor = IterationExecutor::new(backend.clone());
+            let result = executor.continue_iteration(&mut project, &id, Some(load_model()?)).await;
+            finish(&id, result)
+        }
+        PromptCommand::Message(text) if project.iterations.is_empty() => {
+            start_iteration(&backend, &mut project, &text).await
+        }
+        PromptCommand::Message(text) => pm_message(&backend, &mut project, text, history).await,
+    }
+}
+
+/// Create an iteration from a prompt (first line is the title) and run it.
+/// Later iterations evolve from the latest one.
+async fn start_iteration(backend: &Arc<AcpBackend>, project: &mut Project, text: &str) -> Result<String> {
+    let model = load_model()?;
+    let title: String = text.lines().next().unwrap_or(text).chars().take(MAX_TITLE_CHARS).collect();
+    let executor = IterationExecutor::new(backend.clone());
+    let iteration = match latest_iteration_id(project) {
+        Some(base) => executor.create_evolution_iteration(project, title, text, base, InheritanceMode::Partial)?,
+        None => executor.create_genesis_iteration(project, title, text)?,
+    };
+    backend.send_text(format!("✨ Created iteration '{}' ({})\n", iteration.title, iteration.id));
+
+    let result = executor.execute(project, &iteration.id, None, Some(model)).await;
+    finish(&iteration.id, result)
+}
+
+/// Summary of a finished run, which also ends up in the PM history
+fn finish(iteration_id: &str, result: Result<()>) -> Result<String> {
+    result?;
+    let status = IterationStore::new().load(iteration_id).map(|i| i.status).ok();
+    Ok(match status {
+        Some(IterationStatus::Paused) => format!(
+            "⏸️ Iteration '{}' is paused. Send /continue {} to resume.",
+            iteration_id, iteration_id
+        ),
+        Some(IterationStatus::Completed) => format!("✅ Iteration '{}' completed.", iteration_id),
+        status => format!("Iteration '{}' ended with status {:?}.", iteration_id, status),
+    })
+}
+
+struct PmStream(Arc<AcpBackend>);
+
+#[async_trait]
+impl PMAgentStreamCallback for PmStream {
+    async fn on_text_chunk(&self, text: &str, _is_first: bool, _is_last: bool) {
+        self.0.send_text(text);
+    }
+
+    async fn on_tool_call(&self, tool_name: &str, _args: &Value) {
+        tracing::debug!(tool = tool_name, "PM agent tool call");
+    }
+}
+
+/// Ask the client to confirm a PM action
+async fn confirm(backend: &AcpBackend, prompt: String) -> Result<bool> {
+    let options = vec![
+        InputOption { id: "yes".to_string(), label: "Yes".to_string(), description: None },
+        InputOption { id: "no".to_string(), label: "No".to_string(), description: None },
+    ];
+    let response = backend.request_input(&prompt, options, None).await?;
+    Ok(matches!(response, InputResponse::Selection(id) if id == "yes"))
+}
+
+/// Chat with the PM agent, then run the actions it proposes once confirmed
+async fn pm_message(backend: &Arc<AcpBackend>, project: &mut Project, text: String, history: Vec<Value>) -> Result<String> {
+    let iteration_id = latest_iteration_id(project).ok_or_else(|| anyhow::anyhow!("No iteration found"))?;
+    let model = load_model()?;
+    let result = execute_pm_agent_message_streaming(
+        model.clone(),
+        iteration_id.clone(),
+        text,
+        history,
+        Some(Arc::new(PmStream(backend.clone()))),
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!("PM Agent execution failed: {}", e))?;
+
+    let executor = IterationExecutor::new(backend.clone());
+    let mut reply = result.message;
+    for action in result.actions {
+        let outcome = match action {
+            PMAgentAction::GotoStage { target_stage, reason } => {
+                if !confirm(backend, format!("Restart iteration '{}' from the {} stage?", iteration_id, target_stage)).await? {
+                    continue;
+                }
+                // The restarted stage reads this through load_feedback_history
+                if !reason.is_empty() {
+                    crate::persistence::set_iteration_id(iteration_id.clone());
+                    crate::persistence::append_feedback(&crate::data::Feedback {
+                        stage: "pm_agent".to_string(),
+                        feedback_type: crate::data::FeedbackType::QualityIssue,
+                        severity: crate::data::Severity::Major,
+                        details: reason,
+                        suggested_fix: Some(format!("Restart from {} stage via PM Agent", target_stage)),
+                        timestamp: chrono::Utc::now(),
+                    })?;
+                }
+                let result = executor.execute(project, &iteration_id, Some(target_stage), Some(model.clone())).await;
+                finish(&iteration_id, result)?
+            }
+            PMAgentAction::CreateIteration { iteration_id: new_id, title, .. } => {
+                if !confirm(backend, format!("Start the new iteration '{}'?", title)).await? {
+                    continue;
+                }
+                let result = executor.execute(project, &new_id, None, Some(model.clone())).await;
+                finish(&new_id, result)?
+            }
+        };
+        backend.send_text(format!("\n{}\n", outcome));
+        reply.push_str(&format!("\n{}", outcome));
+    }
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_command_parse() {
+        assert_eq!(PromptCommand::parse("/iter Add login\n"), PromptCommand::Iterate("Add login".to_string()));
+        assert_eq!(PromptCommand::parse("/continue"), PromptCommand::Continue(None));
+        assert_eq!(
+            PromptCommand::parse("/continue iter-2-123"),
+            PromptCommand::Continue(Some("iter-2-123".to_string()))
+        );
+        assert_eq!(
+            PromptCommand::parse("  make the header blue "),
+            PromptCommand::Message("make the header blue".to_string())
+        );
+        assert_eq!(PromptCommand::parse("/iterate"), PromptCommand::Message("/iterate".to_string()));
+    }
+}
@@ -0,0 +1,460 @@
+//! Terminals run on behalf of an external ACP agent
+//!
+//! Each terminal is a process spawned in the iteration workspace. Its stdout
+//! and stderr go into one buffer that keeps the most recent bytes up to the
+//! requested limit. Commands are checked with `RuntimeSecurityChecker` before
+//! they are spawned, and run inside the project's `Sandbox`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+
+use agent_client_protocol as acp;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::{mpsc, oneshot, watch};
+
+use super::AgentMessage;
+use crate::runtime_security::RuntimeSecurityChecker;
+use crate::sandbox::{kill_process_group, Sandbox};
+
+/// Output kept per terminal when the agent does not set a limit.
+const DEFAULT_OUTPUT_BYTE_LIMIT: usize = 1024 * 1024;
+
+/// Output included in the tool result mirrored to the UI.
+const MIRRORED_OUTPUT_BYTES: usize = 4000;
+
+/// Tool name used for terminal events
+pub const TERMINAL_TOOL_NAME: &str = "terminal";
+
+/// Output buffer that drops the oldest bytes past its limit
+#[derive(Debug)]
+struct OutputBuffer {
+    bytes: Vec<u8>,
+    limit: usize,
+    truncated: bool,
+}
+
+impl OutputBuffer {
+    fn new(limit: usize) -> Self {
+        Self {
+            bytes: Vec::new(),
+            limit,
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) {
+        self.bytes.extend_from_slice(chunk);
+        if self.bytes.len() > self.limit {
+            let mut cut = self.bytes.len() - self.limit;
+            // Never start in the middle of a UTF-8 sequence
+            while cut < self.bytes.len() && (self.bytes[cut] & 0b1100_0000) == 0b1000_0000 {
+                cut += 1;
+            }
+            self.bytes.drain(..cut);
+            self.truncated = true;
+        }
+    }
+
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.bytes).into_owned()
+    }
+}
+
+struct Terminal {
+    command_line: String,
+    output: Arc<Mutex<OutputBuffer>>,
+    exit: watch::Receiver<Option<acp::TerminalExitStatus>>,
+    kill: Option<oneshot::Sender<()>>,
+}
+
+/// Terminals of one ACP connection, keyed by terminal id
+pub struct TerminalManager {
+    workspace: PathBuf,
+    terminals: Mutex<HashMap<String, Terminal>>,
+    message_tx: mpsc::UnboundedSender<AgentMessage>,
+    security: RuntimeSecurityChecker,
+}
+
+impl TerminalManager {
+    pub fn new(workspace: PathBuf, message_tx: mpsc::UnboundedSender<AgentMessage>) -> Self {
+        Self {
+            workspace,
+            terminals: Mutex::new(HashMap::new()),
+            message_tx,
+            security: RuntimeSecurityChecker::new(),
+        }
+    }
+
+    /// Spawn the requested command and start collecting its output
+    pub fn create(
+        &self,
+        args: acp::CreateTerminalRequest,
+        cwd: PathBuf,
+    ) -> acp::Result<acp::CreateTerminalResponse> {
+        let (program, program_args) = resolve_command(&args.command, &args.args);
+        let command_line = display_command(&args.command, &args.args);
+        let checked = shell_payload(&program, &program_args).unwrap_or(&command_line);
+        if !self.security.is_command_safe(checked) {
+            tracing::warn!(command = %command_line, "ACP terminal command denied");
+            let _ = self.message_tx.send(AgentMessage::Status(format!(
+                "Denied terminal command: {}",
+                command_line
+            )));
+            return Err(acp::Error::invalid_params().data(format!(
+                "command '{}' is not allowed by the runtime security policy",
+                command_line
+            )));
+        }
+
+        let sandbox = Sandbox::for_workspace(&self.workspace)
+            .map_err(|e| acp::Error::internal_error().data(format!("{:#}", e)))?;
+        let mut cmd = sandbox
+            .check(checked)
+            .and_then(|()| sandbox.command(&program, &program_args, &cwd))
+            .map_err(|e| {
+                tracing::warn!(command = %command_line, error = %e, "ACP terminal command refused by sandbox");
+                acp::Error::invalid_params().data(e.to_string())
+            })?;
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        for var in &args.env {
+            cmd.env(&var.name, &var.value);
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            #[allow(unused_imports)]
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        let mut child = cmd.spawn().map_err(|e| {
+            acp::Error::internal_error().data(format!("failed to spawn '{}': {}", command_line, e))
+        })?;
+
+        let terminal_id = format!("term-{}", uuid::Uuid::new_v4().simple());
+        let limit = args
+            .output_byte_limit
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_OUTPUT_BYTE_LIMIT);
+        let output = Arc::new(Mutex::new(OutputBuffer::new(limit)));
+
+        let readers: Vec<_> = [
+            child.stdout.take().map(|s| Box::new(s) as Box<dyn AsyncRead + Unpin + Send>),
+            child.stderr.take().map(|s| Box::new(s) as Box<dyn AsyncRead + Unpin + Send>),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|reader| tokio::spawn(collect_output(reader, output.clone())))
+        .collect();
+
+        let (exit_tx, exit_rx) = watch::channel(None);
+        let (kill_tx, kill_rx) = oneshot::channel();
+
+        tracing::info!(terminal_id, command = %command_line, cwd = %cwd.display(), "ACP terminal started");
+        let _ = self.message_tx.send(AgentMessage::ToolCall {
+            name: TERMINAL_TOOL_NAME.to_string(),
+            arguments: serde_json::json!({
+                "terminal_id": terminal_id,
+                "command": command_line,
+                "cwd": cwd.strip_prefix(&self.workspace).unwrap_or(&cwd).display().to_string(),
+            }),
+        });
+
+        let message_tx = self.message_tx.clone();
+        let watched_output = output.clone();
+        let id = terminal_id.clone();
+        let line = command_line.clone();
+        tokio::spawn(async move {
+            let status = tokio::select! {
+                status = child.wait() => status,
+                _ = kill_rx => {
+                    kill_process_group(child.id());
+                    let _ = child.start_kill();
+                    child.wait().await
+                }
+            };
+            // Drain the pipes before reporting, so the final output is complete
+            for reader in readers {
+                let _ = reader.await;
+            }
+
+            let exit_status = match status {
+                Ok(status) => exit_status_of(status),
+                Err(e) => {
+                    tracing::warn!(terminal_id = %id, error = %e, "ACP terminal wait failed");
+                    acp::TerminalExitStatus::new()
+                }
+            };
+            let success = exit_status.exit_code == Some(0);
+            tracing::info!(terminal_id = %id, exit = ?exit_status.exit_code, signal = ?exit_status.signal, "ACP terminal exited");
+
+            let text = watched_output.lock().map(|o| o.text()).unwrap_or_default();
+            let _ = message_tx.send(AgentMessage::ToolResult {
+                name: TERMINAL_TOOL_NAME.to_string(),
+                result: format!("$ {}\n{}{}", line, tail(&text, MIRRORED_OUTPUT_BYTES), describe_exit(&exit_status)),
+                success,
+            });
+            let _ = exit_tx.send(Some(exit_status));
+        });
+
+        if let Ok(mut terminals) = self.terminals.lock() {
+            terminals.insert(
+                terminal_id.clone(),
+                Terminal {
+                    command_line,
+                    output,
+                    exit: exit_rx,
+                    kill: Some(kill_tx),
+                },
+            );
+        }
+        Ok(acp::CreateTerminalResponse::new(terminal_id))
+    }
+
+    /// Output so far, plus the exit status once the command has finished
+    pub fn output(&self, terminal_id: &acp::TerminalId) -> acp::Result<acp::TerminalOutputResponse> {
+        let terminals = self.terminals.lock().map_err(|_| acp::Error::internal_error())?;
+        let terminal = find(&terminals, terminal_id)?;
+        let (text, truncated) = terminal
+            .output
+            .lock()
+            .map(|o| (o.text(), o.truncated))
+            .map_err(|_| acp::Error::internal_error())?;
+        let exit_status = terminal.exit.borrow().clone();
+        Ok(acp::TerminalOutputResponse::new(text, truncated).exit_status(exit_status))
+    }
+
+    /// Wait until the command exits
+    pub async fn wait_for_exit(
+        &self,
+        terminal_id: &acp::TerminalId,
+    ) -> acp::Result<acp::WaitForTerminalExitResponse> {
+        let mut exit = {
+            let terminals = self.terminals.lock().map_err(|_| acp::Error::internal_error())?;
+            find(&terminals, terminal_id)?.exit.clone()
+        };
+        let status = exit
+            .wait_for(Option::is_some)
+            .await
+            .map_err(acp::Error::into_internal_error)?
+            .clone()
+            .unwrap_or_default();
+        Ok(acp::WaitForTerminalExitResponse::new(status))
+    }
+
+    /// Kill the command but keep the terminal, so its output can still be read
+    pub fn kill(&self, terminal_id: &acp::TerminalId) -> acp::Result<acp::KillTerminalCommandResponse> {
+        let mut terminals = self.terminals.lock().map_err(|_| acp::Error::internal_error())?;
+        let terminal = terminals
+            .get_mut(terminal_id.0.as_ref())
+            .ok_or_else(|| unknown_terminal(terminal_id))?;
+        if let Some(kill) = terminal.kill.take() {
+            tracing::info!(terminal_id = %terminal_id.0, command = %terminal.command_line, "Killing ACP terminal command");
+            let _ = kill.send(());
+        }
+        Ok(acp::KillTerminalCommandResponse::new())
+    }
+
+    /// Kill the command if it is still running and forget the terminal
+    pub fn release(&self, terminal_id: &acp::TerminalId) -> acp::Result<acp::ReleaseTerminalResponse> {
+        let mut terminals = self.terminals.lock().map_err(|_| acp::Error::internal_error())?;
+        let mut terminal = terminals
+            .remove(terminal_id.0.as_ref())
+            .ok_or_else(|| unknown_terminal(terminal_id))?;
+        if let Some(kill) = terminal.kill.take() {
+            let _ = kill.send(());
+        }
+        Ok(acp::ReleaseTerminalResponse::new())
+    }
+
+    /// Kill every command that is still running
+    pub fn kill_all(&self) {
+        if let Ok(mut terminals) = self.terminals.lock() {
+            for (_, mut terminal) in terminals.drain() {
+                if let Some(kill) = terminal.kill.take() {
+                    let _ = kill.send(());
+                }
+            }
+        }
+    }
+}
+
+fn find<'a>(terminals: &'a HashMap<String, Terminal>, id: &acp::TerminalId) -> acp::Result<&'a Terminal> {
+    terminals.get(id.0.as_ref()).ok_or_else(|| unknown_terminal(id))
+}
+
+fn unknown_terminal(id: &acp::TerminalId) -> acp::Error {
+    acp::Error::resource_not_found(Some(id.0.to_string()))
+}
+
+async fn collect_output(mut reader: Box<dyn AsyncRead + Unpin + Send>, output: Arc<Mutex<OutputBuffer>>) {
+    let mut chunk = [0u8; 8192];
+    loop {
+        match reader.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                if let Ok(mut out) = output.lock() {
+                    out.push(&chunk[..n]);
+                }
+            }
+        }
+    }
+}
+
+/// Agents send either a program with arguments, or a whole command line with
+/// no arguments; the latter is run through the platform shell.
+fn resolve_command(command: &str, args: &[String]) -> (String, Vec<String>) {
+    if !args.is_empty() || !command.trim().contains(char::is_whitespace) {
+        return (command.to_string(), args.to_vec());
+    }
+    if cfg!(target_os = "windows") {
+        ("cmd".to_string(), vec!["/C".to_string(), command.to_string()])
+    } else {
+        ("sh".to_string(), vec!["-c".to_string(), command.to_string()])
+    }
+}
+
+/// The command line passed to a shell (`sh -c <payload>`), which is what the
+/// security check has to look at
+fn shell_payload<'a>(program: &str, args: &'a [String]) -> Option<&'a str> {
+    let name = Path::new(program)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(program)
+        .to_lowercase();
+    let is_shell = matches!(name.as_str(), "sh" | "bash" | "zsh" | "dash" | "cmd" | "powershell" | "pwsh");
+    match args {
+        [flag, payload, ..] if is_shell && matches!(flag.to_lowercase().as_str(), "-c" | "/c" | "-command") => {
+            Some(payload.as_str())
+        }
+        _ => None,
+    }
+}
+
+fn display_command(command: &str, args: &[String]) -> String {
+    std::iter::once(command.to_string())
+        .chain(args.iter().map(|a| {
+            if a.contains(char::is_whitespace) {
+                format!("'{}'", a)
+            } else {
+                a.clone()
+            }
+        }))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn exit_status_of(status: std::process::ExitStatus) -> acp::TerminalExitStatus {
+    let exit = acp::TerminalExitStatus::new().exit_code(status.code().map(|c| c as u32));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            let name = match signal {
+                1 => "SIGHUP".to_string(),
+                2 => "SIGINT".to_string(),
+                9 => "SIGKILL".to_string(),
+                15 => "SIGTERM".to_string(),
+                other => format!("signal {}", other),
+            };
+            return exit.signal(name);
+        }
+    }
+    exit
+}
+
+fn describe_exit(status: &acp::TerminalExitStatus) -> String {
+    match (&status.exit_code, &status.signal) {
+        (_, Some(signal)) => format!("\n[killed by {}]", signal),
+        (Some(code), None) => format!("\n[exit code {}]", code),
+        (None, None) => "\n[exited]".to_string(),
+    }
+}
+
+/// Last `max` bytes of `text`, starting at a character boundary
+fn tail(text: &str, max: usize) -> &str {
+    if text.len() <= max {
+        return text;
+    }
+    let mut start = text.len() - max;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    &text[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_buffer_keeps_tail_on_char_boundary() {
+        let mut buffer = OutputBuffer::new(5);
+        buffer.push("ab".as_bytes());
+        assert!(!buffer.truncated);
+        buffer.push("cdé".as_bytes());
+        buffer.push("éf".as_bytes());
+        assert!(buffer.truncated);
+        assert_eq!(buffer.text(), "ééf");
+        // Dropping one byte would split the first 'é'
+        buffer.push(b"g");
+        assert_eq!(buffer.text(), "éfg");
+    }
+
+    #[test]
+    fn test_resolve_command_and_shell_payload() {
+        let (program, args) = resolve_command("npm test", &[]);
+        if cfg!(not(target_os = "windows")) {
+            assert_eq!(program, "sh");
+            assert_eq!(shell_payload(&program, &args), Some("npm test"));
+        }
+
+        let (program, args) = resolve_command("cargo", &["test".to_string()]);
+        assert_eq!(program, "cargo");
+        assert_eq!(shell_payload(&program, &args), None);
+        assert_eq!(
+            shell_payload("/bin/bash", &["-c".to_string(), "rm -rf /".to_string()]),
+            Some("rm -rf /")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_terminal_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let manager = TerminalManager::new(dir.path().to_path_buf(), tx);
+
+        let denied = acp::CreateTerminalRequest::new("s1", "rm -rf /");
+        assert!(manager.create(denied, dir.path().to_path_buf()).is_err());
+
+        if cfg!(target_os = "windows") {
+            return;
+        }
+        let request = acp::CreateTerminalRequest::new("s1", "python3 -c \"print('hello')\"");
+        let Ok(created) = manager.create(request, dir.path().to_path_buf()) else {
+            return; // python3 not installed
+        };
+        let exit = manager.wait_for_exit(&created.terminal_id).await.unwrap();
+        let output = manager.output(&created.terminal_id).unwrap();
+        assert_eq!(output.output.trim(), "hello");
+        assert_eq!(exit.exit_status.exit_code, Some(0));
+        assert!(output.exit_status.is_some());
+
+        let mut saw_result = false;
+        while let Ok(message) = rx.try_recv() {
+            if let AgentMessage::ToolResult { success, .. } = message {
+                saw_result = success;
+            }
+        }
+        assert!(saw_result);
+
+        manager.release(&created.terminal_id).unwrap();
+        assert!(manager.output(&created.terminal_id).is_err());
+    }
+}
@@ -2,12 +2,15 @@
 // This module provides an alternative to the built-in adk-rust coding agent
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use tokio::sync::mpsc;
 
+use crate::acp::permission::PermissionPrompter;
 use crate::acp::{AcpClient, AcpTaskResult, AgentMessage};
 use crate::domain::{InheritanceMode, Iteration};
+use crate::interaction::InteractiveBackend;
 use crate::instructions::coding::CODING_ACTOR_INSTRUCTION;
 use crate::llm::config::{load_config, CodingAgentConfig};
 
@@ -27,6 +30,8 @@ pub struct ExternalCodingAgent {
     ready: bool,
     /// Optional iteration context for evolution iterations
     iteration: Option<Iteration>,
+    /// Where permission requests the policy marks "ask" are sent
+    prompter: Option<PermissionPrompter>,
 }
 
 /// Result of starting a streaming task
@@ -64,9 +69,20 @@ impl ExternalCodingAgent {
             workspace: workspace.clone(),
             ready: false,
             iteration,
+            prompter: None,
         })
     }
 
+    /// Ask the user through `interaction` for permissions the policy marks "ask".
+    /// Must be called from within the tokio runtime the backend runs on.
+    pub fn with_interaction(mut self, interaction: Arc<dyn InteractiveBackend>) -> Self {
+        self.prompter = Some(PermissionPrompter {
+            interaction,
+            runtime: tokio::runtime::Handle::current(),
+        });
+        self
+    }
+
     /// Check if external agent is enabled in config
     pub fn is_enabled() -> Result<bool> {
         let config = load_config()
@@ -90,6 +106,7 @@ impl ExternalCodingAgent {
             self.config,
             self.workspace,
             prompt,
+            self.prompter,
         );
 
         StreamingTask {
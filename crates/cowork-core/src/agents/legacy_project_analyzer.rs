@@ -7,6 +7,9 @@ use crate::tools::{
     ReadProjectFileTool,
     ListProjectDirectoryTool,
     SaveArtifactTool,
+    SearchCodeTool,
+    FindFilesTool,
+    SearchScope,
 };
 use adk_agent::LlmAgentBuilder;
 use adk_core::{IncludeContents, Llm};
@@ -32,6 +35,8 @@ pub fn create_legacy_project_analyzer(model: Arc<dyn Llm>) -> Result<Arc<dyn adk
         .tool(Arc::new(DetectTechStackTool))
         .tool(Arc::new(ReadProjectFileTool))
         .tool(Arc::new(ListProjectDirectoryTool))
+        .tool(Arc::new(FindFilesTool::new(SearchScope::Project)))
+        .tool(Arc::new(SearchCodeTool::new(SearchScope::Project)))
         // Artifact generation tools
         .tool(Arc::new(SaveArtifactTool))
         .include_contents(IncludeContents::None)
@@ -58,6 +63,8 @@ pub fn create_legacy_project_analyzer_with_id(
         .tool(Arc::new(DetectTechStackTool))
         .tool(Arc::new(ReadProjectFileTool))
         .tool(Arc::new(ListProjectDirectoryTool))
+        .tool(Arc::new(FindFilesTool::new(SearchScope::Project)))
+        .tool(Arc::new(SearchCodeTool::new(SearchScope::Project)))
         // Artifact generation tools
         .tool(Arc::new(SaveArtifactTool))
         .include_contents(IncludeContents::None)
@@ -86,6 +93,8 @@ pub fn create_legacy_project_analyzer_with_context(
         .tool(Arc::new(DetectTechStackTool))
         .tool(Arc::new(ReadProjectFileTool))
         .tool(Arc::new(ListProjectDirectoryTool))
+        .tool(Arc::new(FindFilesTool::new(SearchScope::Project)))
+        .tool(Arc::new(SearchCodeTool::new(SearchScope::Project)))
         // Artifact generation tools
         .tool(Arc::new(SaveArtifactTool))
         .include_contents(IncludeContents::None)
@@ -78,6 +78,7 @@ pub fn create_knowledge_generation_agent(
         .tool(Arc::new(LoadDocumentSummaryTool::new(iteration_id.clone())))
         .tool(read_file_with_limit.clone())
         .tool(Arc::new(ListFilesWorkspaceTool))
+        .tool(Arc::new(QueryCodeIndexTool::new(iteration_id.clone())))
         .tool(Arc::new(SaveKnowledgeSnapshotTool::new(iteration_id.clone(), iteration_number)))
         .include_contents(IncludeContents::None);
 
@@ -111,7 +112,7 @@ pub fn create_project_manager_agent(model: Arc<dyn Llm>, iteration_id: String) -
         .include_contents(IncludeContents::None);
 
     // Add MCP toolsets if available
-    builder = crate::config_definition::agent_factory::add_mcp_toolsets_to_builder(builder);
+    builder = crate::config_definition::agent_factory::add_mcp_toolsets_to_builder(builder, "pm_agent", &[]);
 
     let agent = builder.build()?;
 
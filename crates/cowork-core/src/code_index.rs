@@ -0,0 +1,782 @@
+// Code Structure Index - Deterministic, language-aware index of a workspace
+//
+// Extracts files, modules, public symbols and import edges without an LLM so
+// knowledge snapshots and coding agents can look up "where is X defined" and
+// "who depends on this file" instead of re-reading whole files.
+//
+// Supported languages: Rust, TypeScript/JavaScript, Python, Go.
+// Extraction is line/regex based: it favours speed and stability over
+// complete parsing (macros, nested declarations etc. are not resolved).
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Files larger than this are listed but not parsed
+const MAX_INDEXED_FILE_BYTES: u64 = 512 * 1024;
+
+/// Languages understood by the indexer
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeLanguage {
+    Rust,
+    TypeScript,
+    JavaScript,
+    Python,
+    Go,
+}
+
+impl CodeLanguage {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "ts" | "tsx" | "mts" | "cts" => Some(Self::TypeScript),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "py" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of an indexed symbol
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Struct,
+    Class,
+    Enum,
+    Trait,
+    Interface,
+    TypeAlias,
+    Constant,
+    Variable,
+    Module,
+}
+
+impl SymbolKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(value.to_lowercase())).ok()
+    }
+}
+
+/// A public symbol declared in a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// 1-based line number of the declaration
+    pub line: usize,
+    /// Declaration line, trimmed (e.g. `pub fn load(path: &Path) -> Result<Config>`)
+    pub signature: String,
+}
+
+/// An indexed source file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedFile {
+    /// Path relative to the workspace root, using `/` separators
+    pub path: String,
+    pub language: CodeLanguage,
+    /// Module path derived from the file location (e.g. `tools::file_tools`, `app.models`)
+    pub module: String,
+    pub line_count: usize,
+    pub symbols: Vec<CodeSymbol>,
+    /// Raw import specifiers as written in the source
+    pub imports: Vec<String>,
+}
+
+/// Dependency edge between two files (or a file and an external package)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportEdge {
+    pub from: String,
+    /// Workspace file path when resolved, otherwise the raw import specifier
+    pub to: String,
+    /// Whether `to` is a file inside the workspace
+    pub internal: bool,
+}
+
+/// Code structure index of a workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeIndex {
+    pub iteration_id: String,
+    pub generated_at: DateTime<Utc>,
+    pub files: Vec<IndexedFile>,
+    pub edges: Vec<ImportEdge>,
+}
+
+impl CodeIndex {
+    /// Build an index for every supported source file under `workspace`,
+    /// honouring `.gitignore`/`.ignore` files and skipping hidden entries.
+    pub fn build(iteration_id: impl Into<String>, workspace: &Path) -> anyhow::Result<Self> {
+        let mut files = Vec::new();
+
+        for entry in ignore::WalkBuilder::new(workspace).build().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+                continue;
+            }
+            let Some(language) = CodeLanguage::from_path(path) else {
+                continue;
+            };
+            let Ok(rel) = path.strip_prefix(workspace) else {
+                continue;
+            };
+            let rel_path = rel.to_string_lossy().replace('\\', "/");
+            if is_vendored_path(&rel_path) {
+                continue;
+            }
+
+            let too_large = entry
+                .metadata()
+                .map(|m| m.len() > MAX_INDEXED_FILE_BYTES)
+                .unwrap_or(true);
+            let content = if too_large {
+                String::new()
+            } else {
+                std::fs::read_to_string(path).unwrap_or_default()
+            };
+
+            files.push(index_file(&rel_path, language, &content));
+        }
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let edges = resolve_edges(&files);
+
+        Ok(Self {
+            iteration_id: iteration_id.into(),
+            generated_at: Utc::now(),
+            files,
+            edges,
+        })
+    }
+
+    /// Whether any indexed-language file in `workspace` changed after this index was built
+    pub fn is_stale(&self, workspace: &Path) -> bool {
+        let generated_at: std::time::SystemTime = self.generated_at.into();
+        let mut seen = 0usize;
+
+        for entry in ignore::WalkBuilder::new(workspace).build().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if CodeLanguage::from_path(path).is_none() {
+                continue;
+            }
+            let Ok(rel) = path.strip_prefix(workspace) else {
+                continue;
+            };
+            if is_vendored_path(&rel.to_string_lossy().replace('\\', "/")) {
+                continue;
+            }
+            seen += 1;
+            let modified = entry.metadata().ok().and_then(|m| m.modified().ok());
+            if modified.is_none_or(|m| m > generated_at) {
+                return true;
+            }
+        }
+
+        // Files were deleted since the index was built
+        seen != self.files.len()
+    }
+
+    pub fn file(&self, path: &str) -> Option<&IndexedFile> {
+        let normalized = normalize_query_path(path);
+        self.files.iter().find(|f| f.path == normalized)
+    }
+
+    /// Find symbols whose name contains `name` (case-insensitive), optionally
+    /// filtered by kind and file path prefix
+    pub fn find_symbols(
+        &self,
+        name: Option<&str>,
+        kind: Option<SymbolKind>,
+        path_prefix: Option<&str>,
+    ) -> Vec<(&IndexedFile, &CodeSymbol)> {
+        let name_lower = name.map(|n| n.to_lowercase());
+        let prefix = path_prefix.map(normalize_query_path);
+
+        let mut matches: Vec<(&IndexedFile, &CodeSymbol)> = self
+            .files
+            .iter()
+            .filter(|f| prefix.as_ref().is_none_or(|p| f.path.starts_with(p.as_str())))
+            .flat_map(|f| f.symbols.iter().map(move |s| (f, s)))
+            .filter(|(_, s)| kind.is_none_or(|k| s.kind == k))
+            .filter(|(_, s)| {
+                name_lower
+                    .as_ref()
+                    .is_none_or(|n| s.name.to_lowercase().contains(n.as_str()))
+            })
+            .collect();
+
+        // Exact matches first
+        if let Some(n) = &name_lower {
+            matches.sort_by_key(|(_, s)| s.name.to_lowercase() != *n);
+        }
+        matches
+    }
+
+    /// Files and external packages imported by `path`
+    pub fn dependencies_of(&self, path: &str) -> Vec<&ImportEdge> {
+        let normalized = normalize_query_path(path);
+        self.edges.iter().filter(|e| e.from == normalized).collect()
+    }
+
+    /// Workspace files importing `path`
+    pub fn dependents_of(&self, path: &str) -> Vec<&str> {
+        let normalized = normalize_query_path(path);
+        self.edges
+            .iter()
+            .filter(|e| e.internal && e.to == normalized)
+            .map(|e| e.from.as_str())
+            .collect()
+    }
+
+    /// Compact per-language/per-directory overview, suitable for prompts and
+    /// `IterationKnowledge.code_structure`
+    pub fn overview(&self, max_files: usize) -> String {
+        let mut by_language: BTreeMap<String, usize> = BTreeMap::new();
+        for f in &self.files {
+            *by_language.entry(format!("{:?}", f.language)).or_default() += 1;
+        }
+
+        let mut lines = vec![format!(
+            "{} source files, {} public symbols, {} internal import edges ({})",
+            self.files.len(),
+            self.files.iter().map(|f| f.symbols.len()).sum::<usize>(),
+            self.edges.iter().filter(|e| e.internal).count(),
+            by_language
+                .iter()
+                .map(|(lang, count)| format!("{}: {}", lang, count))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )];
+
+        // Most depended-upon files first, then the ones with the most symbols
+        let mut ranked: Vec<&IndexedFile> = self.files.iter().collect();
+        ranked.sort_by_key(|f| {
+            (
+                std::cmp::Reverse(self.dependents_of(&f.path).len()),
+                std::cmp::Reverse(f.symbols.len()),
+            )
+        });
+
+        for f in ranked.into_iter().take(max_files) {
+            let names: Vec<&str> = f.symbols.iter().take(8).map(|s| s.name.as_str()).collect();
+            let more = f.symbols.len().saturating_sub(names.len());
+            lines.push(format!(
+                "- {} [{}]: {}{}",
+                f.path,
+                f.module,
+                if names.is_empty() { "-".to_string() } else { names.join(", ") },
+                if more > 0 { format!(" (+{} more)", more) } else { String::new() }
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn is_vendored_path(rel_path: &str) -> bool {
+    const SKIPPED_DIRS: [&str; 8] = [
+        "node_modules", "target", "dist", "build", "vendor", "__pycache__", ".venv", "venv",
+    ];
+    rel_path.split('/').any(|part| SKIPPED_DIRS.contains(&part))
+}
+
+fn normalize_query_path(path: &str) -> String {
+    path.trim().trim_start_matches("./").replace('\\', "/")
+}
+
+// ============================================================================
+// Per-language extraction
+// ============================================================================
+
+struct SymbolPattern {
+    regex: Regex,
+    kind: SymbolKind,
+}
+
+fn patterns(specs: &[(&str, SymbolKind)]) -> Vec<SymbolPattern> {
+    specs
+        .iter()
+        .map(|(re, kind)| SymbolPattern {
+            regex: Regex::new(re).unwrap(),
+            kind: *kind,
+        })
+        .collect()
+}
+
+static RUST_SYMBOLS: LazyLock<Vec<SymbolPattern>> = LazyLock::new(|| {
+    patterns(&[
+        (r"^\s*pub(?:\([^)]*\))?\s+(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+\S+\s+)?fn\s+([A-Za-z_]\w*)", SymbolKind::Function),
+        (r"^\s*pub(?:\([^)]*\))?\s+struct\s+([A-Za-z_]\w*)", SymbolKind::Struct),
+        (r"^\s*pub(?:\([^)]*\))?\s+enum\s+([A-Za-z_]\w*)", SymbolKind::Enum),
+        (r"^\s*pub(?:\([^)]*\))?\s+(?:unsafe\s+)?trait\s+([A-Za-z_]\w*)", SymbolKind::Trait),
+        (r"^\s*pub(?:\([^)]*\))?\s+type\s+([A-Za-z_]\w*)", SymbolKind::TypeAlias),
+        (r"^\s*pub(?:\([^)]*\))?\s+(?:const|static)\s+(?:mut\s+)?([A-Za-z_]\w*)", SymbolKind::Constant),
+        (r"^\s*pub(?:\([^)]*\))?\s+mod\s+([A-Za-z_]\w*)", SymbolKind::Module),
+    ])
+});
+
+static TS_SYMBOLS: LazyLock<Vec<SymbolPattern>> = LazyLock::new(|| {
+    patterns(&[
+        (r"^\s*export\s+(?:default\s+)?(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)", SymbolKind::Function),
+        (r"^\s*export\s+(?:default\s+)?(?:abstract\s+)?class\s+([A-Za-z_$][\w$]*)", SymbolKind::Class),
+        (r"^\s*export\s+interface\s+([A-Za-z_$][\w$]*)", SymbolKind::Interface),
+        (r"^\s*export\s+type\s+([A-Za-z_$][\w$]*)", SymbolKind::TypeAlias),
+        (r"^\s*export\s+(?:const\s+)?enum\s+([A-Za-z_$][\w$]*)", SymbolKind::Enum),
+        (r"^\s*export\s+const\s+([A-Za-z_$][\w$]*)\s*(?::[^=]*)?=\s*(?:async\s+)?(?:\([^)]*\)|[A-Za-z_$][\w$]*)\s*(?::[^=]*)?=>", SymbolKind::Function),
+        (r"^\s*export\s+(?:const|let|var)\s+([A-Za-z_$][\w$]*)", SymbolKind::Variable),
+        (r"^\s*module\.exports\.([A-Za-z_$][\w$]*)\s*=", SymbolKind::Variable),
+        (r"^\s*exports\.([A-Za-z_$][\w$]*)\s*=", SymbolKind::Variable),
+    ])
+});
+
+static PYTHON_SYMBOLS: LazyLock<Vec<SymbolPattern>> = LazyLock::new(|| {
+    patterns(&[
+        (r"^(?:async\s+)?def\s+([A-Za-z]\w*)", SymbolKind::Function),
+        (r"^class\s+([A-Za-z]\w*)", SymbolKind::Class),
+        (r"^    (?:async\s+)?def\s+([A-Za-z]\w*)", SymbolKind::Method),
+        (r"^([A-Z][A-Z0-9_]*)\s*(?::[^=]*)?=", SymbolKind::Constant),
+    ])
+});
+
+static GO_SYMBOLS: LazyLock<Vec<SymbolPattern>> = LazyLock::new(|| {
+    patterns(&[
+        (r"^func\s+\([^)]*\)\s+([A-Z]\w*)", SymbolKind::Method),
+        (r"^func\s+([A-Z]\w*)", SymbolKind::Function),
+        (r"^type\s+([A-Z]\w*)\s+struct\b", SymbolKind::Struct),
+        (r"^type\s+([A-Z]\w*)\s+interface\b", SymbolKind::Interface),
+        (r"^type\s+([A-Z]\w*)", SymbolKind::TypeAlias),
+        (r"^(?:const|var)\s+([A-Z]\w*)", SymbolKind::Constant),
+    ])
+});
+
+static RUST_USE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+([^;{]+?)(?:::\{|;)").unwrap());
+static RUST_MOD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+([A-Za-z_]\w*)\s*;").unwrap());
+static JS_IMPORT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:^\s*import\s+(?:[^'"]*?\s+from\s+)?|^\s*export\s+[^'"]*?\s+from\s+|\brequire\(\s*|\bimport\(\s*)['"]([^'"]+)['"]"#).unwrap()
+});
+static PY_IMPORT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:from\s+(\.*[\w.]*)\s+import\b|import\s+([\w.]+(?:\s*,\s*[\w.]+)*))").unwrap());
+static GO_IMPORT_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*import\s+(?:[\w.]+\s+)?"([^"]+)""#).unwrap());
+static GO_IMPORT_SPEC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*(?:[\w.]+\s+)?"([^"]+)""#).unwrap());
+
+fn index_file(rel_path: &str, language: CodeLanguage, content: &str) -> IndexedFile {
+    let symbol_patterns: &[SymbolPattern] = match language {
+        CodeLanguage::Rust => &RUST_SYMBOLS,
+        CodeLanguage::TypeScript | CodeLanguage::JavaScript => &TS_SYMBOLS,
+        CodeLanguage::Python => &PYTHON_SYMBOLS,
+        CodeLanguage::Go => &GO_SYMBOLS,
+    };
+
+    let mut symbols = Vec::new();
+    let mut imports = Vec::new();
+    let mut in_go_import_block = false;
+    let mut in_block_comment = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+
+        // Skip comments (block comments only for C-like languages)
+        if language != CodeLanguage::Python {
+            if in_block_comment {
+                if trimmed.contains("*/") {
+                    in_block_comment = false;
+                }
+                continue;
+            }
+            if trimmed.starts_with("/*") {
+                in_block_comment = !trimmed.contains("*/");
+                continue;
+            }
+            if trimmed.starts_with("//") {
+                continue;
+            }
+        } else if trimmed.starts_with('#') {
+            continue;
+        }
+
+        for pattern in symbol_patterns {
+            if let Some(caps) = pattern.regex.captures(line) {
+                let name = caps[1].to_string();
+                // Python: only public names; methods must belong to a class body
+                if language == CodeLanguage::Python && name.starts_with('_') {
+                    break;
+                }
+                symbols.push(CodeSymbol {
+                    name,
+                    kind: pattern.kind,
+                    line: idx + 1,
+                    signature: line.trim().trim_end_matches('{').trim_end().chars().take(200).collect(),
+                });
+                break;
+            }
+        }
+
+        match language {
+            CodeLanguage::Rust => {
+                if let Some(caps) = RUST_USE.captures(line) {
+                    imports.push(caps[1].trim().to_string());
+                } else if let Some(caps) = RUST_MOD.captures(line) {
+                    imports.push(format!("self::{}", &caps[1]));
+                }
+            }
+            CodeLanguage::TypeScript | CodeLanguage::JavaScript => {
+                for caps in JS_IMPORT.captures_iter(line) {
+                    imports.push(caps[1].to_string());
+                }
+            }
+            CodeLanguage::Python => {
+                if let Some(caps) = PY_IMPORT.captures(line) {
+                    if let Some(from) = caps.get(1) {
+                        imports.push(from.as_str().to_string());
+                    } else if let Some(list) = caps.get(2) {
+                        imports.extend(list.as_str().split(',').map(|s| s.trim().to_string()));
+                    }
+                }
+            }
+            CodeLanguage::Go => {
+                if in_go_import_block {
+                    if trimmed.starts_with(')') {
+                        in_go_import_block = false;
+                    } else if let Some(caps) = GO_IMPORT_SPEC.captures(line) {
+                        imports.push(caps[1].to_string());
+                    }
+                } else if trimmed.starts_with("import (") {
+                    in_go_import_block = true;
+                } else if let Some(caps) = GO_IMPORT_LINE.captures(line) {
+                    imports.push(caps[1].to_string());
+                }
+            }
+        }
+    }
+
+    // Python methods are only meaningful inside classes; drop those of private classes
+    if language == CodeLanguage::Python && !symbols.iter().any(|s| s.kind == SymbolKind::Class) {
+        symbols.retain(|s| s.kind != SymbolKind::Method);
+    }
+
+    imports.dedup();
+
+    IndexedFile {
+        path: rel_path.to_string(),
+        language,
+        module: module_path(rel_path, language),
+        line_count: content.lines().count(),
+        symbols,
+        imports,
+    }
+}
+
+/// Derive a module path from a file path (language-specific conventions)
+fn module_path(rel_path: &str, language: CodeLanguage) -> String {
+    let without_ext = rel_path
+        .rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(rel_path);
+    let mut parts: Vec<&str> = without_ext.split('/').collect();
+
+    match language {
+        CodeLanguage::Rust => {
+            // crates/foo/src/bar/baz.rs -> bar::baz ; src/lib.rs -> crate
+            if let Some(pos) = parts.iter().rposition(|p| *p == "src") {
+                parts.drain(..=pos);
+            }
+            if matches!(parts.last(), Some(&"mod") | Some(&"lib") | Some(&"main")) {
+                parts.pop();
+            }
+            if parts.is_empty() {
+                "crate".to_string()
+            } else {
+                parts.join("::")
+            }
+        }
+        CodeLanguage::Python => {
+            if parts.last() == Some(&"__init__") {
+                parts.pop();
+            }
+            parts.join(".")
+        }
+        CodeLanguage::Go => {
+            // Go modules are directories
+            parts.pop();
+            if parts.is_empty() {
+                "main".to_string()
+            } else {
+                parts.join("/")
+            }
+        }
+        CodeLanguage::TypeScript | CodeLanguage::JavaScript => {
+            if parts.last() == Some(&"index") && parts.len() > 1 {
+                parts.pop();
+            }
+            parts.join("/")
+        }
+    }
+}
+
+/// Resolve raw import specifiers to workspace files where possible
+fn resolve_edges(files: &[IndexedFile]) -> Vec<ImportEdge> {
+    let paths: HashSet<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    let mut edges = Vec::new();
+
+    for file in files {
+        let dir = file.path.rsplit_once('/').map(|(d, _)| d).unwrap_or("");
+
+        for import in &file.imports {
+            let resolved = match file.language {
+                CodeLanguage::TypeScript | CodeLanguage::JavaScript => {
+                    resolve_js_import(dir, import, &paths)
+                }
+                CodeLanguage::Python => resolve_python_import(dir, import, files),
+                CodeLanguage::Rust => resolve_rust_import(file, import, files),
+                CodeLanguage::Go => files
+                    .iter()
+                    .find(|f| {
+                        f.language == CodeLanguage::Go
+                            && f.path != file.path
+                            && !f.module.is_empty()
+                            && import.ends_with(&f.module)
+                    })
+                    .map(|f| f.path.clone()),
+            };
+
+            let edge = match resolved {
+                Some(to) if to != file.path => ImportEdge { from: file.path.clone(), to, internal: true },
+                Some(_) => continue,
+                None => ImportEdge { from: file.path.clone(), to: import.clone(), internal: false },
+            };
+            if !edges.contains(&edge) {
+                edges.push(edge);
+            }
+        }
+    }
+
+    edges
+}
+
+fn join_relative(dir: &str, spec: &str) -> String {
+    let mut parts: Vec<&str> = if dir.is_empty() { Vec::new() } else { dir.split('/').collect() };
+    for segment in spec.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            other => parts.push(other),
+        }
+    }
+    parts.join("/")
+}
+
+fn resolve_js_import(dir: &str, spec: &str, paths: &HashSet<&str>) -> Option<String> {
+    let base = if spec.starts_with('.') {
+        join_relative(dir, spec)
+    } else if let Some(rest) = spec.strip_prefix("@/").or_else(|| spec.strip_prefix("~/")) {
+        format!("src/{}", rest)
+    } else {
+        return None;
+    };
+
+    const EXTS: [&str; 8] = ["ts", "tsx", "js", "jsx", "mjs", "cjs", "mts", "cts"];
+    if paths.contains(base.as_str()) {
+        return Some(base);
+    }
+    EXTS.iter()
+        .map(|ext| format!("{}.{}", base, ext))
+        .chain(EXTS.iter().map(|ext| format!("{}/index.{}", base, ext)))
+        .find(|candidate| paths.contains(candidate.as_str()))
+}
+
+fn resolve_python_import(dir: &str, spec: &str, files: &[IndexedFile]) -> Option<String> {
+    let module = if spec.starts_with('.') {
+        let dots = spec.chars().take_while(|c| *c == '.').count();
+        let mut base = dir.to_string();
+        for _ in 1..dots {
+            base = base.rsplit_once('/').map(|(d, _)| d.to_string()).unwrap_or_default();
+        }
+        let rest = spec[dots..].replace('.', "/");
+        join_relative(&base, &rest).replace('/', ".")
+    } else {
+        spec.to_string()
+    };
+
+    files
+        .iter()
+        .filter(|f| f.language == CodeLanguage::Python)
+        .find(|f| f.module == module || f.module.ends_with(&format!(".{}", module)))
+        .map(|f| f.path.clone())
+}
+
+fn resolve_rust_import(file: &IndexedFile, spec: &str, files: &[IndexedFile]) -> Option<String> {
+    let segments: Vec<&str> = spec.split("::").map(|s| s.trim()).collect();
+    let crate_root = file
+        .path
+        .rfind("src/")
+        .map(|pos| &file.path[..pos + 4])
+        .unwrap_or("");
+
+    let module_segments: Vec<String> = match segments.first().copied() {
+        Some("crate") => segments[1..].iter().map(|s| s.to_string()).collect(),
+        Some("self") => {
+            let mut base: Vec<String> = if file.module == "crate" {
+                Vec::new()
+            } else {
+                file.module.split("::").map(|s| s.to_string()).collect()
+            };
+            base.extend(segments[1..].iter().map(|s| s.to_string()));
+            base
+        }
+        Some("super") => {
+            let mut base: Vec<String> = file.module.split("::").map(|s| s.to_string()).collect();
+            base.pop();
+            base.extend(segments[1..].iter().map(|s| s.to_string()));
+            base
+        }
+        _ => return None,
+    };
+
+    // Longest module prefix that maps to a file in the same crate
+    (1..=module_segments.len()).rev().find_map(|len| {
+        let module = module_segments[..len].join("::");
+        files
+            .iter()
+            .find(|f| {
+                f.language == CodeLanguage::Rust
+                    && f.module == module
+                    && f.path.starts_with(crate_root)
+            })
+            .map(|f| f.path.clone())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, rel: &str, content: &str) {
+        let path = dir.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_rust_symbols_and_imports() {
+        let file = index_file(
+            "src/tools/file_tools.rs",
+            CodeLanguage::Rust,
+            "use crate::persistence::IterationStore;\n\
+             use std::fs;\n\
+             // pub fn commented_out() {}\n\
+             pub struct ReadFileTool;\n\
+             pub(crate) async fn read(path: &str) -> String {\n\
+             fn private_helper() {}\n\
+             pub enum Mode { A }\n",
+        );
+
+        let names: Vec<_> = file.symbols.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("ReadFileTool", SymbolKind::Struct),
+                ("read", SymbolKind::Function),
+                ("Mode", SymbolKind::Enum),
+            ]
+        );
+        assert_eq!(file.module, "tools::file_tools");
+        assert_eq!(file.imports, vec!["crate::persistence::IterationStore", "std::fs"]);
+        assert_eq!(file.symbols[1].line, 5);
+    }
+
+    #[test]
+    fn test_typescript_exports() {
+        let file = index_file(
+            "src/components/Button.tsx",
+            CodeLanguage::TypeScript,
+            "import React from 'react';\n\
+             import { theme } from '../theme';\n\
+             export interface ButtonProps { label: string }\n\
+             export const Button = (props: ButtonProps) => null;\n\
+             export default function App() {}\n\
+             const internal = 1;\n",
+        );
+
+        let names: Vec<_> = file.symbols.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("ButtonProps", SymbolKind::Interface),
+                ("Button", SymbolKind::Function),
+                ("App", SymbolKind::Function),
+            ]
+        );
+        assert_eq!(file.imports, vec!["react", "../theme"]);
+    }
+
+    #[test]
+    fn test_python_and_go_symbols() {
+        let py = index_file(
+            "app/models/user.py",
+            CodeLanguage::Python,
+            "from .base import Model\nimport os, json\n\nMAX_USERS = 10\n\nclass User(Model):\n    def save(self):\n        pass\n    def _hidden(self):\n        pass\n\ndef _private():\n    pass\n",
+        );
+        let names: Vec<_> = py.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["MAX_USERS", "User", "save"]);
+        assert_eq!(py.module, "app.models.user");
+        assert_eq!(py.imports, vec![".base", "os", "json"]);
+
+        let go = index_file(
+            "internal/store/store.go",
+            CodeLanguage::Go,
+            "package store\n\nimport (\n\t\"fmt\"\n\tdb \"example.com/app/internal/db\"\n)\n\ntype Store struct {}\n\nfunc (s *Store) Get() {}\nfunc helper() {}\nfunc New() *Store { return nil }\n",
+        );
+        let names: Vec<_> = go.symbols.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Store", SymbolKind::Struct),
+                ("Get", SymbolKind::Method),
+                ("New", SymbolKind::Function),
+            ]
+        );
+        assert_eq!(go.imports, vec!["fmt", "example.com/app/internal/db"]);
+        assert_eq!(go.module, "internal/store");
+    }
+
+    #[test]
+    fn test_build_resolves_internal_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "src/main.rs", "mod config;\nuse crate::config::Config;\nfn main() {}\n");
+        write(root, "src/config.rs", "pub struct Config;\n");
+        write(root, "web/src/index.ts", "import { api } from './api';\nimport lodash from 'lodash';\n");
+        write(root, "web/src/api/index.ts", "export function api() {}\n");
+        write(root, "web/node_modules/lodash/index.js", "export function chunk() {}\n");
+
+        let index = CodeIndex::build("iter-1", root).unwrap();
+
+        assert_eq!(index.files.len(), 4);
+        assert_eq!(index.dependents_of("src/config.rs"), vec!["src/main.rs"]);
+        assert_eq!(index.dependents_of("./web/src/api/index.ts"), vec!["web/src/index.ts"]);
+        assert!(index
+            .dependencies_of("web/src/index.ts")
+            .iter()
+            .any(|e| !e.internal && e.to == "lodash"));
+
+        let found = index.find_symbols(Some("config"), Some(SymbolKind::Struct), None);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0.path, "src/config.rs");
+
+        assert!(!index.is_stale(root));
+        std::fs::remove_file(root.join("src/config.rs")).unwrap();
+        assert!(index.is_stale(root));
+    }
+}
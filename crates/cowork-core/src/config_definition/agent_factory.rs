@@ -6,7 +6,7 @@ use std::sync::Arc;
 use anyhow::{Result, Context};
 
 use crate::config_definition::{
-    AgentDefinition, StageDefinition, StageType, IncludeContentsMode,
+    AgentDefinition, StageDefinition, StageType, IncludeContentsMode, ToolReference,
     global_registry,
 };
 use crate::instructions::*;
@@ -18,7 +18,7 @@ use adk_core::{Llm, Agent, IncludeContents};
 use adk_skill::select_skill_prompt_block;
 use adk_tool::ExitLoopTool;
 use crate::llm::config::McpConfig;
-use crate::tools::{create_mcp_toolsets_from_config, ConnectedMcpToolset};
+use crate::tools::{create_mcp_toolsets_from_config, select_mcp_toolsets, ConnectedMcpToolset, MCP_TOOL_PREFIX};
 
 /// Global MCP toolsets (initialized once at startup)
 static GLOBAL_MCP_TOOLSETS: once_cell::sync::Lazy<std::sync::Mutex<Vec<ConnectedMcpToolset>>> =
@@ -45,12 +45,13 @@ pub async fn initialize_mcp_toolsets() -> Result<usize> {
         }
     };
 
-    tracing::info!("[MCP] Config: tavily_api_key={}, deepwiki_enabled={}",
+    tracing::info!("[MCP] Config: tavily_api_key={}, deepwiki_enabled={}, servers={}",
         if mcp_config.tavily_api_key.is_empty() { "empty" } else { "configured" },
-        mcp_config.deepwiki_enabled);
+        mcp_config.deepwiki_enabled,
+        mcp_config.servers.len());
 
     if !mcp_config.is_any_enabled() {
-        tracing::info!("[MCP] No MCP servers enabled in config (tavily_api_key is empty and deepwiki is disabled)");
+        tracing::info!("[MCP] No MCP servers enabled in config (tavily_api_key is empty, deepwiki is disabled and no servers are listed)");
         return Ok(0);
     }
 
@@ -80,8 +81,11 @@ pub fn is_mcp_initialized() -> bool {
     GLOBAL_MCP_INITIALIZED.load(std::sync::atomic::Ordering::SeqCst)
 }
 
-/// Add MCP toolsets to an agent builder if they are available
-pub fn add_mcp_toolsets_to_builder(builder: LlmAgentBuilder) -> LlmAgentBuilder {
+/// Add the MCP toolsets an agent may use to its builder
+///
+/// `tools` are the agent's tool references; `mcp:<server>` and
+/// `mcp:<server>/<tool>` entries narrow which servers are attached.
+pub fn add_mcp_toolsets_to_builder(builder: LlmAgentBuilder, agent_id: &str, tools: &[ToolReference]) -> LlmAgentBuilder {
     tracing::info!("[MCP] add_mcp_toolsets_to_builder called, attempting to acquire lock...");
 
     // Wait for lock (blocks if MCP is initializing)
@@ -102,17 +106,27 @@ pub fn add_mcp_toolsets_to_builder(builder: LlmAgentBuilder) -> LlmAgentBuilder
         return builder;
     }
 
-    // Add all MCP toolsets to the agent using .toolset() method
+    let toolsets = select_mcp_toolsets(&guard, agent_id, tools.iter().map(|t| t.tool_id.as_str()));
+
+    // Add the selected MCP toolsets to the agent using .toolset() method
     let mut current_builder = builder;
-    for connected in guard.iter() {
-        tracing::info!("[MCP] Adding MCP toolset '{}' to agent", connected.name);
-        current_builder = current_builder.toolset(connected.toolset.clone());
+    for toolset in &toolsets {
+        tracing::info!("[MCP] Adding MCP toolset '{}' to agent '{}'", toolset.name(), agent_id);
+        current_builder = current_builder.toolset(toolset.clone());
     }
 
-    tracing::info!("[MCP] Successfully added {} MCP toolset(s) to agent", guard.len());
+    tracing::info!("[MCP] Successfully added {} MCP toolset(s) to agent '{}'", toolsets.len(), agent_id);
     current_builder
 }
 
+/// Connected MCP servers and the tools they advertise
+pub fn get_mcp_toolsets() -> Vec<ConnectedMcpToolset> {
+    match GLOBAL_MCP_TOOLSETS.lock() {
+        Ok(guard) => guard.clone(),
+        Err(_) => Vec::new(),
+    }
+}
+
 /// Get a list of configured MCP server names (for injecting into agent instructions)
 pub fn get_mcp_server_names() -> Vec<String> {
     match GLOBAL_MCP_TOOLSETS.lock() {
@@ -222,13 +236,13 @@ pub fn create_agent_from_config_with_stage(
         .model(model);
 
     // Add tools based on tool references
-    for tool_ref in &definition.tools {
+    for tool_ref in definition.tools.iter().filter(|t| !t.tool_id.starts_with(MCP_TOOL_PREFIX)) {
         let tool = create_tool_from_reference(&tool_ref.tool_id, &iteration_id)?;
         builder = builder.tool(tool);
     }
 
     // Add MCP toolsets if available
-    builder = add_mcp_toolsets_to_builder(builder);
+    builder = add_mcp_toolsets_to_builder(builder, &definition.id, &definition.tools);
 
     // Set content inclusion mode from config
     let include_contents = include_contents_mode_to_adk(&definition.include_contents);
@@ -311,13 +325,13 @@ fn create_simple_agent_from_config_with_stage(
         .model(model);
 
     // Add tools
-    for tool_ref in &definition.tools {
+    for tool_ref in definition.tools.iter().filter(|t| !t.tool_id.starts_with(MCP_TOOL_PREFIX)) {
         let tool = create_tool_from_reference(&tool_ref.tool_id, &iteration_id)?;
         builder = builder.tool(tool);
     }
 
     // Add MCP toolsets if available
-    builder = add_mcp_toolsets_to_builder(builder);
+    builder = add_mcp_toolsets_to_builder(builder, &definition.id, &definition.tools);
 
     // Set content inclusion mode:
     // - Actor agents use Default to see Critic feedback across LoopAgent iterations
@@ -419,9 +433,20 @@ fn create_tool_from_reference(tool_id: &str, iteration_id: &str) -> Result<Arc<d
         // File tools
         "read_file" => Arc::new(ReadFileTool),
         "write_file" => Arc::new(WriteFileTool),
+        "edit_file" => Arc::new(EditFileTool),
+        "apply_patch" => Arc::new(ApplyPatchTool),
         "list_files" => Arc::new(ListFilesTool),
         "run_command" => Arc::new(RunCommandTool),
         "read_file_truncated" => Arc::new(ReadFileTruncatedTool),
+        "query_code_index" => Arc::new(QueryCodeIndexTool::new(iteration_id.to_string())),
+        "search_code" => Arc::new(SearchCodeTool::new(SearchScope::Workspace)),
+        "find_files" => Arc::new(FindFilesTool::new(SearchScope::Workspace)),
+
+        // Process tools
+        "start_process" => Arc::new(StartProcessTool),
+        "read_process_output" => Arc::new(ReadProcessOutputTool),
+        "http_probe" => Arc::new(HttpProbeTool),
+        "stop_process" => Arc::new(StopProcessTool),
 
         // Document tools
         "load_idea" => Arc::new(LoadIdeaTool),
@@ -448,6 +473,7 @@ fn create_tool_from_reference(tool_id: &str, iteration_id: &str) -> Result<Arc<d
         "check_tests" => Arc::new(CheckTestsTool),
         "check_lint" => Arc::new(CheckLintTool),
         "check_data_format" => Arc::new(CheckDataFormatTool),
+        "check_traceability" => Arc::new(CheckTraceabilityTool),
 
         // HITL tools
         "provide_feedback" => Arc::new(ProvideFeedbackTool),
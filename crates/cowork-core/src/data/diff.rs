@@ -0,0 +1,325 @@
+// Structured diff of iteration data (requirements, features, design, plan)
+//
+// Items are matched by their id (components, tasks, ...) or name (data
+// models); modified items list the top-level fields that changed.
+// Timestamps (`*_at`) are ignored so that re-saving an unchanged item does
+// not show up as a modification.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::models::{DesignSpec, FeatureList, ImplementationPlan, Requirements};
+use crate::persistence::load_iteration_data_file;
+
+/// Data files of one iteration
+#[derive(Debug, Clone, Default)]
+pub struct DataSnapshot {
+    pub requirements: Option<Requirements>,
+    pub features: Option<FeatureList>,
+    pub design: Option<DesignSpec>,
+    pub plan: Option<ImplementationPlan>,
+}
+
+impl DataSnapshot {
+    pub fn load(iteration_id: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            requirements: load_iteration_data_file(iteration_id, "requirements.json")?,
+            features: load_iteration_data_file(iteration_id, "feature_list.json")?,
+            design: load_iteration_data_file(iteration_id, "design_spec.json")?,
+            plan: load_iteration_data_file(iteration_id, "implementation_plan.json")?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requirements.as_ref().is_none_or(|r| r.requirements.is_empty())
+            && self.features.as_ref().is_none_or(|f| f.features.is_empty())
+            && self.design.as_ref().is_none_or(|d| d.architecture.components.is_empty())
+            && self.plan.as_ref().is_none_or(|p| p.tasks.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemRef {
+    pub id: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemChange {
+    pub id: String,
+    pub label: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Changes within one collection (e.g. requirements)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionDiff {
+    pub collection: String,
+    pub added: Vec<ItemRef>,
+    pub removed: Vec<ItemRef>,
+    pub modified: Vec<ItemChange>,
+}
+
+impl CollectionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Diff between the data of two iterations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IterationDataDiff {
+    pub from_iteration_id: String,
+    pub to_iteration_id: String,
+    /// Only collections with changes are listed
+    pub collections: Vec<CollectionDiff>,
+}
+
+impl IterationDataDiff {
+    /// Diff the stored data of two iterations
+    pub fn between(from_iteration_id: &str, to_iteration_id: &str) -> anyhow::Result<Self> {
+        let from = DataSnapshot::load(from_iteration_id)?;
+        let to = DataSnapshot::load(to_iteration_id)?;
+        Ok(Self::compute(from_iteration_id, &from, to_iteration_id, &to))
+    }
+
+    pub fn compute(from_id: &str, from: &DataSnapshot, to_id: &str, to: &DataSnapshot) -> Self {
+        let requirements = |s: &DataSnapshot| {
+            s.requirements.as_ref().map(|r| to_values(&r.requirements)).unwrap_or_default()
+        };
+        let features = |s: &DataSnapshot| {
+            s.features.as_ref().map(|f| to_values(&f.features)).unwrap_or_default()
+        };
+        let components = |s: &DataSnapshot| {
+            s.design.as_ref().map(|d| to_values(&d.architecture.components)).unwrap_or_default()
+        };
+        let data_models = |s: &DataSnapshot| {
+            s.design.as_ref().map(|d| to_values(&d.architecture.data_models)).unwrap_or_default()
+        };
+        let architecture = |s: &DataSnapshot| {
+            s.design
+                .as_ref()
+                .map(|d| {
+                    vec![json!({
+                        "id": "architecture",
+                        "style": d.architecture.style,
+                        "technology_stack": d.technology_stack,
+                        "deployment": d.deployment,
+                    })]
+                })
+                .unwrap_or_default()
+        };
+        let milestones = |s: &DataSnapshot| {
+            s.plan.as_ref().map(|p| to_values(&p.milestones)).unwrap_or_default()
+        };
+        let tasks = |s: &DataSnapshot| s.plan.as_ref().map(|p| to_values(&p.tasks)).unwrap_or_default();
+
+        let collections = [
+            diff_collection("requirements", &requirements(from), &requirements(to), "id", "title"),
+            diff_collection("features", &features(from), &features(to), "id", "name"),
+            diff_collection("architecture", &architecture(from), &architecture(to), "id", "style"),
+            diff_collection("components", &components(from), &components(to), "id", "name"),
+            diff_collection("data_models", &data_models(from), &data_models(to), "name", "name"),
+            diff_collection("milestones", &milestones(from), &milestones(to), "id", "name"),
+            diff_collection("tasks", &tasks(from), &tasks(to), "id", "title"),
+        ]
+        .into_iter()
+        .filter(|c| !c.is_empty())
+        .collect();
+
+        Self {
+            from_iteration_id: from_id.to_string(),
+            to_iteration_id: to_id.to_string(),
+            collections,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.collections.is_empty()
+    }
+
+    /// Compact Markdown summary (field names only, no values), listing at
+    /// most `max_items` entries per collection
+    pub fn summary(&self, max_items: usize) -> String {
+        if self.is_empty() {
+            return "No changes in requirements, features, design or plan.\n".to_string();
+        }
+
+        let mut out = String::new();
+        for c in &self.collections {
+            out.push_str(&format!(
+                "- **{}**: {} added, {} removed, {} modified\n",
+                c.collection,
+                c.added.len(),
+                c.removed.len(),
+                c.modified.len()
+            ));
+            let entries = c
+                .added
+                .iter()
+                .map(|i| format!("  - + {} {}", i.id, i.label))
+                .chain(c.removed.iter().map(|i| format!("  - − {} {}", i.id, i.label)))
+                .chain(c.modified.iter().map(|i| {
+                    let fields: Vec<&str> = i.changes.iter().map(|f| f.field.as_str()).collect();
+                    format!("  - ~ {} {} ({})", i.id, i.label, fields.join(", "))
+                }));
+            let total = c.added.len() + c.removed.len() + c.modified.len();
+            for line in entries.take(max_items) {
+                out.push_str(line.trim_end());
+                out.push('\n');
+            }
+            if total > max_items {
+                out.push_str(&format!("  - … {} more\n", total - max_items));
+            }
+        }
+        out
+    }
+}
+
+fn to_values<T: Serialize>(items: &[T]) -> Vec<Value> {
+    items.iter().filter_map(|i| serde_json::to_value(i).ok()).collect()
+}
+
+fn item_ref(item: &Value, key: &str, label_key: &str) -> ItemRef {
+    ItemRef {
+        id: item.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        label: item.get(label_key).and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+    }
+}
+
+fn diff_collection(name: &str, before: &[Value], after: &[Value], key: &str, label_key: &str) -> CollectionDiff {
+    let id_of = |v: &Value| v.get(key).and_then(|k| k.as_str()).map(str::to_string);
+    let find = |items: &[Value], id: &str| items.iter().find(|v| id_of(v).as_deref() == Some(id)).cloned();
+
+    let mut diff = CollectionDiff {
+        collection: name.to_string(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        modified: Vec::new(),
+    };
+
+    for old in before {
+        let Some(id) = id_of(old) else { continue };
+        match find(after, &id) {
+            None => diff.removed.push(item_ref(old, key, label_key)),
+            Some(new) => {
+                let changes = diff_fields(old, &new);
+                if !changes.is_empty() {
+                    let r = item_ref(&new, key, label_key);
+                    diff.modified.push(ItemChange { id: r.id, label: r.label, changes });
+                }
+            }
+        }
+    }
+    for new in after {
+        let Some(id) = id_of(new) else { continue };
+        if find(before, &id).is_none() {
+            diff.added.push(item_ref(new, key, label_key));
+        }
+    }
+    diff
+}
+
+fn diff_fields(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let empty = serde_json::Map::new();
+    let old = before.as_object().unwrap_or(&empty);
+    let new = after.as_object().unwrap_or(&empty);
+
+    let mut fields: Vec<&String> = old.keys().chain(new.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter(|f| !f.ends_with("_at"))
+        .filter_map(|f| {
+            let b = old.get(f).cloned().unwrap_or(Value::Null);
+            let a = new.get(f).cloned().unwrap_or(Value::Null);
+            (b != a).then(|| FieldChange { field: f.clone(), before: b, after: a })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::*;
+    use chrono::Utc;
+
+    fn requirement(id: &str, title: &str, criteria: &[&str]) -> Requirement {
+        Requirement {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            priority: Priority::Medium,
+            category: RequirementCategory::Functional,
+            acceptance_criteria: criteria.iter().map(|s| s.to_string()).collect(),
+            related_features: vec![],
+        }
+    }
+
+    fn snapshot(reqs: Vec<Requirement>) -> DataSnapshot {
+        let mut requirements = Requirements::new();
+        requirements.requirements = reqs;
+        DataSnapshot { requirements: Some(requirements), ..Default::default() }
+    }
+
+    #[test]
+    fn test_added_removed_modified_by_id() {
+        let from = snapshot(vec![
+            requirement("REQ-001", "Login", &["works"]),
+            requirement("REQ-002", "Logout", &[]),
+        ]);
+        let to = snapshot(vec![
+            requirement("REQ-001", "Login", &["works", "remembers user"]),
+            requirement("REQ-003", "Export", &[]),
+        ]);
+
+        let diff = IterationDataDiff::compute("a", &from, "b", &to);
+        assert_eq!(diff.collections.len(), 1);
+        let reqs = &diff.collections[0];
+        assert_eq!(reqs.added[0].id, "REQ-003");
+        assert_eq!(reqs.removed[0].id, "REQ-002");
+        assert_eq!(reqs.modified[0].id, "REQ-001");
+        assert_eq!(reqs.modified[0].changes[0].field, "acceptance_criteria");
+
+        let summary = diff.summary(10);
+        assert!(summary.contains("**requirements**: 1 added, 1 removed, 1 modified"));
+        assert!(summary.contains("~ REQ-001 Login (acceptance_criteria)"));
+    }
+
+    #[test]
+    fn test_timestamps_are_ignored() {
+        let task = |created_at| Task {
+            id: "TASK-001".to_string(),
+            title: "Setup".to_string(),
+            description: String::new(),
+            feature_id: "FEAT-001".to_string(),
+            component_id: String::new(),
+            status: TaskStatus::Pending,
+            dependencies: vec![],
+            estimated_effort: None,
+            files_to_create: vec![],
+            acceptance_criteria: vec![],
+            created_at,
+            started_at: None,
+            completed_at: None,
+        };
+        let plan = |t| {
+            let mut p = ImplementationPlan::new();
+            p.tasks.push(t);
+            DataSnapshot { plan: Some(p), ..Default::default() }
+        };
+
+        let earlier = Utc::now() - chrono::Duration::hours(1);
+        let diff = IterationDataDiff::compute("a", &plan(task(earlier)), "b", &plan(task(Utc::now())));
+        assert!(diff.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
 // Data models module
 pub mod models;
+pub mod diff;
 pub use models::*;
+pub use diff::{CollectionDiff, DataSnapshot, IterationDataDiff};
@@ -0,0 +1,423 @@
+// Delivery of an iteration workspace into the project root
+//
+// Instead of copying files blindly, delivery computes the full set of
+// added/modified/deleted files between the workspace and the project root.
+// Each change is reviewed file by file. Files the user changed in the project
+// since the iteration started are flagged as conflicts, using a baseline of
+// file hashes taken when the iteration began. Before anything is written, the
+// files about to be overwritten or deleted are saved to a rollback snapshot.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Marker put into per-file review prompts (followed by the path and `]`)
+pub const DELIVERY_REVIEW_MARKER: &str = "[DELIVERY_FILE:";
+/// Marker added to review prompts of conflicting files
+pub const DELIVERY_CONFLICT_MARKER: &str = "[CONFLICT]";
+
+/// Directories never delivered nor touched in the project root
+const SKIPPED_DIRS: &[&str] = &[".git", ".cowork-v2", "node_modules", "target", ".litho", "litho.docs"];
+
+/// Project files that delivery never deletes
+const PROTECTED_PATHS: &[&str] = &[
+    ".gitignore",
+    ".gitattributes",
+    ".zed",
+    ".vscode",
+    ".idea",
+    "AGENT.md",
+    "config.toml",
+    "Cargo.toml",
+    "Cargo.lock",
+    "README.md",
+    "LICENSE",
+    ".DS_Store",
+];
+
+const BASELINE_FILENAME: &str = "project_baseline.json";
+const SNAPSHOT_MANIFEST: &str = "manifest.json";
+
+/// The project root: the directory containing `.cowork-v2`
+pub fn project_root() -> Result<PathBuf> {
+    let cowork_dir = crate::persistence::get_cowork_dir()?;
+    match cowork_dir.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => Ok(parent.to_path_buf()),
+        _ => std::env::current_dir().context("Failed to get project root"),
+    }
+}
+
+fn is_protected(path: &str) -> bool {
+    PROTECTED_PATHS
+        .iter()
+        .any(|p| path == *p || path.starts_with(&format!("{}/", p)))
+}
+
+/// Files under `root` by relative path (always `/`-separated), honouring
+/// `.gitignore` and skipping VCS, dependency and Cowork directories
+fn list_files(root: &Path) -> BTreeMap<String, PathBuf> {
+    let mut files = BTreeMap::new();
+    if !root.exists() {
+        return files;
+    }
+
+    let walker = ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|e| !SKIPPED_DIRS.iter().any(|d| e.file_name() == *d))
+        .build();
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        if let Ok(rel) = entry.path().strip_prefix(root) {
+            let rel = rel.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+            files.insert(rel, entry.path().to_path_buf());
+        }
+    }
+    files
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+/// Hashes of the project root's files when an iteration started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectBaseline {
+    pub captured_at: DateTime<Utc>,
+    pub files: BTreeMap<String, String>,
+}
+
+impl ProjectBaseline {
+    pub fn capture(project_root: &Path) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        for (rel, path) in list_files(project_root) {
+            files.insert(rel, hash_file(&path)?);
+        }
+        Ok(Self {
+            captured_at: Utc::now(),
+            files,
+        })
+    }
+
+    fn path(iteration_dir: &Path) -> PathBuf {
+        iteration_dir.join("data").join(BASELINE_FILENAME)
+    }
+
+    pub fn load(iteration_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(iteration_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))?))
+    }
+
+    pub fn save(&self, iteration_dir: &Path) -> Result<()> {
+        let path = Self::path(iteration_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+impl ChangeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeKind::Added => "Added",
+            ChangeKind::Modified => "Modified",
+            ChangeKind::Deleted => "Deleted",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    /// The project file changed since the iteration started
+    pub conflict: bool,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    /// Unified diff from the project file to the workspace file
+    #[serde(skip)]
+    pub diff: String,
+}
+
+/// Differences between a workspace and the project root
+#[derive(Debug, Clone, Serialize)]
+pub struct DeliveryPlan {
+    pub workspace: PathBuf,
+    pub project_root: PathBuf,
+    pub changes: Vec<FileChange>,
+    /// Whether conflicts could be detected (a baseline was recorded)
+    pub has_baseline: bool,
+}
+
+impl DeliveryPlan {
+    pub fn compute(workspace: &Path, project_root: &Path, baseline: Option<&ProjectBaseline>) -> Result<Self> {
+        let source = list_files(workspace);
+        let target = list_files(project_root);
+
+        // A project file conflicts when it no longer matches the baseline
+        let conflicts = |rel: &str, target_path: &Path| -> Result<bool> {
+            let Some(baseline) = baseline else {
+                return Ok(false);
+            };
+            Ok(baseline.files.get(rel) != Some(&hash_file(target_path)?))
+        };
+
+        let mut changes = Vec::new();
+        for (rel, src) in &source {
+            let new = fs::read(src).with_context(|| format!("Failed to read {:?}", src))?;
+            match target.get(rel) {
+                None => changes.push(file_change(rel, ChangeKind::Added, false, &[], &new)),
+                Some(dst) => {
+                    let old = fs::read(dst).with_context(|| format!("Failed to read {:?}", dst))?;
+                    if old != new {
+                        let conflict = conflicts(rel, dst)?;
+                        changes.push(file_change(rel, ChangeKind::Modified, conflict, &old, &new));
+                    }
+                }
+            }
+        }
+        for (rel, dst) in &target {
+            if source.contains_key(rel) || is_protected(rel) {
+                continue;
+            }
+            let old = fs::read(dst).with_context(|| format!("Failed to read {:?}", dst))?;
+            let conflict = conflicts(rel, dst)?;
+            changes.push(file_change(rel, ChangeKind::Deleted, conflict, &old, &[]));
+        }
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Self {
+            workspace: workspace.to_path_buf(),
+            project_root: project_root.to_path_buf(),
+            changes,
+            has_baseline: baseline.is_some(),
+        })
+    }
+
+    /// Save the project files that `accepted` changes overwrite or delete
+    /// into `snapshot_dir`, then apply those changes
+    pub fn apply(&self, accepted: &[&FileChange], snapshot_dir: &Path) -> Result<DeliverySnapshot> {
+        let snapshot = DeliverySnapshot::write(self, accepted, snapshot_dir)?;
+
+        for change in accepted {
+            let dst = self.project_root.join(&change.path);
+            match change.kind {
+                ChangeKind::Added | ChangeKind::Modified => {
+                    if let Some(parent) = dst.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::copy(self.workspace.join(&change.path), &dst)
+                        .with_context(|| format!("Failed to write {}", change.path))?;
+                }
+                ChangeKind::Deleted => {
+                    fs::remove_file(&dst).with_context(|| format!("Failed to delete {}", change.path))?;
+                    remove_empty_parents(&dst, &self.project_root);
+                }
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+fn file_change(path: &str, kind: ChangeKind, conflict: bool, old: &[u8], new: &[u8]) -> FileChange {
+    let (diff, lines_added, lines_removed) = match (std::str::from_utf8(old), std::str::from_utf8(new)) {
+        (Ok(old), Ok(new)) => {
+            let diff = TextDiff::from_lines(old, new);
+            let (mut added, mut removed) = (0, 0);
+            for change in diff.iter_all_changes() {
+                match change.tag() {
+                    similar::ChangeTag::Insert => added += 1,
+                    similar::ChangeTag::Delete => removed += 1,
+                    similar::ChangeTag::Equal => {}
+                }
+            }
+            let text = diff
+                .unified_diff()
+                .context_radius(3)
+                .header(&format!("project/{}", path), &format!("workspace/{}", path))
+                .to_string();
+            (text, added, removed)
+        }
+        _ => (format!("Binary file {} differs\n", path), 0, 0),
+    };
+    FileChange {
+        path: path.to_string(),
+        kind,
+        conflict,
+        lines_added,
+        lines_removed,
+        diff,
+    }
+}
+
+fn remove_empty_parents(file: &Path, root: &Path) {
+    let mut dir = file.parent();
+    while let Some(d) = dir {
+        if d == root || fs::remove_dir(d).is_err() {
+            break;
+        }
+        dir = d.parent();
+    }
+}
+
+/// Project files saved before a delivery, enough to undo it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliverySnapshot {
+    pub created_at: DateTime<Utc>,
+    pub project_root: PathBuf,
+    /// Files overwritten or deleted; their previous content is kept
+    pub saved: Vec<String>,
+    /// Files created by the delivery; undoing deletes them
+    pub created: Vec<String>,
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+impl DeliverySnapshot {
+    fn write(plan: &DeliveryPlan, accepted: &[&FileChange], dir: &Path) -> Result<Self> {
+        let files_dir = dir.join("files");
+        fs::create_dir_all(&files_dir).with_context(|| format!("Failed to create {:?}", files_dir))?;
+
+        let mut saved = Vec::new();
+        let mut created = Vec::new();
+        for change in accepted {
+            if change.kind == ChangeKind::Added {
+                created.push(change.path.clone());
+                continue;
+            }
+            let dest = files_dir.join(&change.path);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(plan.project_root.join(&change.path), &dest)
+                .with_context(|| format!("Failed to back up {}", change.path))?;
+            saved.push(change.path.clone());
+        }
+
+        let snapshot = Self {
+            created_at: Utc::now(),
+            project_root: plan.project_root.clone(),
+            saved,
+            created,
+            dir: dir.to_path_buf(),
+        };
+        fs::write(dir.join(SNAPSHOT_MANIFEST), serde_json::to_string_pretty(&snapshot)?)?;
+        Ok(snapshot)
+    }
+
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(SNAPSHOT_MANIFEST);
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        let mut snapshot: Self = serde_json::from_str(&content)?;
+        snapshot.dir = dir.to_path_buf();
+        Ok(snapshot)
+    }
+
+    /// Directory for a new snapshot of an iteration
+    pub fn new_dir(iteration_dir: &Path) -> PathBuf {
+        iteration_dir
+            .join("delivery")
+            .join(format!("rollback-{}", Utc::now().format("%Y%m%d-%H%M%S")))
+    }
+
+    /// Most recent snapshot of an iteration
+    pub fn latest(iteration_dir: &Path) -> Option<PathBuf> {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(iteration_dir.join("delivery"))
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.join(SNAPSHOT_MANIFEST).exists())
+            .collect();
+        dirs.sort();
+        dirs.pop()
+    }
+
+    /// Put the saved files back and remove the files the delivery created
+    pub fn restore(&self) -> Result<()> {
+        for rel in &self.saved {
+            let dst = self.project_root.join(rel);
+            if let Some(parent) = dst.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(self.dir.join("files").join(rel), &dst).with_context(|| format!("Failed to restore {}", rel))?;
+        }
+        for rel in &self.created {
+            let dst = self.project_root.join(rel);
+            if dst.exists() {
+                fs::remove_file(&dst).with_context(|| format!("Failed to remove {}", rel))?;
+                remove_empty_parents(&dst, &self.project_root);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_conflicts_apply_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("workspace");
+        let project = dir.path().join("project");
+        fs::create_dir_all(workspace.join("src")).unwrap();
+        fs::create_dir_all(&project).unwrap();
+
+        fs::write(project.join("index.html"), "<h1>old</h1>\n").unwrap();
+        fs::write(project.join("notes.txt"), "mine\n").unwrap();
+        fs::write(project.join("README.md"), "readme\n").unwrap();
+        let baseline = ProjectBaseline::capture(&project).unwrap();
+
+        // The user edits a file after the iteration started
+        fs::write(project.join("notes.txt"), "mine, edited\n").unwrap();
+
+        fs::write(workspace.join("index.html"), "<h1>new</h1>\n").unwrap();
+        fs::write(workspace.join("src/app.js"), "run();\n").unwrap();
+
+        let plan = DeliveryPlan::compute(&workspace, &project, Some(&baseline)).unwrap();
+        let summary: Vec<(&str, ChangeKind, bool)> =
+            plan.changes.iter().map(|c| (c.path.as_str(), c.kind, c.conflict)).collect();
+        assert_eq!(
+            summary,
+            [
+                ("index.html", ChangeKind::Modified, false),
+                ("notes.txt", ChangeKind::Deleted, true),
+                ("src/app.js", ChangeKind::Added, false),
+            ]
+        );
+        assert_eq!((plan.changes[0].lines_added, plan.changes[0].lines_removed), (1, 1));
+
+        let accepted: Vec<&FileChange> = plan.changes.iter().filter(|c| !c.conflict).collect();
+        let snapshot = plan.apply(&accepted, &dir.path().join("rollback")).unwrap();
+        assert_eq!(fs::read_to_string(project.join("index.html")).unwrap(), "<h1>new</h1>\n");
+        assert!(project.join("src/app.js").exists());
+        assert!(project.join("notes.txt").exists());
+
+        DeliverySnapshot::load(&snapshot.dir).unwrap().restore().unwrap();
+        assert_eq!(fs::read_to_string(project.join("index.html")).unwrap(), "<h1>old</h1>\n");
+        assert!(!project.join("src").exists());
+    }
+}
@@ -152,6 +152,22 @@ impl Iteration {
         self.current_stage = Some(stage.into());
     }
 
+    /// Forget `stages` (the target stage and everything after it) and make
+    /// `target` the stage to resume from. A started iteration is left paused
+    /// so that `continue` can pick it up if the re-run does not start; a
+    /// draft stays a draft so that it still inherits from its base.
+    pub fn rewind_to(&mut self, target: &str, stages: &[String]) {
+        self.completed_stages.retain(|s| !stages.contains(s));
+        for stage in stages {
+            self.artifacts.clear(stage);
+        }
+        self.current_stage = Some(target.to_string());
+        self.completed_at = None;
+        if self.status != IterationStatus::Draft {
+            self.status = IterationStatus::Paused;
+        }
+    }
+
     pub fn complete_stage(&mut self, stage: impl Into<String>, artifact_path: Option<String>) {
         let stage_name = stage.into();
         self.completed_stages.push(stage_name.clone());
@@ -253,6 +269,18 @@ impl Artifacts {
         }
     }
 
+    pub fn clear(&mut self, stage: &str) {
+        match stage {
+            "idea" => self.idea = None,
+            "prd" => self.prd = None,
+            "design" => self.design = None,
+            "plan" => self.plan = None,
+            "coding" => self.coding = None,
+            "delivery" => self.delivery = None,
+            _ => {}
+        }
+    }
+
     pub fn set(&mut self, stage: &str, path: String) {
         match stage {
             "idea" => self.idea = Some(path),
@@ -376,6 +404,33 @@ mod tests {
         assert_eq!(iteration.artifacts.idea, Some("/path/to/idea.md".to_string()));
     }
 
+    #[test]
+    fn test_rewind_to_stage() {
+        let project = create_test_project();
+        let mut iteration = Iteration::create_genesis(
+            &project,
+            "Test".to_string(),
+            "Test".to_string(),
+        );
+
+        for stage in ["idea", "prd", "design", "plan"] {
+            iteration.complete_stage(stage, Some(format!("/path/to/{}.md", stage)));
+        }
+        iteration.start();
+        iteration.complete();
+
+        let downstream = vec!["design".to_string(), "plan".to_string(), "coding".to_string()];
+        iteration.rewind_to("design", &downstream);
+
+        assert_eq!(iteration.completed_stages, vec!["idea".to_string(), "prd".to_string()]);
+        assert!(iteration.artifacts.design.is_none());
+        assert!(iteration.artifacts.plan.is_none());
+        assert!(iteration.artifacts.prd.is_some());
+        assert_eq!(iteration.current_stage, Some("design".to_string()));
+        assert_eq!(iteration.status, IterationStatus::Paused);
+        assert!(iteration.completed_at.is_none());
+    }
+
     #[test]
     fn test_determine_start_stage_none_mode() {
         let project = create_test_project();
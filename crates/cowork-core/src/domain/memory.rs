@@ -10,6 +10,10 @@ pub struct ProjectMemory {
     pub context: ProjectContext,
     /// Iteration knowledge cache - stores knowledge snapshots for each iteration
     pub iteration_knowledge: std::collections::HashMap<String, IterationKnowledge>,
+    /// Knowledge lineage - how each snapshot was derived from its base iteration.
+    /// Entries survive `cleanup_old_knowledge` so the history stays traceable.
+    #[serde(default)]
+    pub knowledge_lineage: Vec<KnowledgeLineageEntry>,
 }
 
 impl ProjectMemory {
@@ -61,7 +65,37 @@ impl ProjectMemory {
         self.iteration_knowledge.remove(iteration_id);
     }
 
-    /// Cleanup old knowledge, keeping only the most recent N iterations
+    /// Record (or replace) the lineage entry for an iteration's knowledge
+    pub fn record_knowledge_lineage(&mut self, entry: KnowledgeLineageEntry) {
+        self.knowledge_lineage.retain(|e| e.iteration_id != entry.iteration_id);
+        self.knowledge_lineage.push(entry);
+    }
+
+    /// Get the knowledge lineage of an iteration, oldest ancestor first
+    pub fn knowledge_lineage(&self, iteration_id: &str) -> Vec<&KnowledgeLineageEntry> {
+        let mut chain = Vec::new();
+        let mut current = Some(iteration_id.to_string());
+
+        while let Some(id) = current {
+            // Guard against cycles in corrupted memory files
+            if chain.iter().any(|e: &&KnowledgeLineageEntry| e.iteration_id == id) {
+                break;
+            }
+            match self.knowledge_lineage.iter().find(|e| e.iteration_id == id) {
+                Some(entry) => {
+                    current = entry.base_iteration_id.clone();
+                    chain.push(entry);
+                }
+                None => break,
+            }
+        }
+
+        chain.reverse();
+        chain
+    }
+
+    /// Cleanup old knowledge, keeping only the most recent N iterations.
+    /// Only snapshots are dropped; lineage entries are kept.
     pub fn cleanup_old_knowledge(&mut self, keep_count: usize) {
         let mut knowledge_vec: Vec<_> = self.iteration_knowledge.values().collect();
         
@@ -107,6 +141,18 @@ pub struct IterationKnowledge {
     
     /// Known issues or limitations
     pub known_issues: Vec<String>,
+
+    /// Base iteration this knowledge was derived from (evolution iterations)
+    #[serde(default)]
+    pub base_iteration_id: Option<String>,
+
+    /// Content hashes of the summarized documents, keyed by doc type
+    #[serde(default)]
+    pub document_hashes: std::collections::HashMap<String, String>,
+
+    /// Doc types whose summaries were carried forward unchanged from the base
+    #[serde(default)]
+    pub carried_forward: Vec<String>,
     
     /// Timestamp when knowledge was generated
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -127,11 +173,28 @@ impl IterationKnowledge {
             key_patterns: Vec::new(),
             code_structure: String::new(),
             known_issues: Vec::new(),
+            base_iteration_id: None,
+            document_hashes: std::collections::HashMap::new(),
+            carried_forward: Vec::new(),
             created_at: now,
         }
     }
 }
 
+/// Knowledge lineage entry - records which documents changed in an iteration
+/// relative to its base, and which summaries were reused
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeLineageEntry {
+    pub iteration_id: String,
+    pub iteration_number: u32,
+    pub base_iteration_id: Option<String>,
+    /// Doc types that were (re-)summarized in this iteration
+    pub changed_documents: Vec<String>,
+    /// Doc types whose summaries were carried forward from the base
+    pub carried_forward: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Decision - Key project decision
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Decision {
@@ -141,6 +204,9 @@ pub struct Decision {
     pub decision: String,
     pub consequences: Vec<String>,
     pub iteration_id: String,
+    /// Originating project name, set when the decision lives in global memory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_project: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -160,6 +226,7 @@ impl Decision {
             decision: decision.into(),
             consequences: Vec::new(),
             iteration_id,
+            source_project: None,
             created_at: now,
         }
     }
@@ -175,6 +242,9 @@ pub struct Pattern {
     pub tags: Vec<String>,
     pub code_example: Option<String>,
     pub iteration_id: String,
+    /// Originating project name, set when the pattern lives in global memory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_project: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -194,6 +264,7 @@ impl Pattern {
             tags: Vec::new(),
             code_example: None,
             iteration_id,
+            source_project: None,
             created_at: now,
         }
     }
@@ -214,6 +285,66 @@ pub struct Dependency {
     pub purpose: String,
 }
 
+/// Global memory (across projects) - organization-wide decisions and patterns,
+/// stored under the app data directory and shared by every project on this machine
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GlobalMemory {
+    pub decisions: Vec<Decision>,
+    pub patterns: Vec<Pattern>,
+}
+
+impl GlobalMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a decision, replacing an existing one with the same title
+    pub fn upsert_decision(&mut self, decision: Decision) {
+        self.decisions
+            .retain(|d| !d.title.eq_ignore_ascii_case(&decision.title));
+        self.decisions.push(decision);
+    }
+
+    /// Add a pattern, replacing an existing one with the same name
+    pub fn upsert_pattern(&mut self, pattern: Pattern) {
+        self.patterns
+            .retain(|p| !p.name.eq_ignore_ascii_case(&pattern.name));
+        self.patterns.push(pattern);
+    }
+
+    /// Remove a decision or pattern by ID. Returns true if something was removed
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.decisions.len() + self.patterns.len();
+        self.decisions.retain(|d| d.id != id);
+        self.patterns.retain(|p| p.id != id);
+        before != self.decisions.len() + self.patterns.len()
+    }
+
+    /// Query decisions by keyword
+    pub fn query_decisions(&self, keyword: &str) -> Vec<&Decision> {
+        let keyword_lower = keyword.to_lowercase();
+        self.decisions
+            .iter()
+            .filter(|d| {
+                d.title.to_lowercase().contains(&keyword_lower)
+                    || d.context.to_lowercase().contains(&keyword_lower)
+            })
+            .collect()
+    }
+
+    /// Query patterns by tag or name
+    pub fn query_patterns(&self, keyword: &str) -> Vec<&Pattern> {
+        let keyword_lower = keyword.to_lowercase();
+        self.patterns
+            .iter()
+            .filter(|p| {
+                p.tags.iter().any(|t| t.to_lowercase() == keyword_lower)
+                    || p.name.to_lowercase().contains(&keyword_lower)
+            })
+            .collect()
+    }
+}
+
 /// Iteration-level memory (current iteration insights)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct IterationMemory {
@@ -305,7 +436,8 @@ pub struct MemoryQuery {
 pub enum MemoryScope {
     Project,      // Only project-level
     Iteration,    // Only current iteration
-    Smart,        // Smart merge (project + current iteration)
+    Smart,        // Smart merge (project + current iteration, plus global if enabled)
+    Global,       // Only global (cross-project) memory
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -342,14 +474,14 @@ impl MemoryQueryResult {
         if !self.decisions.is_empty() {
             parts.push("## Key Decisions\n".to_string());
             for d in &self.decisions {
-                parts.push(format!("- {}: {}", d.title, d.decision));
+                parts.push(format!("- {}{}: {}", global_marker(&d.source_project), d.title, d.decision));
             }
         }
 
         if !self.patterns.is_empty() {
             parts.push("\n## Patterns\n".to_string());
             for p in &self.patterns {
-                parts.push(format!("- {}: {}", p.name, p.description));
+                parts.push(format!("- {}{}: {}", global_marker(&p.source_project), p.name, p.description));
             }
         }
 
@@ -364,3 +496,123 @@ impl MemoryQueryResult {
     }
 }
 
+
+fn global_marker(source_project: &Option<String>) -> String {
+    match source_project {
+        Some(project) => format!("[global, from {}] ", project),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lineage_entry(id: &str, number: u32, base: Option<&str>) -> KnowledgeLineageEntry {
+        KnowledgeLineageEntry {
+            iteration_id: id.to_string(),
+            iteration_number: number,
+            base_iteration_id: base.map(|b| b.to_string()),
+            changed_documents: vec!["prd".to_string()],
+            carried_forward: vec!["idea".to_string()],
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_knowledge_lineage_follows_base_chain() {
+        let mut memory = ProjectMemory::new();
+        memory.record_knowledge_lineage(lineage_entry("iter-1", 1, None));
+        memory.record_knowledge_lineage(lineage_entry("iter-2", 2, Some("iter-1")));
+        memory.record_knowledge_lineage(lineage_entry("iter-3", 3, Some("iter-2")));
+        memory.record_knowledge_lineage(lineage_entry("iter-4", 4, Some("iter-1")));
+
+        let chain: Vec<_> = memory
+            .knowledge_lineage("iter-3")
+            .iter()
+            .map(|e| e.iteration_id.as_str())
+            .collect();
+        assert_eq!(chain, vec!["iter-1", "iter-2", "iter-3"]);
+
+        let chain: Vec<_> = memory
+            .knowledge_lineage("iter-4")
+            .iter()
+            .map(|e| e.iteration_id.as_str())
+            .collect();
+        assert_eq!(chain, vec!["iter-1", "iter-4"]);
+
+        assert!(memory.knowledge_lineage("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_record_knowledge_lineage_replaces_existing() {
+        let mut memory = ProjectMemory::new();
+        memory.record_knowledge_lineage(lineage_entry("iter-1", 1, None));
+        memory.record_knowledge_lineage(lineage_entry("iter-1", 1, None));
+        assert_eq!(memory.knowledge_lineage.len(), 1);
+    }
+
+    #[test]
+    fn test_cleanup_old_knowledge_keeps_lineage() {
+        let mut memory = ProjectMemory::new();
+        for (id, number, base) in [("iter-1", 1, None), ("iter-2", 2, Some("iter-1")), ("iter-3", 3, Some("iter-2"))] {
+            memory.save_iteration_knowledge(IterationKnowledge::new(id.to_string(), number));
+            memory.record_knowledge_lineage(lineage_entry(id, number, base));
+        }
+
+        memory.cleanup_old_knowledge(1);
+
+        assert!(memory.get_iteration_knowledge("iter-1").is_none());
+        assert!(memory.get_iteration_knowledge("iter-3").is_some());
+        assert_eq!(memory.knowledge_lineage("iter-3").len(), 3);
+    }
+
+    #[test]
+    fn test_global_memory_upsert_and_remove() {
+        let mut global = GlobalMemory::new();
+
+        let mut pattern = Pattern::new("Error Handling", "Use thiserror in libraries", "iter-1");
+        pattern.tags.push("rust".to_string());
+        global.upsert_pattern(pattern);
+        global.upsert_pattern(Pattern::new("error handling", "Use anyhow in binaries", "iter-2"));
+
+        assert_eq!(global.patterns.len(), 1);
+        assert_eq!(global.patterns[0].description, "Use anyhow in binaries");
+        assert_eq!(global.query_patterns("ERROR").len(), 1);
+
+        let id = global.patterns[0].id.clone();
+        assert!(global.remove(&id));
+        assert!(!global.remove(&id));
+        assert!(global.patterns.is_empty());
+    }
+
+    #[test]
+    fn test_context_string_marks_global_entries() {
+        let mut pattern = Pattern::new("Logging", "Use tracing", "iter-1");
+        pattern.source_project = Some("billing".to_string());
+        let result = MemoryQueryResult {
+            decisions: Vec::new(),
+            patterns: vec![pattern, Pattern::new("CI", "GitHub Actions", "iter-1")],
+            insights: Vec::new(),
+        };
+
+        let context = result.to_context_string();
+        assert!(context.contains("[global, from billing] Logging"));
+        assert!(context.contains("- CI: GitHub Actions"));
+    }
+
+    #[test]
+    fn test_iteration_knowledge_deserializes_without_lineage_fields() {
+        let json = r#"{
+            "iteration_id": "iter-1",
+            "iteration_number": 1,
+            "idea_summary": "", "prd_summary": "", "design_summary": "", "plan_summary": "",
+            "tech_stack": [], "key_decisions": [], "key_patterns": [],
+            "code_structure": "", "known_issues": [],
+            "created_at": "2024-01-01T00:00:00Z"
+        }"#;
+        let knowledge: IterationKnowledge = serde_json::from_str(json).unwrap();
+        assert!(knowledge.base_iteration_id.is_none());
+        assert!(knowledge.document_hashes.is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+// Git integration for iteration workspaces
+//
+// Each iteration workspace is a git repository on the branch
+// `cowork/<iteration-id>`. A genesis iteration starts from an empty commit;
+// an evolution iteration fetches its base iteration's branch and continues
+// from its head, so the history of a project is one chain of iterations.
+// The commit an iteration started from is tagged `cowork-start`, which lets
+// delivery export just this iteration's commits as a patch series.
+//
+// Everything shells out to the `git` binary. When it is missing, or
+// `[git] enabled = false` is set, workspaces stay plain directories.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::domain::Iteration;
+
+/// Tag marking the commit an iteration started from
+pub const START_TAG: &str = "cowork-start";
+
+const COMMITTER_NAME: &str = "Cowork Forge";
+const COMMITTER_EMAIL: &str = "cowork-forge@localhost";
+
+/// Build output and dependencies that never belong in a stage commit
+const EXCLUDED_PATHS: &[&str] = &[
+    "node_modules/",
+    "target/",
+    "dist/",
+    "build/",
+    ".venv/",
+    "__pycache__/",
+    ".DS_Store",
+];
+
+/// Whether workspaces should be git repositories
+pub fn is_git_enabled() -> bool {
+    let enabled = crate::llm::config::load_config()
+        .map(|c| c.git.enabled)
+        .unwrap_or(true);
+    enabled && git_available()
+}
+
+/// Whether delivery should publish a branch instead of copying files
+pub fn is_branch_delivery_enabled() -> bool {
+    crate::llm::config::load_config()
+        .map(|c| c.git.deliver_as_branch)
+        .unwrap_or(true)
+}
+
+/// Whether the `git` binary can be run
+pub fn git_available() -> bool {
+    Command::new("git")
+        .arg("--version")
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+/// Branch holding an iteration's commits
+pub fn iteration_branch(iteration_id: &str) -> String {
+    format!("cowork/{}", iteration_id)
+}
+
+/// Run git in `dir`, returning trimmed stdout
+fn run_git(dir: &Path, args: &[&str], author: Option<&str>) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(dir)
+        .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false", "-c", "core.autocrlf=false"])
+        .args(args)
+        .env("GIT_COMMITTER_NAME", COMMITTER_NAME)
+        .env("GIT_COMMITTER_EMAIL", COMMITTER_EMAIL)
+        .env("GIT_AUTHOR_NAME", author.unwrap_or(COMMITTER_NAME))
+        .env("GIT_AUTHOR_EMAIL", COMMITTER_EMAIL);
+
+    let output = cmd
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether `dir` is inside a git working tree
+pub fn is_work_tree(dir: &Path) -> bool {
+    run_git(dir, &["rev-parse", "--is-inside-work-tree"], None).is_ok_and(|out| out == "true")
+}
+
+/// Git repository of one iteration workspace
+pub struct WorkspaceRepo {
+    root: PathBuf,
+}
+
+impl WorkspaceRepo {
+    /// Open the repository of a workspace, if it has one
+    pub fn open(workspace: &Path) -> Option<Self> {
+        workspace.join(".git").exists().then(|| Self {
+            root: workspace.to_path_buf(),
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        run_git(&self.root, args, None)
+    }
+
+    /// Create the repository for a workspace, committing whatever it
+    /// already contains as the iteration's starting point
+    pub fn init(workspace: &Path, iteration: &Iteration) -> Result<Self> {
+        let repo = Self::init_empty(workspace, &iteration.id)?;
+        repo.git(&["add", "-A"])?;
+        repo.mark_start(iteration)?;
+        Ok(repo)
+    }
+
+    /// Create the repository for a workspace by branching from the base
+    /// iteration's head. With `code_only`, the inherited `artifacts`
+    /// directory is dropped in a separate commit.
+    pub fn init_from_base(
+        workspace: &Path,
+        iteration: &Iteration,
+        base: &WorkspaceRepo,
+        code_only: bool,
+    ) -> Result<Self> {
+        let repo = Self::init_empty(workspace, &iteration.id)?;
+        let base_path = base.root.to_string_lossy().to_string();
+        repo.git(&["fetch", "-q", "--no-tags", &base_path, "HEAD"])?;
+        repo.git(&["checkout", "-q", "-B", &iteration_branch(&iteration.id), "FETCH_HEAD"])?;
+
+        if code_only && workspace.join("artifacts").exists() {
+            repo.git(&["rm", "-r", "-q", "--ignore-unmatch", "artifacts"])?;
+            repo.commit_all(&format!("Drop inherited artifacts for: {}", iteration.title), None)?;
+        }
+        repo.mark_start(iteration)?;
+        Ok(repo)
+    }
+
+    fn mark_start(&self, iteration: &Iteration) -> Result<()> {
+        let message = format!("Start iteration: {}\n\nIteration: {}\n", iteration.title, iteration.id);
+        self.git(&["commit", "-q", "--no-verify", "--allow-empty", "-m", &message])?;
+        self.git(&["tag", "-f", START_TAG])?;
+        Ok(())
+    }
+
+    fn init_empty(workspace: &Path, iteration_id: &str) -> Result<Self> {
+        std::fs::create_dir_all(workspace)?;
+        run_git(workspace, &["init", "-q"], None)?;
+        let repo = Self {
+            root: workspace.to_path_buf(),
+        };
+        repo.git(&["symbolic-ref", "HEAD", &format!("refs/heads/{}", iteration_branch(iteration_id))])?;
+
+        let exclude = workspace.join(".git").join("info").join("exclude");
+        if let Some(parent) = exclude.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&exclude, format!("{}\n", EXCLUDED_PATHS.join("\n")))?;
+        Ok(repo)
+    }
+
+    /// Commit every change in the workspace. Returns the new commit, or
+    /// `None` when there was nothing to commit.
+    pub fn commit_all(&self, message: &str, author: Option<&str>) -> Result<Option<String>> {
+        self.git(&["add", "-A"])?;
+        if self.git(&["status", "--porcelain"])?.is_empty() {
+            return Ok(None);
+        }
+        run_git(&self.root, &["commit", "-q", "--no-verify", "-m", message], author)?;
+        Ok(Some(self.head()?))
+    }
+
+    pub fn head(&self) -> Result<String> {
+        self.git(&["rev-parse", "HEAD"])
+    }
+
+    /// Commits made by this iteration (after `cowork-start`), oldest first,
+    /// as `(hash, subject)`
+    pub fn iteration_commits(&self) -> Result<Vec<(String, String)>> {
+        let range = format!("{}..HEAD", START_TAG);
+        let log = self.git(&["log", "--reverse", "--format=%H %s", &range])?;
+        Ok(log
+            .lines()
+            .filter_map(|l| l.split_once(' '))
+            .map(|(hash, subject)| (hash.to_string(), subject.to_string()))
+            .collect())
+    }
+
+    /// Write this iteration's commits as a patch series into `out_dir`
+    pub fn export_patches(&self, out_dir: &Path) -> Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(out_dir)?;
+        let out = out_dir.to_string_lossy().to_string();
+        let range = format!("{}..HEAD", START_TAG);
+        let files = self.git(&["format-patch", "-o", &out, &range])?;
+        Ok(files.lines().filter(|l| !l.is_empty()).map(PathBuf::from).collect())
+    }
+
+    /// Fetch the workspace head into the repository at `project_root` as
+    /// `branch`, leaving the project's working tree untouched
+    pub fn publish_branch(&self, project_root: &Path, branch: &str) -> Result<String> {
+        let source = self.root.to_string_lossy().to_string();
+        let refspec = format!("+HEAD:refs/heads/{}", branch);
+        run_git(project_root, &["fetch", "-q", "--no-tags", &source, &refspec], None)?;
+        run_git(project_root, &["rev-parse", &format!("refs/heads/{}", branch)], None)
+    }
+}
+
+/// Message of a stage commit: stage and iteration in the subject, the agent
+/// and the feedback that led to this revision in the body
+pub fn stage_commit_message(stage: &str, iteration: &Iteration, agent: &str, feedback: Option<&str>) -> String {
+    let mut message = format!("{}: {}\n\nIteration: {}\n", stage, iteration.title, iteration.id);
+    if !agent.is_empty() {
+        message.push_str(&format!("Agent: {}\n", agent));
+    }
+    if let Some(feedback) = feedback.map(str::trim).filter(|f| !f.is_empty()) {
+        message.push_str(&format!("\nFeedback:\n{}\n", feedback));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{InheritanceMode, Project};
+
+    #[test]
+    fn test_stage_commits_and_inheritance() {
+        if !git_available() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let base_ws = dir.path().join("base");
+        std::fs::create_dir_all(&base_ws).unwrap();
+
+        let project = Project::new("demo");
+        let base_iter = Iteration::create_genesis(&project, "Base".to_string(), String::new());
+        let base = WorkspaceRepo::init(&base_ws, &base_iter).unwrap();
+        std::fs::write(base_ws.join("index.html"), "<h1>v1</h1>").unwrap();
+        let msg = stage_commit_message("coding", &base_iter, "Coding Actor", Some("Use a heading"));
+        assert!(base.commit_all(&msg, Some("Coding Actor")).unwrap().is_some());
+        assert!(base.commit_all(&msg, None).unwrap().is_none());
+
+        let next_ws = dir.path().join("next");
+        let next_iter = Iteration::create_evolution(
+            &project,
+            "Next".to_string(),
+            String::new(),
+            base_iter.id.clone(),
+            InheritanceMode::Partial,
+        );
+        let next = WorkspaceRepo::init_from_base(&next_ws, &next_iter, &base, true).unwrap();
+        assert_eq!(std::fs::read_to_string(next_ws.join("index.html")).unwrap(), "<h1>v1</h1>");
+        assert!(next.iteration_commits().unwrap().is_empty());
+
+        std::fs::write(next_ws.join("index.html"), "<h1>v2</h1>").unwrap();
+        next.commit_all("coding: Next", None).unwrap();
+        let commits = next.iteration_commits().unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].1, "coding: Next");
+
+        let patches = next.export_patches(&dir.path().join("patches")).unwrap();
+        assert_eq!(patches.len(), 1);
+
+        let project_root = dir.path().join("project");
+        std::fs::create_dir_all(&project_root).unwrap();
+        run_git(&project_root, &["init", "-q"], None).unwrap();
+        let branch = iteration_branch(&next_iter.id);
+        assert_eq!(next.publish_branch(&project_root, &branch).unwrap(), next.head().unwrap());
+    }
+}
@@ -32,7 +32,16 @@ Extract from README the commands for:
 
 Execute them sequentially using `run_command(command, description)`.
 
-## Step 3: Analyze Results & Decide
+## Step 2.5: Smoke Test (servers and web apps)
+If the README documents a dev/start command (npm run dev, cargo run, uvicorn, ...), run it with `start_process(command)` — never with `run_command`, which rejects servers. Use `{PORT}` where the command takes a port (e.g. `npx vite --port {PORT}`); the port is also in `PORT`.
+- Status "failed" → the app crashes on start: `goto_stage("coding", <output excerpt>)`.
+- Status "ready" → `http_probe(id, "/")` plus one or two documented API routes. A 5xx or connection error is a failure; use `read_process_output(id)` to find the cause.
+- Call `stop_process(id)` when done. Skip this step for CLIs, libraries and static HTML.
+
+## Step 3: Trace Requirements
+After the build succeeds, call `check_traceability(save=true)`. Add a "Traceability" section to the report listing the gaps (requirements without tasks, planned files missing, files without task/tests). Gaps are reported, not blocking — unless planned files are missing, which is a STRUCTURE INCOMPLETE failure.
+
+## Step 4: Analyze Results & Decide
 
 ### ALL CHECKS PASS → save_check_report:
 ```
@@ -61,9 +70,15 @@ List missing files and what they should contain.
 
 # Tools
 - `list_files(path)` — verify project structure
+- `query_code_index(mode, name?, path?)` — locate symbols and imports without reading whole files
 - `read_file(path)` — read README.md
 - `run_command(command, description, timeout?)` — run install/build commands
+- `start_process(command, port?, ready_pattern?)` — start a dev server in the background
+- `read_process_output(id, cursor?)` — server logs
+- `http_probe(id, path)` / `http_probe(url)` — request a local server, returns status and body
+- `stop_process(id)` — stop the server
 - `get_plan()` — check task status (optional)
+- `check_traceability(format?, save?)` — requirement → feature → task → file → test matrix and gaps
 - `goto_stage(stage, reason)` — return to Coding with detailed feedback
 - `save_check_report(content)` — save passing report
 
@@ -82,7 +97,10 @@ list_files(".") → structure OK
 read_file("README.md") → npm install && npm run build
 run_command("npm install", "Install deps") → success
 run_command("npm run build", "Build") → success
-save_check_report("# Check Report\n\n## Results\n- Dependencies: ✅\n- Build: ✅\n\n## Conclusion\n项目构建成功。")
+start_process("npm run dev -- --port {PORT}") → ready, id "proc-1"
+http_probe(id="proc-1", path="/") → 200
+stop_process("proc-1")
+save_check_report("# Check Report\n\n## Results\n- Dependencies: ✅\n- Build: ✅\n- Smoke test: ✅ GET / 200\n\n## Conclusion\n项目构建成功。")
 ```
 
 **Build failure**:
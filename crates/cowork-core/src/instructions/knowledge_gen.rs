@@ -51,7 +51,8 @@ Use `read_file_with_limit` to examine configuration files (package.json, Cargo.t
 - Dependencies
 
 ## 3. Code Structure Analysis
-Use `list_files` and `read_file_with_limit` to understand the codebase structure:
+Start with `query_code_index(mode="overview")` - it returns a deterministic outline of source files, public symbols and import edges.
+Then use `list_files` and `read_file_with_limit` to understand the codebase structure:
 - Main directories and their purposes
 - Key files and their roles
 - Entry points
@@ -153,6 +154,11 @@ You will generate a JSON object with the following structure:
   - Recursive by default
   - No call limit
 
+- `query_code_index(mode, name?, kind?, path?)`: Query the code structure index
+  - mode "overview": files, public symbols and most-imported files
+  - mode "symbols"/"file"/"dependencies"/"dependents" for targeted lookups
+  - No call limit - prefer it over reading files to learn the structure
+
 ## Context Loading
 - `load_document_summary(doc_type)`: Load pre-summarized document
   - doc_type: "idea", "prd", "design", "plan"
@@ -18,6 +18,7 @@ DO NOT STOP after analysis - you MUST generate and save all artifacts.
 - Call `scan_project(project_path)` to get directory structure
 - Call `detect_tech_stack(project_path)` to identify technologies
 - Call `read_project_file(project_path, relative_path)` to read key files (README.md, package.json, etc.)
+- Call `find_files(project_path, pattern)` and `search_code(project_path, pattern)` to locate entry points, routes, models and configuration instead of reading files one by one
 
 ### Step 2: Generate Artifacts (MANDATORY)
 Based on artifact_options, you MUST generate and save:
@@ -65,6 +66,8 @@ save_artifact(filename="plan.md", content="...")
 - `detect_tech_stack(project_path)` - Detect technologies
 - `read_project_file(project_path, relative_path, max_lines?)` - Read a file
 - `list_project_directory(project_path, relative_path?)` - List directory
+- `find_files(project_path, pattern, path?)` - Find files by glob (e.g. "**/routes/*.ts")
+- `search_code(project_path, pattern, path?, glob?, context_lines?)` - Regex search with line numbers and context
 - `save_artifact(filename, content)` - Save artifact (MANDATORY for completion)
 
 ## Project Information
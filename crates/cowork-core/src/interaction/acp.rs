@@ -0,0 +1,395 @@
+// ACP implementation of InteractiveBackend, used by `cowork acp`
+//
+// Cowork acts as the agent here: messages and streaming output become
+// `session/update` notifications for the current session, tool calls become
+// ACP tool calls, and input requests become `session/request_permission`
+// requests. The ACP connection is not `Send`, so everything is handed to the
+// connection loop through a channel (see `crate::acp::server`).
+
+use super::{InteractiveBackend, InputOption, InputResponse, MessageContext, MessageLevel, MessageType, ProgressInfo};
+use agent_client_protocol as acp;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, oneshot};
+
+/// Work for the ACP connection loop
+pub enum AcpOutbound {
+    Update(acp::SessionNotification),
+    Permission {
+        request: acp::RequestPermissionRequest,
+        reply: oneshot::Sender<acp::Result<acp::RequestPermissionResponse>>,
+    },
+    /// Answered once every earlier update has been sent
+    Flush(oneshot::Sender<()>),
+}
+
+/// Option ids and labels that answer an input request positively or negatively
+const ALLOW_WORDS: &[&str] = &["yes", "y", "approve", "accept", "confirm", "continue", "allow", "ok"];
+const REJECT_WORDS: &[&str] = &["no", "n", "reject", "deny", "cancel", "skip", "abort", "stop"];
+
+/// Option ids offered for free-text input requests, which ACP cannot answer
+/// with text: accept keeps the proposed content, skip cancels
+const ACCEPT_OPTION: &str = "accept";
+const SKIP_OPTION: &str = "skip";
+
+struct Shared {
+    tx: mpsc::UnboundedSender<AcpOutbound>,
+    session_id: Mutex<Option<acp::SessionId>>,
+    /// Open tool call ids per tool name, oldest first (results are reported
+    /// by name only)
+    tool_calls: Mutex<HashMap<String, VecDeque<String>>>,
+    next_id: AtomicU64,
+    current_stage: Mutex<Option<String>>,
+}
+
+impl Shared {
+    fn send_update(&self, update: acp::SessionUpdate) {
+        let Some(session_id) = self.session_id.lock().ok().and_then(|s| s.clone()) else {
+            return;
+        };
+        let _ = self
+            .tx
+            .send(AcpOutbound::Update(acp::SessionNotification::new(session_id, update)));
+    }
+
+    fn send_text(&self, text: String, thought: bool) {
+        let chunk = acp::ContentChunk::new(text.into());
+        self.send_update(if thought {
+            acp::SessionUpdate::AgentThoughtChunk(chunk)
+        } else {
+            acp::SessionUpdate::AgentMessageChunk(chunk)
+        });
+    }
+
+    fn tool_call(&self, tool_name: &str, arguments: &Value, agent_name: &str) {
+        let id = format!("cowork-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        if let Ok(mut calls) = self.tool_calls.lock() {
+            calls.entry(tool_name.to_string()).or_default().push_back(id.clone());
+        }
+        let mut call = acp::ToolCall::new(id, format!("{} ({})", tool_name, agent_name))
+            .kind(tool_kind(tool_name))
+            .status(acp::ToolCallStatus::InProgress);
+        if !arguments.is_null() {
+            call = call.raw_input(arguments.clone());
+        }
+        self.send_update(acp::SessionUpdate::ToolCall(call));
+    }
+
+    fn tool_result(&self, tool_name: &str, result: &str, success: bool) {
+        let id = self
+            .tool_calls
+            .lock()
+            .ok()
+            .and_then(|mut calls| calls.get_mut(tool_name).and_then(VecDeque::pop_front));
+        let status = if success {
+            acp::ToolCallStatus::Completed
+        } else {
+            acp::ToolCallStatus::Failed
+        };
+        let mut fields = acp::ToolCallUpdateFields::new().status(status);
+        if !result.is_empty() {
+            fields = fields.content(vec![result.to_string().into()]);
+        }
+        match id {
+            Some(id) => self.send_update(acp::SessionUpdate::ToolCallUpdate(acp::ToolCallUpdate::new(id, fields))),
+            // A result without a reported call still shows up as a finished call
+            None => {
+                let id = format!("cowork-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+                let call = acp::ToolCall::new(id, tool_name.to_string())
+                    .kind(tool_kind(tool_name))
+                    .status(status)
+                    .content(if result.is_empty() { Vec::new() } else { vec![result.to_string().into()] });
+                self.send_update(acp::SessionUpdate::ToolCall(call));
+            }
+        }
+    }
+}
+
+pub struct AcpBackend {
+    shared: Arc<Shared>,
+}
+
+impl AcpBackend {
+    /// Create the backend and the receiving end for the connection loop
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<AcpOutbound>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let shared = Shared {
+            tx,
+            session_id: Mutex::new(None),
+            tool_calls: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            current_stage: Mutex::new(None),
+        };
+        (Self { shared: Arc::new(shared) }, rx)
+    }
+
+    /// Session that receives updates; `None` drops them (between prompts)
+    pub fn set_session(&self, session_id: Option<acp::SessionId>) {
+        if let Ok(mut current) = self.shared.session_id.lock() {
+            *current = session_id;
+        }
+        if let Ok(mut calls) = self.shared.tool_calls.lock() {
+            calls.clear();
+        }
+        if let Ok(mut stage) = self.shared.current_stage.lock() {
+            *stage = None;
+        }
+    }
+
+    /// Send agent text to the current session
+    pub fn send_text(&self, text: impl Into<String>) {
+        self.shared.send_text(text.into(), false);
+    }
+
+    /// Wait until every update queued so far has been sent
+    pub async fn flush(&self) {
+        let (done, sent) = oneshot::channel();
+        if self.shared.tx.send(AcpOutbound::Flush(done)).is_ok() {
+            let _ = sent.await;
+        }
+    }
+
+    /// Forward tool notifications (`notify_tool_call`/`notify_tool_result`)
+    /// as ACP tool calls
+    pub fn install_tool_notifier(&self) {
+        let shared = self.shared.clone();
+        // notify_tool_result passes `Null` arguments and reports success in
+        // the flag that is always `true` for calls
+        crate::tools::set_tool_notify_callback(
+            move |tool_name: &str, args: &Value, flag: bool, result: &str, agent_name: &str| {
+                if args.is_null() {
+                    shared.tool_result(tool_name, result, flag);
+                } else {
+                    shared.tool_call(tool_name, args, agent_name);
+                }
+            },
+        );
+    }
+
+    fn track_stage(&self, stage: &str) {
+        let Ok(mut current) = self.shared.current_stage.lock() else {
+            return;
+        };
+        if current.as_deref() != Some(stage) {
+            *current = Some(stage.to_string());
+            drop(current);
+            self.shared.send_text(format!("\n\n## Stage: {}\n\n", stage), false);
+        }
+    }
+}
+
+/// ACP tool kind for a Cowork tool, used by clients to pick icons
+pub fn tool_kind(tool_name: &str) -> acp::ToolKind {
+    let name = tool_name.to_lowercase();
+    if name.starts_with("read") || name.starts_with("load") || name.starts_with("get") || name.starts_with("list") {
+        acp::ToolKind::Read
+    } else if name.starts_with("write") || name.starts_with("save") || name.starts_with("edit") || name.starts_with("update") || name.contains("patch") {
+        acp::ToolKind::Edit
+    } else if name.starts_with("delete") || name.starts_with("remove") {
+        acp::ToolKind::Delete
+    } else if name.contains("search") || name.starts_with("find") {
+        acp::ToolKind::Search
+    } else if name.contains("command") || name.starts_with("run") || name.starts_with("check") || name == "terminal" {
+        acp::ToolKind::Execute
+    } else if name.contains("fetch") || name.contains("http") {
+        acp::ToolKind::Fetch
+    } else if name.contains("goto") || name.contains("stage") {
+        acp::ToolKind::SwitchMode
+    } else {
+        acp::ToolKind::Other
+    }
+}
+
+fn matches_word(option: &InputOption, words: &[&str]) -> bool {
+    let id = option.id.to_lowercase();
+    let label = option.label.to_lowercase();
+    words
+        .iter()
+        .any(|w| id == *w || label == *w || label.starts_with(&format!("{} ", w)))
+}
+
+/// Permission options for an input request. Options that read as a refusal
+/// are offered as "reject", everything else as "allow".
+pub fn permission_options(options: &[InputOption]) -> Vec<acp::PermissionOption> {
+    if options.is_empty() {
+        return vec![
+            acp::PermissionOption::new(ACCEPT_OPTION, "Accept", acp::PermissionOptionKind::AllowOnce),
+            acp::PermissionOption::new(SKIP_OPTION, "Skip", acp::PermissionOptionKind::RejectOnce),
+        ];
+    }
+    options
+        .iter()
+        .map(|o| {
+            let kind = if matches_word(o, REJECT_WORDS) && !matches_word(o, ALLOW_WORDS) {
+                acp::PermissionOptionKind::RejectOnce
+            } else {
+                acp::PermissionOptionKind::AllowOnce
+            };
+            acp::PermissionOption::new(o.id.clone(), o.label.clone(), kind)
+        })
+        .collect()
+}
+
+/// Map the client's answer back to the input request
+pub fn input_response(
+    outcome: &acp::RequestPermissionOutcome,
+    options: &[InputOption],
+    initial_content: Option<String>,
+) -> InputResponse {
+    match outcome {
+        acp::RequestPermissionOutcome::Selected(selected) => {
+            let id = selected.option_id.0.to_string();
+            if options.is_empty() {
+                match id.as_str() {
+                    ACCEPT_OPTION => InputResponse::Text(initial_content.unwrap_or_default()),
+                    _ => InputResponse::Cancel,
+                }
+            } else if options.iter().any(|o| o.id == id) {
+                InputResponse::Selection(id)
+            } else {
+                InputResponse::Cancel
+            }
+        }
+        _ => InputResponse::Cancel,
+    }
+}
+
+#[async_trait]
+impl InteractiveBackend for AcpBackend {
+    async fn show_message(&self, level: MessageLevel, content: String) {
+        self.shared.send_text(format!("{} {}\n", level.emoji(), content), false);
+    }
+
+    async fn show_message_with_context(&self, level: MessageLevel, content: String, context: MessageContext) {
+        if let Some(stage) = &context.stage_name {
+            self.track_stage(stage);
+        }
+        match context.message_type {
+            MessageType::Thinking => self.shared.send_text(content, true),
+            // Reported through send_tool_call/send_tool_result
+            MessageType::ToolCall { .. } | MessageType::ToolResult { .. } => {}
+            MessageType::Streaming { .. } => self.shared.send_text(content, false),
+            MessageType::Normal => self.show_message(level, content).await,
+        }
+    }
+
+    async fn send_streaming(&self, content: String, _agent_name: &str, is_thinking: bool) {
+        self.shared.send_text(content, is_thinking);
+    }
+
+    async fn send_tool_call(&self, tool_name: &str, arguments: &Value, agent_name: &str) {
+        self.shared.tool_call(tool_name, arguments, agent_name);
+    }
+
+    async fn send_tool_result(&self, tool_name: &str, result: &str, success: bool, _agent_name: &str) {
+        self.shared.tool_result(tool_name, result, success);
+    }
+
+    async fn request_input(&self, prompt: &str, options: Vec<InputOption>, initial_content: Option<String>) -> Result<InputResponse> {
+        let session_id = self
+            .shared
+            .session_id
+            .lock()
+            .ok()
+            .and_then(|s| s.clone())
+            .ok_or_else(|| anyhow::anyhow!("No ACP session to ask: {}", prompt))?;
+
+        let id = format!("cowork-input-{}", self.shared.next_id.fetch_add(1, Ordering::Relaxed));
+        let mut fields = acp::ToolCallUpdateFields::new()
+            .title(prompt.to_string())
+            .kind(acp::ToolKind::Think)
+            .status(acp::ToolCallStatus::Pending);
+        if let Some(content) = initial_content.as_ref().filter(|c| !c.is_empty()) {
+            fields = fields.content(vec![content.clone().into()]);
+        }
+        let request = acp::RequestPermissionRequest::new(
+            session_id,
+            acp::ToolCallUpdate::new(id, fields),
+            permission_options(&options),
+        );
+
+        let (reply, response) = oneshot::channel();
+        self.shared
+            .tx
+            .send(AcpOutbound::Permission { request, reply })
+            .map_err(|_| anyhow::anyhow!("ACP connection closed while waiting for input: {}", prompt))?;
+        let response = response
+            .await
+            .map_err(|_| anyhow::anyhow!("ACP connection closed while waiting for input: {}", prompt))?
+            .map_err(|e| anyhow::anyhow!("ACP permission request failed: {}", e))?;
+
+        Ok(input_response(&response.outcome, &options, initial_content))
+    }
+
+    async fn show_progress(&self, _task_id: String, progress: ProgressInfo) {
+        self.shared.send_text(
+            format!("{} ({}/{})\n", progress.message, progress.current, progress.total),
+            true,
+        );
+    }
+
+    async fn submit_response(&self, _request_id: String, _response: String) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(id: &str, label: &str) -> InputOption {
+        InputOption { id: id.to_string(), label: label.to_string(), description: None }
+    }
+
+    #[test]
+    fn test_permission_options_and_response() {
+        let options = vec![option("yes", "Continue"), option("edit", "Edit the PRD"), option("no", "Reject")];
+        let kinds: Vec<_> = permission_options(&options).into_iter().map(|o| o.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                acp::PermissionOptionKind::AllowOnce,
+                acp::PermissionOptionKind::AllowOnce,
+                acp::PermissionOptionKind::RejectOnce
+            ]
+        );
+
+        let selected = |id: &str| {
+            acp::RequestPermissionOutcome::Selected(acp::SelectedPermissionOutcome::new(id.to_string()))
+        };
+        assert!(matches!(input_response(&selected("edit"), &options, None), InputResponse::Selection(id) if id == "edit"));
+        assert!(matches!(input_response(&acp::RequestPermissionOutcome::Cancelled, &options, None), InputResponse::Cancel));
+
+        // Free-text requests can only be accepted as proposed or skipped
+        assert_eq!(permission_options(&[]).len(), 2);
+        assert!(matches!(
+            input_response(&selected(ACCEPT_OPTION), &[], Some("draft".to_string())),
+            InputResponse::Text(t) if t == "draft"
+        ));
+        assert!(matches!(input_response(&selected(SKIP_OPTION), &[], None), InputResponse::Cancel));
+    }
+
+    #[tokio::test]
+    async fn test_updates_follow_session_and_pair_tool_calls() {
+        let (backend, mut rx) = AcpBackend::new();
+        backend.show_message(MessageLevel::Info, "dropped".to_string()).await;
+        assert!(rx.try_recv().is_err());
+
+        backend.set_session(Some(acp::SessionId::new("s1")));
+        backend.send_tool_call("read_file", &serde_json::json!({"path": "a.rs"}), "coding").await;
+        backend.send_tool_result("read_file", "fn main() {}", true, "coding").await;
+
+        let Ok(AcpOutbound::Update(call)) = rx.try_recv() else { panic!("expected tool call") };
+        let Ok(AcpOutbound::Update(result)) = rx.try_recv() else { panic!("expected tool result") };
+        let (acp::SessionUpdate::ToolCall(call), acp::SessionUpdate::ToolCallUpdate(update)) = (call.update, result.update) else {
+            panic!("unexpected updates");
+        };
+        assert_eq!(call.kind, acp::ToolKind::Read);
+        assert_eq!(call.tool_call_id, update.tool_call_id);
+        assert_eq!(update.fields.status, Some(acp::ToolCallStatus::Completed));
+    }
+}
@@ -0,0 +1,366 @@
+// Headless implementation of InteractiveBackend for CI and unattended runs
+//
+// Never reads stdin. Stage confirmation gates are resolved from a policy
+// file, `ask_user` questions are answered from pre-supplied answers, and
+// anything that genuinely needs a human is recorded so the caller can exit
+// with a "paused for human" status instead of hanging.
+
+use super::{CliBackend, InteractiveBackend, InputOption, InputResponse, MessageContext, MessageLevel, ProgressInfo};
+use crate::data::Severity;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Environment variable that enables headless mode (`1`/`true`/`yes`)
+pub const NON_INTERACTIVE_ENV: &str = "COWORK_NON_INTERACTIVE";
+/// Environment variable pointing at the policy file
+pub const POLICY_FILE_ENV: &str = "COWORK_POLICY";
+/// Policy file looked up in `.cowork-v2/` when none is given explicitly
+pub const DEFAULT_POLICY_FILENAME: &str = "policy.toml";
+
+/// Process exit codes for headless runs (2 is left to clap usage errors)
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_PAUSED_FOR_HUMAN: i32 = 3;
+
+/// Marker the executor puts into stage confirmation prompts
+const GATE_PROMPT_MARKER: &str = "[ARTIFACT_TYPE:";
+
+/// Whether headless mode is requested through the environment
+pub fn non_interactive_from_env() -> bool {
+    std::env::var(NON_INTERACTIVE_ENV)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// How a stage confirmation gate is resolved
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GatePolicy {
+    /// Always continue
+    AutoApprove,
+    /// Continue unless the iteration has outstanding critical feedback
+    #[default]
+    AutoApproveIfNoCriticalFeedback,
+    /// Stop at the gate and leave the iteration paused for a human
+    FailOnGate,
+}
+
+/// Policy for headless runs, usually loaded from `policy.toml`:
+///
+/// ```toml
+/// gates = "auto-approve-if-no-critical-feedback"
+///
+/// [stage_gates]
+/// coding = "fail-on-gate"
+///
+/// [answers]
+/// "which database" = "PostgreSQL"
+/// "deploy to production" = "no"
+/// ```
+///
+/// `answers` keys are matched case-insensitively as substrings of the
+/// question; the longest matching key wins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeadlessPolicy {
+    #[serde(default)]
+    pub gates: GatePolicy,
+    #[serde(default)]
+    pub stage_gates: HashMap<String, GatePolicy>,
+    #[serde(default)]
+    pub answers: BTreeMap<String, String>,
+}
+
+impl HeadlessPolicy {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read policy file {:?}", path))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse policy file {:?}", path))
+    }
+
+    /// Resolve the policy from an explicit path, `COWORK_POLICY`, or
+    /// `.cowork-v2/policy.toml`, falling back to the default policy
+    pub fn resolve(explicit: Option<&Path>) -> Result<Self> {
+        if let Some(path) = explicit {
+            return Self::load(path);
+        }
+        if let Ok(path) = std::env::var(POLICY_FILE_ENV)
+            && !path.trim().is_empty()
+        {
+            return Self::load(Path::new(path.trim()));
+        }
+        if let Ok(dir) = crate::persistence::get_cowork_dir() {
+            let path: PathBuf = dir.join(DEFAULT_POLICY_FILENAME);
+            if path.exists() {
+                return Self::load(&path);
+            }
+        }
+        Ok(Self::default())
+    }
+
+    pub fn gate_for(&self, stage: Option<&str>) -> GatePolicy {
+        stage
+            .and_then(|s| self.stage_gates.get(s))
+            .copied()
+            .unwrap_or(self.gates)
+    }
+
+    pub fn answer_for(&self, question: &str) -> Option<&str> {
+        let question = question.to_lowercase();
+        self.answers
+            .iter()
+            .filter(|(key, _)| question.contains(&key.to_lowercase()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, answer)| answer.as_str())
+    }
+}
+
+/// A gate decision taken by the headless backend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GateDecision {
+    pub stage: Option<String>,
+    pub approved: bool,
+    pub reason: String,
+}
+
+pub struct HeadlessBackend {
+    policy: HeadlessPolicy,
+    /// Backend used for all output (terminal text or JSON lines)
+    output: Arc<dyn InteractiveBackend>,
+    current_stage: Mutex<Option<String>>,
+    gate_decisions: Mutex<Vec<GateDecision>>,
+    unanswered: Mutex<Vec<String>>,
+}
+
+impl HeadlessBackend {
+    pub fn new(policy: HeadlessPolicy) -> Self {
+        Self::with_output(policy, Arc::new(CliBackend::new()))
+    }
+
+    pub fn with_output(policy: HeadlessPolicy, output: Arc<dyn InteractiveBackend>) -> Self {
+        Self {
+            policy,
+            output,
+            current_stage: Mutex::new(None),
+            gate_decisions: Mutex::new(Vec::new()),
+            unanswered: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn gate_decisions(&self) -> Vec<GateDecision> {
+        self.gate_decisions.lock().map(|g| g.clone()).unwrap_or_default()
+    }
+
+    /// Questions that could not be answered from the policy
+    pub fn unanswered_questions(&self) -> Vec<String> {
+        self.unanswered.lock().map(|u| u.clone()).unwrap_or_default()
+    }
+
+    /// The gate that stopped the run, if any
+    pub fn blocking_gate(&self) -> Option<GateDecision> {
+        self.gate_decisions().into_iter().rev().find(|d| !d.approved)
+    }
+
+    fn current_stage(&self) -> Option<String> {
+        self.current_stage.lock().ok().and_then(|s| s.clone())
+    }
+
+    fn resolve_gate(&self) -> GateDecision {
+        let stage = self.current_stage();
+        let (approved, reason) = match self.policy.gate_for(stage.as_deref()) {
+            GatePolicy::AutoApprove => (true, "auto-approve".to_string()),
+            GatePolicy::FailOnGate => (false, "fail-on-gate: human review required".to_string()),
+            GatePolicy::AutoApproveIfNoCriticalFeedback => match outstanding_critical_feedback() {
+                0 => (true, "no critical feedback".to_string()),
+                n => (false, format!("{} outstanding critical feedback item(s)", n)),
+            },
+        };
+        let decision = GateDecision { stage, approved, reason };
+        if let Ok(mut decisions) = self.gate_decisions.lock() {
+            decisions.push(decision.clone());
+        }
+        decision
+    }
+
+    fn answer_question(&self, prompt: &str, options: &[InputOption]) -> Result<InputResponse> {
+        let Some(answer) = self.policy.answer_for(prompt) else {
+            if let Ok(mut unanswered) = self.unanswered.lock() {
+                unanswered.push(prompt.to_string());
+            }
+            anyhow::bail!(
+                "Non-interactive mode: no pre-supplied answer for question '{}'. \
+                 Add a matching entry under [answers] in the policy file.",
+                prompt
+            );
+        };
+
+        // Map answers onto option ids/labels (e.g. "yes"/"no") when possible
+        let selected = options.iter().find(|o| {
+            o.id.eq_ignore_ascii_case(answer) || o.label.eq_ignore_ascii_case(answer)
+        });
+        Ok(match selected {
+            Some(option) => InputResponse::Selection(option.id.clone()),
+            None => InputResponse::Text(answer.to_string()),
+        })
+    }
+}
+
+/// Critical feedback still recorded for the current iteration
+fn outstanding_critical_feedback() -> usize {
+    crate::persistence::load_feedback_history()
+        .map(|h| h.feedbacks.iter().filter(|f| f.severity == Severity::Critical).count())
+        .unwrap_or(0)
+}
+
+#[async_trait]
+impl InteractiveBackend for HeadlessBackend {
+    async fn show_message(&self, level: MessageLevel, content: String) {
+        self.output.show_message(level, content).await;
+    }
+
+    async fn show_message_with_context(&self, level: MessageLevel, content: String, context: MessageContext) {
+        if let Some(stage) = &context.stage_name
+            && let Ok(mut current) = self.current_stage.lock()
+        {
+            *current = Some(stage.clone());
+        }
+        self.output.show_message_with_context(level, content, context).await;
+    }
+
+    async fn send_streaming(&self, content: String, agent_name: &str, is_thinking: bool) {
+        self.output.send_streaming(content, agent_name, is_thinking).await;
+    }
+
+    async fn send_tool_call(&self, tool_name: &str, arguments: &Value, agent_name: &str) {
+        self.output.send_tool_call(tool_name, arguments, agent_name).await;
+    }
+
+    async fn send_tool_result(&self, tool_name: &str, result: &str, success: bool, agent_name: &str) {
+        self.output.send_tool_result(tool_name, result, success, agent_name).await;
+    }
+
+    async fn request_input(&self, prompt: &str, options: Vec<InputOption>, _initial_content: Option<String>) -> Result<InputResponse> {
+        let has_option = |id: &str| options.iter().any(|o| o.id == id);
+
+        // Stage confirmation gate
+        if prompt.contains(GATE_PROMPT_MARKER) {
+            let decision = self.resolve_gate();
+            let stage = decision.stage.as_deref().unwrap_or("unknown");
+            if decision.approved {
+                self.output
+                    .show_message(MessageLevel::Info, format!("🤖 Gate '{}' approved ({})", stage, decision.reason))
+                    .await;
+                return Ok(InputResponse::Selection("yes".to_string()));
+            }
+            self.output
+                .show_message(MessageLevel::Warning, format!("⏸️ Gate '{}' requires a human ({})", stage, decision.reason))
+                .await;
+            return Ok(InputResponse::Cancel);
+        }
+
+        // Delivery reviews apply the iteration's changes but never overwrite
+        // files the user changed in the meantime
+        if prompt.contains(crate::delivery::DELIVERY_REVIEW_MARKER) {
+            let choice = if prompt.contains(crate::delivery::DELIVERY_CONFLICT_MARKER) { "reject" } else { "accept" };
+            return Ok(InputResponse::Selection(choice.to_string()));
+        }
+
+        // Content reviews and human-review escalations proceed unchanged
+        if has_option("pass") {
+            return Ok(InputResponse::Selection("pass".to_string()));
+        }
+        if has_option("continue") {
+            return Ok(InputResponse::Selection("continue".to_string()));
+        }
+
+        self.answer_question(prompt, &options)
+    }
+
+    async fn show_progress(&self, task_id: String, progress: ProgressInfo) {
+        self.output.show_progress(task_id, progress).await;
+    }
+
+    async fn submit_response(&self, _request_id: String, _response: String) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yes_no() -> Vec<InputOption> {
+        ["yes", "no"]
+            .iter()
+            .map(|id| InputOption { id: id.to_string(), label: id.to_string(), description: None })
+            .collect()
+    }
+
+    #[test]
+    fn test_policy_parsing_and_stage_override() {
+        let policy: HeadlessPolicy = toml::from_str(
+            r#"
+gates = "auto-approve"
+
+[stage_gates]
+coding = "fail-on-gate"
+
+[answers]
+"database" = "PostgreSQL"
+"which database engine" = "SQLite"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.gate_for(Some("prd")), GatePolicy::AutoApprove);
+        assert_eq!(policy.gate_for(Some("coding")), GatePolicy::FailOnGate);
+        assert_eq!(policy.answer_for("Which DATABASE engine should we use?"), Some("SQLite"));
+        assert_eq!(policy.answer_for("Pick a database"), Some("PostgreSQL"));
+        assert_eq!(policy.answer_for("Deploy now?"), None);
+        assert_eq!(HeadlessPolicy::default().gates, GatePolicy::AutoApproveIfNoCriticalFeedback);
+    }
+
+    #[tokio::test]
+    async fn test_gates_follow_policy() {
+        let policy = HeadlessPolicy {
+            gates: GatePolicy::AutoApprove,
+            stage_gates: HashMap::from([("design".to_string(), GatePolicy::FailOnGate)]),
+            ..Default::default()
+        };
+        let backend = HeadlessBackend::new(policy);
+        let gate = "Stage completed.\n[ARTIFACT_TYPE:requirements]";
+
+        backend
+            .show_message_with_context(MessageLevel::Info, "start".into(), MessageContext::new("x").with_stage("prd"))
+            .await;
+        let response = backend.request_input(gate, yes_no(), None).await.unwrap();
+        assert!(matches!(response, InputResponse::Selection(id) if id == "yes"));
+
+        backend
+            .show_message_with_context(MessageLevel::Info, "start".into(), MessageContext::new("x").with_stage("design"))
+            .await;
+        let response = backend.request_input(gate, yes_no(), None).await.unwrap();
+        assert!(matches!(response, InputResponse::Cancel));
+        assert_eq!(backend.blocking_gate().unwrap().stage.as_deref(), Some("design"));
+    }
+
+    #[tokio::test]
+    async fn test_questions_use_answers_or_fail() {
+        let policy = HeadlessPolicy {
+            answers: BTreeMap::from([("deploy".to_string(), "No".to_string())]),
+            ..Default::default()
+        };
+        let backend = HeadlessBackend::new(policy);
+
+        let response = backend.request_input("Deploy to production?", yes_no(), None).await.unwrap();
+        assert!(matches!(response, InputResponse::Selection(id) if id == "no"));
+
+        let err = backend.request_input("Project name?", vec![], None).await.unwrap_err();
+        assert!(err.to_string().contains("no pre-supplied answer"));
+        assert_eq!(backend.unanswered_questions(), vec!["Project name?"]);
+    }
+}
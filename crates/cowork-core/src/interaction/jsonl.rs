@@ -0,0 +1,369 @@
+// JSON Lines implementation of InteractiveBackend for wrappers and scripts
+//
+// Every event is written to stdout (or another sink, see `RemoteBackend`)
+// as one JSON object per line:
+//
+//   {"v":1,"ts":"2026-01-01T12:00:00Z","type":"message","level":"info",...}
+//
+// `v` is the schema version and is bumped only on incompatible changes;
+// new event types or fields may be added within a version. Input requests
+// are emitted as `input_request` events and answered with one line on stdin
+// (see `parse_input_line`).
+
+use super::{InteractiveBackend, InputOption, InputResponse, MessageContext, MessageLevel, MessageType, ProgressInfo};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+/// Current event schema version
+pub const JSONL_SCHEMA_VERSION: u32 = 1;
+
+/// Envelope written for each event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonlRecord {
+    pub v: u32,
+    pub ts: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: JsonlEvent,
+}
+
+impl JsonlRecord {
+    pub fn new(event: JsonlEvent) -> Self {
+        Self {
+            v: JSONL_SCHEMA_VERSION,
+            ts: Utc::now(),
+            event,
+        }
+    }
+}
+
+/// Events emitted by `--output jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonlEvent {
+    /// A message from an agent or the pipeline
+    Message {
+        level: String,
+        agent: Option<String>,
+        stage: Option<String>,
+        /// normal | thinking | tool_call | tool_result | streaming
+        message_type: String,
+        content: String,
+    },
+    /// Incremental model output
+    Streaming {
+        agent: String,
+        thinking: bool,
+        content: String,
+    },
+    ToolCall {
+        agent: String,
+        tool: String,
+        arguments: Value,
+    },
+    ToolResult {
+        agent: String,
+        tool: String,
+        success: bool,
+        result: String,
+    },
+    /// The pipeline moved to another stage
+    Stage {
+        stage: String,
+        previous: Option<String>,
+    },
+    Progress {
+        task_id: String,
+        current: u32,
+        total: u32,
+        message: String,
+    },
+    /// Input is required; answer with one line on stdin (or through
+    /// `request_id` when served remotely)
+    InputRequest {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+        prompt: String,
+        options: Vec<InputOption>,
+    },
+    /// Iteration lifecycle (created, resumed)
+    Iteration {
+        event: String,
+        iteration_id: String,
+        title: Option<String>,
+    },
+    /// Checks re-run by `cowork watch` after workspace changes
+    Checks {
+        iteration_id: String,
+        changed_files: Vec<String>,
+        results: Value,
+        regressions: Vec<String>,
+    },
+    /// Final outcome of a run: completed | paused | failed
+    Result {
+        status: String,
+        iteration_id: Option<String>,
+        error: Option<String>,
+    },
+}
+
+static STDOUT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Write one event as a JSON line to stdout
+pub fn emit_event(event: JsonlEvent) {
+    let Ok(line) = serde_json::to_string(&JsonlRecord::new(event)) else {
+        return;
+    };
+    let _guard = STDOUT_LOCK.lock();
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", line);
+    let _ = stdout.flush();
+}
+
+fn level_name(level: MessageLevel) -> &'static str {
+    match level {
+        MessageLevel::Info => "info",
+        MessageLevel::Success => "success",
+        MessageLevel::Warning => "warning",
+        MessageLevel::Error => "error",
+        MessageLevel::Debug => "debug",
+    }
+}
+
+fn message_type_name(message_type: &MessageType) -> &'static str {
+    match message_type {
+        MessageType::Normal => "normal",
+        MessageType::Thinking => "thinking",
+        MessageType::ToolCall { .. } => "tool_call",
+        MessageType::ToolResult { .. } => "tool_result",
+        MessageType::Streaming { .. } => "streaming",
+    }
+}
+
+/// Parse one stdin line answering an `input_request`.
+///
+/// Accepts `{"selection":"<option id>"}`, `{"text":"..."}`, `{"cancel":true}`,
+/// or plain text: an option id or 1-based option number selects, an empty
+/// line cancels, anything else is free text.
+pub fn parse_input_line(line: &str, options: &[InputOption]) -> InputResponse {
+    let line = line.trim();
+
+    if let Ok(Value::Object(obj)) = serde_json::from_str::<Value>(line) {
+        if let Some(id) = obj.get("selection").and_then(|v| v.as_str()) {
+            return InputResponse::Selection(id.to_string());
+        }
+        if let Some(text) = obj.get("text").and_then(|v| v.as_str()) {
+            return InputResponse::Text(text.to_string());
+        }
+        return InputResponse::Cancel;
+    }
+
+    if line.is_empty() {
+        return InputResponse::Cancel;
+    }
+    if let Some(option) = options.iter().find(|o| o.id == line) {
+        return InputResponse::Selection(option.id.clone());
+    }
+    if let Ok(n) = line.parse::<usize>()
+        && n > 0
+        && n <= options.len()
+    {
+        return InputResponse::Selection(options[n - 1].id.clone());
+    }
+    InputResponse::Text(line.to_string())
+}
+
+/// Destination of the events produced by `JsonlBackend`
+pub type EventSink = Arc<dyn Fn(JsonlEvent) + Send + Sync>;
+
+pub struct JsonlBackend {
+    sink: EventSink,
+    current_stage: Mutex<Option<String>>,
+}
+
+impl JsonlBackend {
+    /// Write events to stdout
+    pub fn new() -> Self {
+        Self::with_sink(Arc::new(emit_event))
+    }
+
+    pub fn with_sink(sink: EventSink) -> Self {
+        Self {
+            sink,
+            current_stage: Mutex::new(None),
+        }
+    }
+
+    fn emit(&self, event: JsonlEvent) {
+        (self.sink)(event);
+    }
+
+    /// Forward tool notifications (`notify_tool_call`/`notify_tool_result`)
+    /// to the event stream
+    pub fn install_tool_notifier(&self) {
+        let sink = self.sink.clone();
+        // notify_tool_result passes `Null` arguments and reports success in
+        // the flag that is always `true` for calls
+        crate::tools::set_tool_notify_callback(
+            move |tool_name: &str, args: &Value, flag: bool, result: &str, agent_name: &str| {
+                if args.is_null() {
+                    sink(JsonlEvent::ToolResult {
+                        agent: agent_name.to_string(),
+                        tool: tool_name.to_string(),
+                        success: flag,
+                        result: result.to_string(),
+                    });
+                } else {
+                    sink(JsonlEvent::ToolCall {
+                        agent: agent_name.to_string(),
+                        tool: tool_name.to_string(),
+                        arguments: args.clone(),
+                    });
+                }
+            },
+        );
+    }
+
+    fn track_stage(&self, stage: &str) {
+        let Ok(mut current) = self.current_stage.lock() else {
+            return;
+        };
+        if current.as_deref() != Some(stage) {
+            let previous = current.replace(stage.to_string());
+            self.emit(JsonlEvent::Stage {
+                stage: stage.to_string(),
+                previous,
+            });
+        }
+    }
+}
+
+impl Default for JsonlBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InteractiveBackend for JsonlBackend {
+    async fn show_message(&self, level: MessageLevel, content: String) {
+        self.emit(JsonlEvent::Message {
+            level: level_name(level).to_string(),
+            agent: None,
+            stage: None,
+            message_type: "normal".to_string(),
+            content,
+        });
+    }
+
+    async fn show_message_with_context(&self, level: MessageLevel, content: String, context: MessageContext) {
+        if let Some(stage) = &context.stage_name {
+            self.track_stage(stage);
+        }
+        self.emit(JsonlEvent::Message {
+            level: level_name(level).to_string(),
+            agent: Some(context.agent_name),
+            stage: context.stage_name,
+            message_type: message_type_name(&context.message_type).to_string(),
+            content,
+        });
+    }
+
+    async fn send_streaming(&self, content: String, agent_name: &str, is_thinking: bool) {
+        self.emit(JsonlEvent::Streaming {
+            agent: agent_name.to_string(),
+            thinking: is_thinking,
+            content,
+        });
+    }
+
+    async fn send_tool_call(&self, tool_name: &str, arguments: &Value, agent_name: &str) {
+        self.emit(JsonlEvent::ToolCall {
+            agent: agent_name.to_string(),
+            tool: tool_name.to_string(),
+            arguments: arguments.clone(),
+        });
+    }
+
+    async fn send_tool_result(&self, tool_name: &str, result: &str, success: bool, agent_name: &str) {
+        self.emit(JsonlEvent::ToolResult {
+            agent: agent_name.to_string(),
+            tool: tool_name.to_string(),
+            success,
+            result: result.to_string(),
+        });
+    }
+
+    async fn request_input(&self, prompt: &str, options: Vec<InputOption>, _initial_content: Option<String>) -> Result<InputResponse> {
+        self.emit(JsonlEvent::InputRequest {
+            request_id: None,
+            prompt: prompt.to_string(),
+            options: options.clone(),
+        });
+
+        let mut line = String::new();
+        let read = std::io::stdin()
+            .read_line(&mut line)
+            .map_err(|e| anyhow::anyhow!("Failed to read input: {}", e))?;
+        if read == 0 {
+            anyhow::bail!("stdin closed while waiting for input: {}", prompt);
+        }
+        Ok(parse_input_line(&line, &options))
+    }
+
+    async fn show_progress(&self, task_id: String, progress: ProgressInfo) {
+        self.emit(JsonlEvent::Progress {
+            task_id,
+            current: progress.current,
+            total: progress.total,
+            message: progress.message,
+        });
+    }
+
+    async fn submit_response(&self, _request_id: String, _response: String) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> Vec<InputOption> {
+        ["yes", "no"]
+            .iter()
+            .map(|id| InputOption { id: id.to_string(), label: id.to_string(), description: None })
+            .collect()
+    }
+
+    #[test]
+    fn test_record_schema() {
+        let record = JsonlRecord {
+            v: JSONL_SCHEMA_VERSION,
+            ts: Utc::now(),
+            event: JsonlEvent::Stage { stage: "prd".to_string(), previous: Some("idea".to_string()) },
+        };
+        let value = serde_json::to_value(&record).unwrap();
+        assert_eq!(value["v"], 1);
+        assert_eq!(value["type"], "stage");
+        assert_eq!(value["stage"], "prd");
+        assert_eq!(value["previous"], "idea");
+        assert!(value["ts"].is_string());
+    }
+
+    #[test]
+    fn test_parse_input_line() {
+        let opts = options();
+        assert!(matches!(parse_input_line(r#"{"selection":"no"}"#, &opts), InputResponse::Selection(id) if id == "no"));
+        assert!(matches!(parse_input_line(r#"{"text":"more tests"}"#, &opts), InputResponse::Text(t) if t == "more tests"));
+        assert!(matches!(parse_input_line(r#"{"cancel":true}"#, &opts), InputResponse::Cancel));
+        assert!(matches!(parse_input_line("2\n", &opts), InputResponse::Selection(id) if id == "no"));
+        assert!(matches!(parse_input_line("yes", &opts), InputResponse::Selection(id) if id == "yes"));
+        assert!(matches!(parse_input_line("", &opts), InputResponse::Cancel));
+        assert!(matches!(parse_input_line("add caching", &opts), InputResponse::Text(_)));
+    }
+}
@@ -1,12 +1,16 @@
 // Interaction abstraction layer - decouples core engine from UI implementations
-// This module defines the trait that different UI backends (CLI, GUI) must implement
+// This module defines the trait that different UI backends (CLI, GUI, remote) must implement
 
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+pub mod acp;
 pub mod cli;
+pub mod headless;
+pub mod jsonl;
+pub mod remote;
 
 /// Message level for UI feedback
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -159,4 +163,8 @@ pub trait InteractiveBackend: Send + Sync {
 }
 
 // Re-export implementations
+pub use acp::{AcpBackend, AcpOutbound};
 pub use cli::CliBackend;
+pub use headless::{GatePolicy, HeadlessBackend, HeadlessPolicy};
+pub use jsonl::{JsonlBackend, JsonlEvent};
+pub use remote::{PendingInputRequest, RemoteBackend};
@@ -0,0 +1,196 @@
+// Remote implementation of InteractiveBackend for `cowork serve`
+//
+// Display events use the JSON Lines schema (`JsonlEvent`) and are broadcast
+// to subscribers (e.g. an SSE endpoint) instead of stdout. Input requests
+// are parked under a request id until a client answers them through
+// `submit_response`.
+
+use super::jsonl::{parse_input_line, JsonlBackend, JsonlEvent, JsonlRecord};
+use super::{InteractiveBackend, InputOption, InputResponse, MessageContext, MessageLevel, ProgressInfo};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, oneshot};
+
+/// Events buffered per subscriber before it starts lagging
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// An input request waiting for a remote answer
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingInputRequest {
+    pub request_id: String,
+    pub prompt: String,
+    pub options: Vec<InputOption>,
+    pub initial_content: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+struct Pending {
+    request: PendingInputRequest,
+    responder: oneshot::Sender<InputResponse>,
+}
+
+pub struct RemoteBackend {
+    events: broadcast::Sender<JsonlRecord>,
+    display: JsonlBackend,
+    pending: Mutex<BTreeMap<String, Pending>>,
+    next_request: AtomicU64,
+}
+
+impl RemoteBackend {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let sender = events.clone();
+        let display = JsonlBackend::with_sink(Arc::new(move |event| {
+            // No subscribers is fine; events are not replayed
+            let _ = sender.send(JsonlRecord::new(event));
+        }));
+
+        Self {
+            events,
+            display,
+            pending: Mutex::new(BTreeMap::new()),
+            next_request: AtomicU64::new(1),
+        }
+    }
+
+    /// Forward tool notifications to subscribers
+    pub fn install_tool_notifier(&self) {
+        self.display.install_tool_notifier();
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<JsonlRecord> {
+        self.events.subscribe()
+    }
+
+    /// Publish an event that does not come from the executor (iteration
+    /// lifecycle, results)
+    pub fn publish(&self, event: JsonlEvent) {
+        let _ = self.events.send(JsonlRecord::new(event));
+    }
+
+    pub fn pending_requests(&self) -> Vec<PendingInputRequest> {
+        self.pending
+            .lock()
+            .map(|p| p.values().map(|p| p.request.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Cancel every open request, e.g. when the run is abandoned
+    pub fn cancel_pending(&self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            for (_, p) in std::mem::take(&mut *pending) {
+                let _ = p.responder.send(InputResponse::Cancel);
+            }
+        }
+    }
+}
+
+impl Default for RemoteBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InteractiveBackend for RemoteBackend {
+    async fn show_message(&self, level: MessageLevel, content: String) {
+        self.display.show_message(level, content).await;
+    }
+
+    async fn show_message_with_context(&self, level: MessageLevel, content: String, context: MessageContext) {
+        self.display.show_message_with_context(level, content, context).await;
+    }
+
+    async fn send_streaming(&self, content: String, agent_name: &str, is_thinking: bool) {
+        self.display.send_streaming(content, agent_name, is_thinking).await;
+    }
+
+    async fn send_tool_call(&self, tool_name: &str, arguments: &Value, agent_name: &str) {
+        self.display.send_tool_call(tool_name, arguments, agent_name).await;
+    }
+
+    async fn send_tool_result(&self, tool_name: &str, result: &str, success: bool, agent_name: &str) {
+        self.display.send_tool_result(tool_name, result, success, agent_name).await;
+    }
+
+    async fn request_input(&self, prompt: &str, options: Vec<InputOption>, initial_content: Option<String>) -> Result<InputResponse> {
+        let request_id = format!("req-{}", self.next_request.fetch_add(1, Ordering::SeqCst));
+        let (tx, rx) = oneshot::channel();
+
+        let request = PendingInputRequest {
+            request_id: request_id.clone(),
+            prompt: prompt.to_string(),
+            options: options.clone(),
+            initial_content,
+            created_at: Utc::now(),
+        };
+        self.pending
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?
+            .insert(request_id.clone(), Pending { request, responder: tx });
+
+        self.publish(JsonlEvent::InputRequest {
+            request_id: Some(request_id),
+            prompt: prompt.to_string(),
+            options,
+        });
+
+        rx.await.map_err(|_| anyhow::anyhow!("Input request was dropped"))
+    }
+
+    async fn show_progress(&self, task_id: String, progress: ProgressInfo) {
+        self.display.show_progress(task_id, progress).await;
+    }
+
+    /// `response` uses the same format as a JSON Lines stdin answer, e.g.
+    /// `{"selection":"yes"}`, `{"text":"..."}` or `{"cancel":true}`
+    async fn submit_response(&self, request_id: String, response: String) -> Result<()> {
+        let pending = self
+            .pending
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?
+            .remove(&request_id)
+            .ok_or_else(|| anyhow::anyhow!("Request {} not found", request_id))?;
+
+        let answer = parse_input_line(&response, &pending.request.options);
+        pending
+            .responder
+            .send(answer)
+            .map_err(|_| anyhow::anyhow!("Request {} is no longer waiting", request_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_answered_through_submit_response() {
+        let backend = Arc::new(RemoteBackend::new());
+        let mut events = backend.subscribe();
+
+        let options = vec![InputOption { id: "yes".to_string(), label: "Continue".to_string(), description: None }];
+        let waiting = tokio::spawn({
+            let backend = backend.clone();
+            async move { backend.request_input("Continue?", options, None).await }
+        });
+
+        let record = events.recv().await.unwrap();
+        let JsonlEvent::InputRequest { request_id: Some(request_id), .. } = record.event else {
+            panic!("expected an input request");
+        };
+        assert_eq!(backend.pending_requests().len(), 1);
+
+        backend.submit_response(request_id.clone(), r#"{"selection":"yes"}"#.to_string()).await.unwrap();
+        let answer = waiting.await.unwrap().unwrap();
+        assert!(matches!(answer, InputResponse::Selection(id) if id == "yes"));
+        assert!(backend.pending_requests().is_empty());
+        assert!(backend.submit_response(request_id, "yes".to_string()).await.is_err());
+    }
+}
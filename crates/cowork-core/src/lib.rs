@@ -19,11 +19,32 @@ pub mod data;
 // Tech stack configuration
 pub mod tech_stack;
 
+// Code structure index (symbols, modules, import graph)
+pub mod code_index;
+
+// Requirement → feature → task → file → test traceability
+pub mod traceability;
+
+// Git repositories for iteration workspaces
+pub mod git;
+
+// Reviewed delivery of workspaces into the project root
+pub mod delivery;
+
+// Watch mode: re-run checks when workspace files change
+pub mod watch;
+
 // Project runtime configuration (for GUI Preview/Run)
 pub mod project_runtime;
 pub mod runtime_security;
 pub mod runtime_analyzer;
 
+// Sandboxed execution of agent commands
+pub mod sandbox;
+
+// Background processes (dev servers) started by agents
+pub mod process;
+
 // Core modules
 pub mod llm;
 pub mod tools;
@@ -41,6 +62,9 @@ pub mod integration;
 // Project importer for legacy projects
 pub mod importer;
 
+// MCP server exposing project data to other agents
+pub mod mcp_server;
+
 // Re-exports for convenience
 pub use domain::*;
 pub use persistence::*;
@@ -57,6 +81,8 @@ pub use project_runtime::{
     get_preset_config,
 };
 pub use runtime_security::RuntimeSecurityChecker;
+pub use code_index::{CodeIndex, CodeLanguage, CodeSymbol, IndexedFile, ImportEdge, SymbolKind};
+pub use traceability::{TraceabilityFormat, TraceabilityMatrix, TraceabilityOrphans};
 pub use runtime_analyzer::{
     RuntimeAnalyzer, ProjectInfo, save_runtime_config, load_runtime_config, has_runtime_config,
 };
@@ -3,10 +3,20 @@ use adk_model::openai::{OpenAIClient, OpenAIConfig};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 const CONFIG_FILENAME: &str = "config.toml";
 const APP_DIR_NAME: &str = "CoworkCreative";
+const PROFILES_DIR: &str = "profiles";
+
+/// Environment variable selecting a configuration profile
+pub const PROFILE_ENV: &str = "COWORK_PROFILE";
+
+/// Name of the profile stored in the plain `config.toml`
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Profile selected with `--profile`, overriding `COWORK_PROFILE`
+static ACTIVE_PROFILE: RwLock<Option<String>> = RwLock::new(None);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpConfig {
@@ -16,6 +26,9 @@ pub struct McpConfig {
     /// DeepWiki MCP server enabled
     #[serde(default)]
     pub deepwiki_enabled: bool,
+    /// Additional MCP servers (`[[mcp.servers]]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<McpServerEntry>,
 }
 
 impl Default for McpConfig {
@@ -23,6 +36,7 @@ impl Default for McpConfig {
         Self {
             tavily_api_key: String::new(),
             deepwiki_enabled: false,
+            servers: Vec::new(),
         }
     }
 }
@@ -30,7 +44,78 @@ impl Default for McpConfig {
 impl McpConfig {
     /// Check if any MCP server is configured
     pub fn is_any_enabled(&self) -> bool {
-        !self.tavily_api_key.is_empty() || self.deepwiki_enabled
+        !self.tavily_api_key.is_empty() || self.deepwiki_enabled || !self.servers.is_empty()
+    }
+}
+
+/// How Cowork reaches an MCP server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransportKind {
+    /// Streamable HTTP endpoint at `url`
+    #[default]
+    Http,
+    /// Process spawned from `command`, speaking JSON-RPC over stdin/stdout
+    Stdio,
+}
+
+/// A user-configured MCP server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpServerEntry {
+    /// Name used in logs and in `mcp:<name>` tool references
+    pub name: String,
+    #[serde(default)]
+    pub transport: McpTransportKind,
+    /// Endpoint URL (http)
+    #[serde(default)]
+    pub url: String,
+    /// Extra request headers, e.g. `Authorization` (http)
+    #[serde(default)]
+    pub headers: std::collections::HashMap<String, String>,
+    /// Program to spawn (stdio)
+    #[serde(default)]
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment variables for the spawned program (stdio)
+    #[serde(default)]
+    pub env: std::collections::HashMap<String, String>,
+    /// Connection timeout in seconds (default: 60)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Agent ids allowed to use this server; empty allows every agent
+    #[serde(default)]
+    pub agents: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MemoryConfig {
+    /// Opt in to the cross-project global memory store under the app data directory
+    #[serde(default)]
+    pub global_enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitConfig {
+    /// Keep iteration workspaces as git repositories (needs `git` on PATH)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Deliver as a `cowork/<iteration>` branch when the project root is a
+    /// git repository, instead of copying files into it
+    #[serde(default = "default_true")]
+    pub deliver_as_branch: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            deliver_as_branch: true,
+        }
     }
 }
 
@@ -68,12 +153,76 @@ pub struct ExternalAgentConfig {
     pub env: Option<std::collections::HashMap<String, String>>,
     #[serde(default = "default_transport")]
     pub transport: String,
+    /// How tool calls the agent asks permission for are answered
+    #[serde(default)]
+    pub permissions: PermissionPolicy,
 }
 
 fn default_transport() -> String {
     "stdio".to_string()
 }
 
+/// Answer to an agent's permission request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionAction {
+    Allow,
+    Deny,
+    /// Ask the user through the interaction backend
+    Ask,
+}
+
+/// One permission rule; the first rule matching a tool call decides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionRule {
+    /// Tool kind: read, edit, delete, move, search, execute, think, fetch,
+    /// switch_mode or other. Unset or `*` matches any kind.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Glob matched against every path the tool call touches, relative to
+    /// the workspace (e.g. `src/**`). Unset matches any tool call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub action: PermissionAction,
+}
+
+impl PermissionRule {
+    fn new(kind: &str, action: PermissionAction) -> Self {
+        Self {
+            kind: Some(kind.to_string()),
+            path: None,
+            action,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionPolicy {
+    /// Action when no rule matches
+    #[serde(default = "default_permission_action")]
+    pub default: PermissionAction,
+    #[serde(default)]
+    pub rules: Vec<PermissionRule>,
+}
+
+fn default_permission_action() -> PermissionAction {
+    PermissionAction::Ask
+}
+
+impl Default for PermissionPolicy {
+    fn default() -> Self {
+        Self {
+            default: PermissionAction::Ask,
+            rules: vec![
+                PermissionRule::new("read", PermissionAction::Allow),
+                PermissionRule::new("search", PermissionAction::Allow),
+                PermissionRule::new("think", PermissionAction::Allow),
+                PermissionRule::new("edit", PermissionAction::Allow),
+            ],
+        }
+    }
+}
+
 pub type CodingAgentConfig = ExternalAgentConfig;
 
 impl Default for ExternalAgentConfig {
@@ -90,6 +239,7 @@ impl Default for ExternalAgentConfig {
             workspace_path: None,
             env: None,
             transport: "stdio".to_string(),
+            permissions: PermissionPolicy::default(),
         }
     }
 }
@@ -103,6 +253,10 @@ pub struct ModelConfig {
     pub coding_agent: ExternalAgentConfig,
     #[serde(default)]
     pub mcp: McpConfig,
+    #[serde(default)]
+    pub memory: MemoryConfig,
+    #[serde(default)]
+    pub git: GitConfig,
 }
 
 impl Default for ModelConfig {
@@ -116,6 +270,8 @@ impl Default for ModelConfig {
             embedding: EmbeddingConfig::default(),
             coding_agent: ExternalAgentConfig::default(),
             mcp: McpConfig::default(),
+            memory: MemoryConfig::default(),
+            git: GitConfig::default(),
         }
     }
 }
@@ -153,6 +309,8 @@ impl ModelConfig {
             embedding: EmbeddingConfig::default(),
             coding_agent: ExternalAgentConfig::default(),
             mcp: McpConfig::default(),
+            memory: MemoryConfig::default(),
+            git: GitConfig::default(),
         })
     }
 
@@ -193,9 +351,79 @@ pub fn get_app_data_dir() -> Result<PathBuf> {
     Ok(base_dir.join(APP_DIR_NAME))
 }
 
-pub fn get_config_path() -> Result<PathBuf> {
+/// Select the profile whose config file `load_config`/`save_config` use
+pub fn set_active_profile(profile: Option<&str>) -> Result<()> {
+    if let Some(name) = profile {
+        validate_profile_name(name)?;
+    }
+    if let Ok(mut active) = ACTIVE_PROFILE.write() {
+        *active = profile.map(str::to_string);
+    }
+    Ok(())
+}
+
+/// Selected profile (`--profile`, then `COWORK_PROFILE`); `None` means default
+pub fn active_profile() -> Option<String> {
+    let explicit = ACTIVE_PROFILE.read().ok().and_then(|p| p.clone());
+    explicit
+        .or_else(|| std::env::var(PROFILE_ENV).ok())
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty() && p != DEFAULT_PROFILE)
+}
+
+fn validate_profile_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!(
+            "Invalid profile name '{}': use letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Config file of a profile: `config.toml` for the default profile,
+/// `profiles/<name>.toml` otherwise
+pub fn profile_config_path(profile: Option<&str>) -> Result<PathBuf> {
     let app_dir = get_app_data_dir()?;
-    Ok(app_dir.join(CONFIG_FILENAME))
+    match profile.filter(|p| *p != DEFAULT_PROFILE) {
+        Some(name) => {
+            validate_profile_name(name)?;
+            Ok(app_dir.join(PROFILES_DIR).join(format!("{}.toml", name)))
+        }
+        None => Ok(app_dir.join(CONFIG_FILENAME)),
+    }
+}
+
+/// Profiles that have a config file, starting with `default`
+pub fn list_profiles() -> Result<Vec<String>> {
+    let app_dir = get_app_data_dir()?;
+    let mut profiles = Vec::new();
+    if app_dir.join(CONFIG_FILENAME).exists() {
+        profiles.push(DEFAULT_PROFILE.to_string());
+    }
+
+    let mut named: Vec<String> = std::fs::read_dir(app_dir.join(PROFILES_DIR))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let path = e.path();
+                    (path.extension().is_some_and(|ext| ext == "toml"))
+                        .then(|| path.file_stem()?.to_str().map(str::to_string))
+                        .flatten()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    named.sort();
+    profiles.extend(named);
+    Ok(profiles)
+}
+
+pub fn get_config_path() -> Result<PathBuf> {
+    profile_config_path(active_profile().as_deref())
 }
 
 pub fn ensure_config_dir() -> Result<PathBuf> {
@@ -227,6 +455,156 @@ pub fn save_config(config: &ModelConfig) -> Result<PathBuf> {
     Ok(config_path)
 }
 
+/// Settings whose values are masked unless asked for explicitly
+pub fn is_secret_key(key: &str) -> bool {
+    key.ends_with("api_key") || key.starts_with("coding_agent.env.")
+}
+
+fn config_table(config: &ModelConfig) -> Result<toml::Table> {
+    toml::Table::try_from(config).context("Failed to serialize config")
+}
+
+/// Every setting as a dotted key (`llm.model_name`) with its value
+pub fn config_entries(config: &ModelConfig) -> Result<Vec<(String, toml::Value)>> {
+    fn flatten(prefix: &str, table: &toml::Table, out: &mut Vec<(String, toml::Value)>) {
+        for (key, value) in table {
+            let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            match value {
+                toml::Value::Table(inner) => flatten(&key, inner, out),
+                other => out.push((key, other.clone())),
+            }
+        }
+    }
+
+    let mut entries = Vec::new();
+    flatten("", &config_table(config)?, &mut entries);
+    Ok(entries)
+}
+
+/// Value of a dotted key
+pub fn get_config_value(config: &ModelConfig, key: &str) -> Result<toml::Value> {
+    let table = config_table(config)?;
+    let mut value: Option<&toml::Value> = None;
+    for part in key.split('.') {
+        value = match value {
+            None => table.get(part),
+            Some(toml::Value::Table(t)) => t.get(part),
+            Some(_) => None,
+        };
+        if value.is_none() {
+            anyhow::bail!("Unknown or unset config key '{}'", key);
+        }
+    }
+    value
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Unknown or unset config key '{}'", key))
+}
+
+/// Parse `raw` for a setting: as the type of its current value when it has
+/// one, otherwise as a TOML literal, falling back to a plain string
+fn parse_config_value(raw: &str, current: Option<&toml::Value>) -> Result<toml::Value> {
+    let literal = || -> Option<toml::Value> {
+        toml::from_str::<toml::Table>(&format!("v = {}", raw))
+            .ok()
+            .and_then(|mut t| t.remove("v"))
+    };
+    Ok(match current {
+        Some(toml::Value::String(_)) => toml::Value::String(raw.to_string()),
+        Some(toml::Value::Boolean(_)) => toml::Value::Boolean(
+            raw.parse().with_context(|| format!("Expected true or false, got '{}'", raw))?,
+        ),
+        Some(toml::Value::Integer(_)) => toml::Value::Integer(
+            raw.parse().with_context(|| format!("Expected an integer, got '{}'", raw))?,
+        ),
+        Some(toml::Value::Array(_)) => match literal() {
+            Some(array @ toml::Value::Array(_)) => array,
+            // Comma-separated shorthand: `x,opencode-ai,acp`
+            _ => toml::Value::Array(
+                raw.split(',')
+                    .map(|s| toml::Value::String(s.trim().to_string()))
+                    .filter(|v| v.as_str() != Some(""))
+                    .collect(),
+            ),
+        },
+        _ => literal().unwrap_or_else(|| toml::Value::String(raw.to_string())),
+    })
+}
+
+/// Return `config` with the dotted `key` set to `raw`. Fails for keys the
+/// configuration does not have and for values of the wrong type.
+pub fn set_config_value(config: &ModelConfig, key: &str, raw: &str) -> Result<ModelConfig> {
+    let mut table = config_table(config)?;
+    let current = get_config_value(config, key).ok();
+    let value = parse_config_value(raw, current.as_ref())?;
+
+    let parts: Vec<&str> = key.split('.').collect();
+    let (last, parents) = parts
+        .split_last()
+        .ok_or_else(|| anyhow::anyhow!("Empty config key"))?;
+    let mut target = &mut table;
+    for part in parents {
+        target = target
+            .entry(part.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("'{}' is not a section", part))?;
+    }
+    target.insert(last.to_string(), value);
+
+    let updated: ModelConfig = toml::Value::Table(table)
+        .try_into()
+        .with_context(|| format!("Invalid value for '{}'", key))?;
+    // Unknown keys are dropped by deserialization; make sure it stuck
+    if get_config_value(&updated, key).is_err() {
+        anyhow::bail!("Unknown config key '{}'", key);
+    }
+    Ok(updated)
+}
+
+/// Check that the LLM endpoint answers and accepts the API key, by listing
+/// its models. Returns a short description of what was found.
+pub async fn check_llm_endpoint(config: &LlmConfig) -> Result<String> {
+    if config.api_base_url.is_empty() || config.model_name.is_empty() {
+        anyhow::bail!("llm.api_base_url and llm.model_name must be set");
+    }
+    let url = format!("{}/models", config.api_base_url.trim_end_matches('/'));
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()?;
+    let response = client
+        .get(&url)
+        .bearer_auth(&config.api_key)
+        .send()
+        .await
+        .with_context(|| format!("Cannot reach {}", config.api_base_url))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        anyhow::bail!("{} rejected the API key ({})", config.api_base_url, status);
+    }
+    if !status.is_success() {
+        // Some OpenAI-compatible servers do not implement /models
+        return Ok(format!("reachable, /models answered {}", status));
+    }
+
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let models: Vec<&str> = body
+        .get("data")
+        .and_then(|d| d.as_array())
+        .map(|d| d.iter().filter_map(|m| m.get("id").and_then(|id| id.as_str())).collect())
+        .unwrap_or_default();
+    if models.is_empty() || models.contains(&config.model_name.as_str()) {
+        Ok(format!("reachable, model '{}'", config.model_name))
+    } else {
+        anyhow::bail!(
+            "Model '{}' is not served by {} ({} models available)",
+            config.model_name,
+            config.api_base_url,
+            models.len()
+        )
+    }
+}
+
 pub fn create_llm_client(config: &LlmConfig) -> Result<Arc<dyn Llm>> {
     use crate::llm::rate_limiter::TokenBucketRateLimiter;
 
@@ -286,5 +664,35 @@ model_name = "gpt-5"
         let config: ModelConfig = toml::from_str(toml_content).unwrap();
         assert!(!config.coding_agent.enabled);
         assert!(config.embedding.api_base_url.is_empty());
+        assert!(!config.memory.global_enabled);
+    }
+
+    #[test]
+    fn test_config_get_set() {
+        let config = ModelConfig::default();
+
+        let config = set_config_value(&config, "llm.model_name", "gpt-5").unwrap();
+        assert_eq!(config.llm.model_name, "gpt-5");
+
+        let config = set_config_value(&config, "git.enabled", "false").unwrap();
+        assert!(!config.git.enabled);
+        assert!(set_config_value(&config, "git.enabled", "maybe").is_err());
+
+        let config = set_config_value(&config, "coding_agent.args", "x, opencode-ai,acp").unwrap();
+        assert_eq!(config.coding_agent.args, ["x", "opencode-ai", "acp"]);
+
+        let config = set_config_value(&config, "coding_agent.workspace_path", "/tmp/ws").unwrap();
+        assert_eq!(config.coding_agent.workspace_path.as_deref(), Some("/tmp/ws"));
+
+        assert!(set_config_value(&config, "llm.modle_name", "typo").is_err());
+        assert_eq!(
+            get_config_value(&config, "llm.model_name").unwrap().as_str(),
+            Some("gpt-5")
+        );
+        assert!(config_entries(&config)
+            .unwrap()
+            .iter()
+            .any(|(k, _)| k == "mcp.deepwiki_enabled"));
+        assert!(is_secret_key("llm.api_key"));
     }
 }
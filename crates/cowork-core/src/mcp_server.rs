@@ -0,0 +1,330 @@
+// MCP server exposing a Cowork project to other agents (`cowork mcp-serve`)
+//
+// Everything is read-only. Tools reuse the names, descriptions and schemas
+// of the agents' own query tools, with an optional `iteration_id` (the
+// current iteration by default). Resources expose the markdown artifacts of
+// every iteration, knowledge snapshots and project memory:
+//
+//   cowork://iterations/{id}/artifacts/{file}   text/markdown
+//   cowork://iterations/{id}/knowledge          application/json
+//   cowork://project/memory                     application/json
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use rmcp::model::{
+    AnnotateAble, CallToolRequestParams, CallToolResult, Content, Implementation, ListResourcesResult,
+    ListToolsResult, PaginatedRequestParams, RawResource, ReadResourceRequestParams, ReadResourceResult,
+    ResourceContents, ServerCapabilities, ServerInfo, Tool, ToolAnnotations,
+};
+use rmcp::service::RequestContext;
+use rmcp::{ErrorData as McpError, RoleServer, ServerHandler, ServiceExt};
+use serde_json::{json, Value};
+
+use crate::domain::Project;
+use crate::persistence::{
+    get_cowork_dir, load_design_spec, load_feature_list, load_requirements, set_iteration_id,
+    MemoryStore, ProjectStore, TRACKED_ARTIFACTS,
+};
+use crate::tools::{
+    get_plan, query_memory, GetDesignTool, GetPlanTool, GetRequirementsTool, LoadDesignDocTool, LoadIdeaTool,
+    LoadPlanDocTool, LoadPrdDocTool, QueryMemoryTool,
+};
+
+pub const SERVER_NAME: &str = "cowork-forge";
+
+const URI_PREFIX: &str = "cowork://";
+const PROJECT_MEMORY_URI: &str = "cowork://project/memory";
+
+const LIST_ITERATIONS: &str = "list_iterations";
+
+/// Artifact loader tools and the file each one reads
+const ARTIFACT_TOOLS: &[(&str, &str)] = &[
+    ("load_idea", "idea.md"),
+    ("load_prd_doc", "prd.md"),
+    ("load_design_doc", "design.md"),
+    ("load_plan_doc", "plan.md"),
+];
+
+/// Data loaders read the iteration selected with `set_iteration_id`, so
+/// calls are serialized
+static CALL_LOCK: Mutex<()> = Mutex::new(());
+
+/// Tool definitions, with `iteration_id` added to every schema
+pub fn tool_definitions() -> Vec<Tool> {
+    let agent_tools: Vec<Arc<dyn adk_core::Tool>> = vec![
+        Arc::new(GetRequirementsTool),
+        Arc::new(GetDesignTool),
+        Arc::new(GetPlanTool),
+        Arc::new(QueryMemoryTool::new(String::new())),
+        Arc::new(LoadIdeaTool),
+        Arc::new(LoadPrdDocTool),
+        Arc::new(LoadDesignDocTool),
+        Arc::new(LoadPlanDocTool),
+    ];
+
+    let mut tools = vec![Tool::new(
+        LIST_ITERATIONS,
+        "List the project's iterations with their status and completed stages.",
+        Arc::new(serde_json::Map::from_iter([("type".to_string(), json!("object")), ("properties".to_string(), json!({}))])),
+    )];
+    for tool in agent_tools {
+        let mut schema = tool
+            .parameters_schema()
+            .and_then(|s| s.as_object().cloned())
+            .unwrap_or_default();
+        if let Some(properties) = schema.entry("properties").or_insert_with(|| json!({})).as_object_mut() {
+            properties.insert(
+                "iteration_id".to_string(),
+                json!({
+                    "type": "string",
+                    "description": "Iteration to read (defaults to the current iteration)"
+                }),
+            );
+        }
+        tools.push(Tool::new(tool.name().to_string(), tool.description().to_string(), Arc::new(schema)));
+    }
+
+    let read_only = ToolAnnotations::from_raw(None, Some(true), Some(false), Some(true), Some(false));
+    tools.into_iter().map(|t| t.with_annotations(read_only.clone())).collect()
+}
+
+fn load_project() -> Result<Project> {
+    ProjectStore::new()
+        .load()?
+        .ok_or_else(|| anyhow::anyhow!("No project found. Run 'cowork init' first."))
+}
+
+/// Iteration a call reads: the requested one, or the current one
+fn select_iteration(project: &Project, args: &Value) -> Result<String> {
+    let requested = args.get("iteration_id").and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+    let iteration_id = match requested {
+        Some(id) => id.to_string(),
+        None => project
+            .current_iteration_id
+            .clone()
+            .or_else(|| project.iterations.last().map(|i| i.id.clone()))
+            .ok_or_else(|| anyhow::anyhow!("The project has no iterations"))?,
+    };
+    if !project.iterations.iter().any(|i| i.id == iteration_id) {
+        anyhow::bail!("Iteration '{}' not found", iteration_id);
+    }
+    Ok(iteration_id)
+}
+
+/// Run a tool. Artifact loaders return the markdown as a string.
+pub fn call_tool(name: &str, args: &Value) -> Result<Value> {
+    let _guard = CALL_LOCK.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+    let project = load_project()?;
+    if name == LIST_ITERATIONS {
+        return Ok(json!({
+            "current_iteration_id": project.current_iteration_id,
+            "iterations": project.iterations,
+        }));
+    }
+
+    let iteration_id = select_iteration(&project, args)?;
+    set_iteration_id(iteration_id.clone());
+    match name {
+        "get_requirements" => Ok(json!({
+            "requirements": load_requirements()?.requirements,
+            "features": load_feature_list()?.features,
+        })),
+        "get_design" => Ok(serde_json::to_value(load_design_spec()?)?),
+        "get_plan" => get_plan(args.get("status_filter").and_then(|v| v.as_str())),
+        "query_memory" => query_memory(args, &iteration_id),
+        _ => {
+            let (_, file) = ARTIFACT_TOOLS
+                .iter()
+                .find(|(tool, _)| *tool == name)
+                .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", name))?;
+            Ok(Value::String(read_artifact(&iteration_id, file)?))
+        }
+    }
+}
+
+fn read_artifact(iteration_id: &str, file: &str) -> Result<String> {
+    let path = get_cowork_dir()?
+        .join("iterations")
+        .join(iteration_id)
+        .join("artifacts")
+        .join(file);
+    std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {} of iteration '{}': {}", file, iteration_id, e))
+}
+
+/// Resources that currently exist
+pub fn list_resources() -> Result<Vec<RawResource>> {
+    let project = load_project()?;
+    let memory = MemoryStore::new().load_project_memory()?;
+    let mut resources = Vec::new();
+
+    for iteration in &project.iterations {
+        let dir = get_cowork_dir()?.join("iterations").join(&iteration.id).join("artifacts");
+        for (file, stage) in TRACKED_ARTIFACTS {
+            if dir.join(file).is_file() {
+                let mut resource = RawResource::new(
+                    format!("{}iterations/{}/artifacts/{}", URI_PREFIX, iteration.id, file),
+                    format!("{}/{}", iteration.id, file),
+                );
+                resource.description = Some(format!("{} artifact of iteration {} ({})", stage, iteration.number, iteration.title));
+                resource.mime_type = Some("text/markdown".to_string());
+                resources.push(resource);
+            }
+        }
+        if memory.iteration_knowledge.contains_key(&iteration.id) {
+            let mut resource = RawResource::new(
+                format!("{}iterations/{}/knowledge", URI_PREFIX, iteration.id),
+                format!("{}/knowledge", iteration.id),
+            );
+            resource.description = Some(format!("Knowledge snapshot of iteration {} ({})", iteration.number, iteration.title));
+            resource.mime_type = Some("application/json".to_string());
+            resources.push(resource);
+        }
+    }
+
+    let mut resource = RawResource::new(PROJECT_MEMORY_URI, "project/memory");
+    resource.description = Some("Project decisions and patterns".to_string());
+    resource.mime_type = Some("application/json".to_string());
+    resources.push(resource);
+    Ok(resources)
+}
+
+/// Read a resource, returning its MIME type and text
+pub fn read_resource(uri: &str) -> Result<(&'static str, String)> {
+    let project = load_project()?;
+    if uri == PROJECT_MEMORY_URI {
+        let memory = MemoryStore::new().load_project_memory()?;
+        let value = json!({
+            "decisions": memory.decisions,
+            "patterns": memory.patterns,
+            "context": memory.context,
+        });
+        return Ok(("application/json", serde_json::to_string_pretty(&value)?));
+    }
+
+    let path = uri
+        .strip_prefix(URI_PREFIX)
+        .and_then(|p| p.strip_prefix("iterations/"))
+        .ok_or_else(|| anyhow::anyhow!("Unknown resource: {}", uri))?;
+    let parts: Vec<&str> = path.split('/').collect();
+    let iteration_id = parts[0];
+    if !project.iterations.iter().any(|i| i.id == iteration_id) {
+        anyhow::bail!("Iteration '{}' not found", iteration_id);
+    }
+    match parts.as_slice() {
+        [_, "artifacts", file] if TRACKED_ARTIFACTS.iter().any(|(f, _)| f == file) => {
+            Ok(("text/markdown", read_artifact(iteration_id, file)?))
+        }
+        [_, "knowledge"] => {
+            let memory = MemoryStore::new().load_project_memory()?;
+            let knowledge = memory
+                .iteration_knowledge
+                .get(iteration_id)
+                .ok_or_else(|| anyhow::anyhow!("No knowledge snapshot for iteration '{}'", iteration_id))?;
+            Ok(("application/json", serde_json::to_string_pretty(knowledge)?))
+        }
+        _ => anyhow::bail!("Unknown resource: {}", uri),
+    }
+}
+
+/// MCP handler over the current project
+#[derive(Debug, Clone, Default)]
+pub struct CoworkMcpServer;
+
+impl ServerHandler for CoworkMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().enable_resources().build())
+            .with_server_info(Implementation::new(SERVER_NAME, env!("CARGO_PKG_VERSION")))
+            .with_instructions(
+                "Read-only access to a Cowork Forge project: requirements, design, plan, memory and \
+                 stage artifacts. Tools default to the current iteration; call list_iterations to pick another.",
+            )
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(tool_definitions()))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let args = Value::Object(request.arguments.unwrap_or_default());
+        let name = request.name.to_string();
+        let result = tokio::task::spawn_blocking(move || call_tool(&name, &args))
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(match result {
+            Ok(Value::String(text)) => CallToolResult::success(vec![Content::text(text)]),
+            Ok(value) => CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string()),
+            )]),
+            // Tool failures go back to the model rather than failing the request
+            Err(e) => CallToolResult::error(vec![Content::text(e.to_string())]),
+        })
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let resources = tokio::task::spawn_blocking(list_resources)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(ListResourcesResult::with_all_items(
+            resources.into_iter().map(|r| r.no_annotation()).collect(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let uri = request.uri;
+        let read_uri = uri.clone();
+        let (mime_type, text) = tokio::task::spawn_blocking(move || read_resource(&read_uri))
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .map_err(|e| McpError::resource_not_found(e.to_string(), Some(json!({ "uri": uri }))))?;
+        let contents = ResourceContents::text(text, uri).with_mime_type(mime_type);
+        Ok(ReadResourceResult::new(vec![contents]))
+    }
+}
+
+/// Serve over stdin/stdout until the client disconnects
+pub async fn serve_stdio() -> Result<()> {
+    let service = CoworkMcpServer
+        .serve(rmcp::transport::stdio())
+        .await
+        .map_err(|e| anyhow::anyhow!("MCP initialization failed: {}", e))?;
+    service.waiting().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_definitions_take_iteration_id() {
+        let tools = tool_definitions();
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_ref()).collect();
+        for name in [LIST_ITERATIONS, "get_requirements", "get_plan", "query_memory", "load_prd_doc"] {
+            assert!(names.contains(&name), "missing {}", name);
+        }
+        let plan = tools.iter().find(|t| t.name == "get_plan").unwrap();
+        let properties = plan.input_schema.get("properties").unwrap();
+        assert!(properties.get("status_filter").is_some());
+        assert!(properties.get("iteration_id").is_some());
+        assert!(tools.iter().all(|t| t.annotations.as_ref().and_then(|a| a.read_only_hint) == Some(true)));
+    }
+}
@@ -0,0 +1,301 @@
+// Numbered revisions of the markdown artifacts of an iteration
+//
+// Every save of idea.md, prd.md, ... goes through `ArtifactHistory::write`,
+// which snapshots the new content as
+// `iterations/{id}/history/{artifact}/{NNN}.md` and indexes it in
+// `revisions.json` with its author and the feedback that triggered it. If the
+// file on disk no longer matches the latest revision (someone edited it by
+// hand), that content is recorded as a `human` revision first, so nothing is
+// lost when an agent overwrites it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::get_cowork_dir;
+
+/// Author recorded for hand edits and restores
+pub const HUMAN_AUTHOR: &str = "human";
+
+/// Author recorded for content that existed before history was kept
+const UNKNOWN_AUTHOR: &str = "unknown";
+
+/// Artifacts that keep a revision history, with the stage that writes them
+pub const TRACKED_ARTIFACTS: &[(&str, &str)] = &[
+    ("idea.md", "idea"),
+    ("prd.md", "prd"),
+    ("design.md", "design"),
+    ("plan.md", "plan"),
+    ("check_report.md", "check"),
+    ("delivery_report.md", "delivery"),
+];
+
+/// Feedback a human gave at a stage gate, waiting for the stage's next save
+static PENDING_FEEDBACK: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+/// Remember human feedback for `stage`; the next revision of that stage's
+/// artifact is attributed to it
+pub fn set_pending_revision_feedback(stage: &str, feedback: &str) {
+    if let Ok(mut pending) = PENDING_FEEDBACK.lock() {
+        *pending = Some((stage.to_string(), feedback.to_string()));
+    }
+}
+
+/// Take the pending human feedback if it was given for `stage`
+pub fn take_pending_revision_feedback(stage: &str) -> Option<String> {
+    let mut pending = PENDING_FEEDBACK.lock().ok()?;
+    match pending.as_ref() {
+        Some((s, _)) if s == stage => pending.take().map(|(_, f)| f),
+        _ => None,
+    }
+}
+
+/// Stage that writes `filename`
+pub fn artifact_stage(filename: &str) -> Option<&'static str> {
+    TRACKED_ARTIFACTS
+        .iter()
+        .find(|(f, _)| *f == filename)
+        .map(|(_, stage)| *stage)
+}
+
+/// Resolve a user-supplied artifact name (`prd`, `prd.md`, `requirements`,
+/// `check`, ...) to its file name
+pub fn resolve_artifact_name(name: &str) -> Result<&'static str> {
+    let name = name.trim().trim_end_matches(".md");
+    let name = match name {
+        "requirements" => "prd",
+        "check_report" => "check",
+        "delivery_report" => "delivery",
+        other => other,
+    };
+    TRACKED_ARTIFACTS
+        .iter()
+        .find(|(_, stage)| *stage == name)
+        .map(|(f, _)| *f)
+        .ok_or_else(|| {
+            let valid: Vec<&str> = TRACKED_ARTIFACTS.iter().map(|(_, s)| *s).collect();
+            anyhow::anyhow!("Unknown artifact '{}'. Valid artifacts: {}", name, valid.join(", "))
+        })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactRevision {
+    pub revision: u32,
+    /// Agent display name, or `human`
+    pub author: String,
+    /// Feedback that triggered this revision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<String>,
+    /// Set when the revision restores an earlier one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restored_from: Option<u32>,
+    pub created_at: DateTime<Utc>,
+    pub size: usize,
+}
+
+/// Revision history of one artifact of one iteration
+pub struct ArtifactHistory {
+    filename: String,
+    artifact_file: PathBuf,
+    history_dir: PathBuf,
+}
+
+impl ArtifactHistory {
+    /// History of `filename` (e.g. `prd.md`) inside an iteration directory
+    pub fn new(iteration_dir: &Path, filename: &str) -> Self {
+        Self {
+            filename: filename.to_string(),
+            artifact_file: iteration_dir.join("artifacts").join(filename),
+            history_dir: iteration_dir.join("history").join(filename),
+        }
+    }
+
+    /// History of `filename` of the iteration with ID `iteration_id`
+    pub fn open(iteration_id: &str, filename: &str) -> Result<Self> {
+        let iteration_dir = get_cowork_dir()?.join("iterations").join(iteration_id);
+        if !iteration_dir.exists() {
+            anyhow::bail!("Iteration '{}' not found", iteration_id);
+        }
+        Ok(Self::new(&iteration_dir, filename))
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.history_dir.join("revisions.json")
+    }
+
+    fn revision_path(&self, revision: u32) -> PathBuf {
+        self.history_dir.join(format!("{:03}.md", revision))
+    }
+
+    /// All revisions, oldest first
+    pub fn revisions(&self) -> Result<Vec<ArtifactRevision>> {
+        let path = self.index_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))
+    }
+
+    pub fn latest(&self) -> Result<Option<ArtifactRevision>> {
+        Ok(self.revisions()?.pop())
+    }
+
+    /// Content of a revision
+    pub fn load(&self, revision: u32) -> Result<String> {
+        let path = self.revision_path(revision);
+        if !path.exists() {
+            anyhow::bail!("{} has no revision {}", self.filename, revision);
+        }
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))
+    }
+
+    /// Current content of the artifact file, if any
+    pub fn current(&self) -> Option<String> {
+        fs::read_to_string(&self.artifact_file).ok()
+    }
+
+    /// Write `content` to the artifact file and record it as a new revision.
+    /// Returns `None` when the content equals the latest revision.
+    pub fn write(&self, content: &str, author: &str, feedback: Option<String>) -> Result<Option<ArtifactRevision>> {
+        self.write_revision(content, author, feedback, None)
+    }
+
+    /// Make an earlier revision current again, as a new `human` revision
+    pub fn restore(&self, revision: u32) -> Result<ArtifactRevision> {
+        let content = self.load(revision)?;
+        let restored = self.write_revision(&content, HUMAN_AUTHOR, None, Some(revision))?;
+        // Restoring the latest revision over an unedited file records nothing
+        match restored {
+            Some(r) => Ok(r),
+            None => self
+                .latest()?
+                .ok_or_else(|| anyhow::anyhow!("{} has no revisions", self.filename)),
+        }
+    }
+
+    fn write_revision(
+        &self,
+        content: &str,
+        author: &str,
+        feedback: Option<String>,
+        restored_from: Option<u32>,
+    ) -> Result<Option<ArtifactRevision>> {
+        let mut revisions = self.revisions()?;
+
+        // Keep hand edits (or pre-history content) before overwriting them
+        if let Some(on_disk) = self.current() {
+            let recorded = match revisions.last() {
+                Some(last) => self.load(last.revision).ok(),
+                None => None,
+            };
+            if recorded.as_deref() != Some(on_disk.as_str()) && on_disk != content {
+                let author = if revisions.is_empty() { UNKNOWN_AUTHOR } else { HUMAN_AUTHOR };
+                self.append(&mut revisions, &on_disk, author, None, None)?;
+            }
+        }
+
+        let unchanged = match revisions.last() {
+            Some(last) => self.load(last.revision).ok().as_deref() == Some(content),
+            None => false,
+        };
+
+        if let Some(parent) = self.artifact_file.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+        fs::write(&self.artifact_file, content)
+            .with_context(|| format!("Failed to write {:?}", self.artifact_file))?;
+
+        if unchanged {
+            self.save_index(&revisions)?;
+            return Ok(None);
+        }
+        let revision = self.append(&mut revisions, content, author, feedback, restored_from)?;
+        Ok(Some(revision))
+    }
+
+    fn append(
+        &self,
+        revisions: &mut Vec<ArtifactRevision>,
+        content: &str,
+        author: &str,
+        feedback: Option<String>,
+        restored_from: Option<u32>,
+    ) -> Result<ArtifactRevision> {
+        let revision = ArtifactRevision {
+            revision: revisions.last().map(|r| r.revision + 1).unwrap_or(1),
+            author: author.to_string(),
+            feedback,
+            restored_from,
+            created_at: Utc::now(),
+            size: content.len(),
+        };
+
+        fs::create_dir_all(&self.history_dir)
+            .with_context(|| format!("Failed to create directory {:?}", self.history_dir))?;
+        let path = self.revision_path(revision.revision);
+        fs::write(&path, content).with_context(|| format!("Failed to write {:?}", path))?;
+
+        revisions.push(revision.clone());
+        self.save_index(revisions)?;
+        Ok(revision)
+    }
+
+    fn save_index(&self, revisions: &[ArtifactRevision]) -> Result<()> {
+        if revisions.is_empty() {
+            return Ok(());
+        }
+        let path = self.index_path();
+        fs::write(&path, serde_json::to_string_pretty(revisions)?)
+            .with_context(|| format!("Failed to write {:?}", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revisions_keep_hand_edits_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = ArtifactHistory::new(dir.path(), "prd.md");
+
+        let first = history.write("# PRD v1", "PRD Actor", None).unwrap().unwrap();
+        assert_eq!(first.revision, 1);
+        assert!(history.write("# PRD v1", "PRD Actor", None).unwrap().is_none());
+
+        // A hand edit is recorded before the agent overwrites it
+        fs::write(dir.path().join("artifacts/prd.md"), "# PRD v1 (edited)").unwrap();
+        let second = history
+            .write("# PRD v2", "PRD Actor", Some("Add acceptance criteria".to_string()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.revision, 3);
+
+        let revisions = history.revisions().unwrap();
+        let authors: Vec<&str> = revisions.iter().map(|r| r.author.as_str()).collect();
+        assert_eq!(authors, ["PRD Actor", HUMAN_AUTHOR, "PRD Actor"]);
+        assert_eq!(history.load(2).unwrap(), "# PRD v1 (edited)");
+        assert_eq!(revisions[2].feedback.as_deref(), Some("Add acceptance criteria"));
+
+        let restored = history.restore(1).unwrap();
+        assert_eq!((restored.revision, restored.restored_from), (4, Some(1)));
+        assert_eq!(restored.author, HUMAN_AUTHOR);
+        assert_eq!(history.current().as_deref(), Some("# PRD v1"));
+    }
+
+    #[test]
+    fn test_resolve_artifact_name() {
+        assert_eq!(resolve_artifact_name("prd").unwrap(), "prd.md");
+        assert_eq!(resolve_artifact_name("requirements").unwrap(), "prd.md");
+        assert_eq!(resolve_artifact_name("check_report.md").unwrap(), "check_report.md");
+        assert!(resolve_artifact_name("coding").is_err());
+    }
+}
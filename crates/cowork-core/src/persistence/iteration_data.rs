@@ -2,6 +2,7 @@
 // Handles data, artifacts, and session files within .cowork-v2/iterations/{iteration_id}/
 
 use crate::data::*;
+use super::artifact_history::{artifact_stage, take_pending_revision_feedback, ArtifactHistory};
 use super::get_cowork_dir;
 use anyhow::{Context, Result};
 use std::fs;
@@ -63,6 +64,32 @@ fn session_path(filename: &str) -> Result<PathBuf> {
     Ok(get_iteration_dir()?.join("session").join(filename))
 }
 
+/// Helper to get log file path (iteration-specific)
+pub fn iteration_log_path(filename: &str) -> Result<PathBuf> {
+    Ok(get_iteration_dir()?.join("logs").join(filename))
+}
+
+/// Load a data file (`requirements.json`, ...) of a specific iteration,
+/// independent of the current iteration ID. Returns `None` if it does not exist.
+pub fn load_iteration_data_file<T: serde::de::DeserializeOwned>(
+    iteration_id: &str,
+    filename: &str,
+) -> Result<Option<T>> {
+    let path = get_cowork_dir()?
+        .join("iterations")
+        .join(iteration_id)
+        .join("data")
+        .join(filename);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+    let data = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {} of iteration {}", filename, iteration_id))?;
+    Ok(Some(data))
+}
+
 // ============================================================================
 // Requirements
 // ============================================================================
@@ -298,82 +325,69 @@ pub fn load_idea() -> Result<String> {
     Ok(content)
 }
 
-pub fn save_idea(content: &str) -> Result<()> {
-    let path = artifact_path("idea.md")?;
+/// Write a markdown artifact and record it as a new revision, attributed to
+/// the current agent and the feedback given for its stage since the last one
+fn save_artifact_revision(filename: &str, content: &str) -> Result<()> {
+    let history = ArtifactHistory::new(&get_iteration_dir()?, filename);
 
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory {:?}", parent))?;
+    let mut author = crate::tools::current_agent_name();
+    if author.is_empty() {
+        author = "agent".to_string();
     }
 
-    fs::write(&path, content)?;
+    let stage = artifact_stage(filename).unwrap_or_default();
+    let feedback = match take_pending_revision_feedback(stage) {
+        Some(feedback) => Some(feedback),
+        None => {
+            let since = history.latest()?.map(|r| r.created_at);
+            load_feedback_history()?
+                .feedbacks
+                .into_iter()
+                .rev()
+                .find(|f| f.stage == stage && since.is_none_or(|t| f.timestamp > t))
+                .map(|f| f.details)
+        }
+    };
+
+    history.write(content, &author, feedback)?;
     Ok(())
 }
 
-pub fn save_plan_doc(content: &str) -> Result<()> {
-    let path = artifact_path("plan.md")?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory {:?}", parent))?;
-    }
+pub fn save_idea(content: &str) -> Result<()> {
+    save_artifact_revision("idea.md", content)
+}
 
-    fs::write(&path, content)?;
-    Ok(())
+pub fn save_plan_doc(content: &str) -> Result<()> {
+    save_artifact_revision("plan.md", content)
 }
 
 pub fn save_prd_doc(content: &str) -> Result<()> {
-    let path = artifact_path("prd.md")?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory {:?}", parent))?;
-    }
-
-    fs::write(&path, content)?;
-    Ok(())
+    save_artifact_revision("prd.md", content)
 }
 
 pub fn save_design_doc(content: &str) -> Result<()> {
-    let path = artifact_path("design.md")?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory {:?}", parent))?;
-    }
-
-    fs::write(&path, content)?;
-    Ok(())
+    save_artifact_revision("design.md", content)
 }
 
 pub fn save_delivery_report(content: &str) -> Result<()> {
-    let path = artifact_path("delivery_report.md")?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create directory {:?}", parent))?;
-    }
-
-    fs::write(&path, content)?;
-    Ok(())
+    save_artifact_revision("delivery_report.md", content)
 }
 
 pub fn save_check_report(content: &str) -> Result<()> {
-    let path = artifact_path("check_report.md")?;
+    save_artifact_revision("check_report.md", content)
+}
+
+/// Save an exported traceability matrix as `artifacts/traceability.{extension}`
+pub fn save_traceability_matrix(extension: &str, content: &str) -> Result<PathBuf> {
+    let path = artifact_path(&format!("traceability.{}", extension))?;
 
-    // Ensure parent directory exists
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create directory {:?}", parent))?;
     }
 
     fs::write(&path, content)?;
-    Ok(())
+    Ok(path)
 }
 
 // ============================================================================
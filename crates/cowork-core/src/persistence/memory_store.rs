@@ -1,8 +1,18 @@
 use std::path::PathBuf;
 
-use crate::domain::{IterationMemory, MemoryQuery, MemoryQueryResult, ProjectMemory};
-
-use super::get_cowork_dir;
+use crate::code_index::CodeIndex;
+use crate::domain::{
+    Decision, GlobalMemory, IterationMemory, MemoryQuery, MemoryQueryResult, Pattern, ProjectMemory,
+};
+
+use super::{get_cowork_dir, ProjectStore};
+
+/// Whether the cross-project global memory is enabled (`[memory] global_enabled` in config.toml)
+pub fn is_global_memory_enabled() -> bool {
+    crate::llm::config::load_config()
+        .map(|c| c.memory.global_enabled)
+        .unwrap_or(false)
+}
 
 /// Memory store for persistence
 pub struct MemoryStore;
@@ -123,6 +133,108 @@ impl MemoryStore {
         Ok(promoted_count)
     }
 
+    // Global Memory
+
+    /// Load global (cross-project) memory
+    pub fn load_global_memory(&self) -> anyhow::Result<GlobalMemory> {
+        let path = self.global_memory_path()?;
+        if !path.exists() {
+            return Ok(GlobalMemory::new());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let memory: GlobalMemory = serde_json::from_str(&content)?;
+        Ok(memory)
+    }
+
+    /// Save global (cross-project) memory
+    pub fn save_global_memory(&self, memory: &GlobalMemory) -> anyhow::Result<()> {
+        let path = self.global_memory_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(memory)?;
+        std::fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// Copy a project pattern into global memory.
+    /// A global pattern with the same name is replaced.
+    pub fn promote_pattern_to_global(&self, pattern_id: &str) -> anyhow::Result<Pattern> {
+        let project_memory = self.load_project_memory()?;
+        let mut pattern = project_memory
+            .patterns
+            .iter()
+            .find(|p| p.id == pattern_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Pattern not found in project memory: {}", pattern_id))?;
+        pattern.source_project = Some(self.current_project_name());
+
+        let mut global = self.load_global_memory()?;
+        global.upsert_pattern(pattern.clone());
+        self.save_global_memory(&global)?;
+        Ok(pattern)
+    }
+
+    /// Copy a project decision into global memory.
+    /// A global decision with the same title is replaced.
+    pub fn promote_decision_to_global(&self, decision_id: &str) -> anyhow::Result<Decision> {
+        let project_memory = self.load_project_memory()?;
+        let mut decision = project_memory
+            .decisions
+            .iter()
+            .find(|d| d.id == decision_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Decision not found in project memory: {}", decision_id))?;
+        decision.source_project = Some(self.current_project_name());
+
+        let mut global = self.load_global_memory()?;
+        global.upsert_decision(decision.clone());
+        self.save_global_memory(&global)?;
+        Ok(decision)
+    }
+
+    /// Remove a decision or pattern from global memory
+    pub fn remove_global_entry(&self, id: &str) -> anyhow::Result<bool> {
+        let mut global = self.load_global_memory()?;
+        let removed = global.remove(id);
+        if removed {
+            self.save_global_memory(&global)?;
+        }
+        Ok(removed)
+    }
+
+    fn current_project_name(&self) -> String {
+        ProjectStore::new()
+            .load()
+            .ok()
+            .flatten()
+            .map(|p| p.name)
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    // Code Index
+
+    /// Load the code structure index stored for an iteration
+    pub fn load_code_index(&self, iteration_id: &str) -> anyhow::Result<Option<CodeIndex>> {
+        let path = self.code_index_path(iteration_id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Save the code structure index of an iteration
+    pub fn save_code_index(&self, index: &CodeIndex) -> anyhow::Result<()> {
+        let path = self.code_index_path(&index.iteration_id)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(index)?;
+        std::fs::write(&path, content)?;
+        Ok(())
+    }
+
     // Query
 
     /// Query memory based on scope and type
@@ -153,6 +265,18 @@ impl MemoryStore {
                     let iter_memory = self.load_iteration_memory(iter_id)?;
                     self.apply_query_to_iteration(&iter_memory, query, &mut result);
                 }
+
+                // Global memory comes last so project-specific entries take precedence
+                if is_global_memory_enabled() {
+                    let global_memory = self.load_global_memory()?;
+                    self.apply_query_to_global(&global_memory, query, &mut result);
+                }
+            }
+            crate::domain::MemoryScope::Global => {
+                if is_global_memory_enabled() {
+                    let global_memory = self.load_global_memory()?;
+                    self.apply_query_to_global(&global_memory, query, &mut result);
+                }
             }
         }
 
@@ -200,6 +324,30 @@ impl MemoryStore {
         }
     }
 
+    fn apply_query_to_global(&self, memory: &GlobalMemory, query: &MemoryQuery, result: &mut MemoryQueryResult) {
+        use crate::domain::MemoryQueryType;
+
+        if matches!(query.query_type, MemoryQueryType::Decisions | MemoryQueryType::All) {
+            if query.keywords.is_empty() {
+                result.decisions.extend(memory.decisions.clone());
+            } else {
+                for keyword in &query.keywords {
+                    result.decisions.extend(memory.query_decisions(keyword).into_iter().cloned());
+                }
+            }
+        }
+
+        if matches!(query.query_type, MemoryQueryType::Patterns | MemoryQueryType::All) {
+            if query.keywords.is_empty() {
+                result.patterns.extend(memory.patterns.clone());
+            } else {
+                for keyword in &query.keywords {
+                    result.patterns.extend(memory.query_patterns(keyword).into_iter().cloned());
+                }
+            }
+        }
+    }
+
     fn apply_query_to_iteration(&self, memory: &IterationMemory, query: &MemoryQuery, result: &mut MemoryQueryResult) {
         use crate::domain::MemoryQueryType;
 
@@ -218,6 +366,14 @@ impl MemoryStore {
     fn iteration_memory_path(&self, iteration_id: &str) -> anyhow::Result<PathBuf> {
         Ok(get_cowork_dir()?.join("memory/iterations").join(format!("{}.json", iteration_id)))
     }
+
+    fn global_memory_path(&self) -> anyhow::Result<PathBuf> {
+        Ok(crate::llm::config::get_app_data_dir()?.join("memory/global.json"))
+    }
+
+    fn code_index_path(&self, iteration_id: &str) -> anyhow::Result<PathBuf> {
+        Ok(get_cowork_dir()?.join("memory/code_index").join(format!("{}.json", iteration_id)))
+    }
 }
 
 impl Default for MemoryStore {
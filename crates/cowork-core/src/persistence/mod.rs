@@ -6,11 +6,13 @@ pub mod iteration_store;
 pub mod memory_store;
 pub mod project_store;
 pub mod iteration_data;
+pub mod artifact_history;
 
 pub use iteration_store::*;
 pub use memory_store::*;
 pub use project_store::*;
 pub use iteration_data::*;
+pub use artifact_history::*;
 
 const COWORK_DIR: &str = ".cowork-v2";
 
@@ -1,14 +1,60 @@
 // Knowledge generation and injection logic for iteration executor
 
 use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::domain::{Importance, Iteration, IterationStatus};
+use crate::domain::{Importance, Iteration, IterationStatus, KnowledgeLineageEntry};
 use crate::persistence::IterationStore;
 use crate::pipeline::{PipelineContext, stage_executor::{SimpleInvocationContext, extract_text_from_event}};
 use adk_core::{Content, Llm};
 
+const SUMMARY_DOC_TYPES: [&str; 4] = ["idea", "prd", "design", "plan"];
+const SUMMARY_MANIFEST_FILE: &str = "manifest.json";
+
+/// Summary manifest - content hashes of the documents each summary was built from
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SummaryManifest {
+    pub documents: HashMap<String, SummaryManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryManifestEntry {
+    /// SHA-256 of the source document content
+    pub content_hash: String,
+    /// Iteration in which this summary was actually generated
+    pub source_iteration_id: String,
+    pub summarized_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Load the summary manifest of an iteration (empty if none was written yet)
+pub fn load_summary_manifest(
+    iteration_store: &IterationStore,
+    iteration_id: &str,
+) -> anyhow::Result<SummaryManifest> {
+    let path = iteration_store
+        .iteration_path(iteration_id)?
+        .join("summaries")
+        .join(SUMMARY_MANIFEST_FILE);
+
+    if !path.exists() {
+        return Ok(SummaryManifest::default());
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
 /// Generate summaries for iteration documents using LLM
+///
+/// For evolution iterations only documents whose content hash changed since
+/// the base iteration are re-summarized; unchanged summaries are carried forward.
 pub async fn generate_document_summaries(
     iteration_store: &IterationStore,
     iteration: &Iteration,
@@ -25,9 +71,22 @@ pub async fn generate_document_summaries(
 
     std::fs::create_dir_all(&summaries_dir)?;
 
-    let doc_types = vec!["idea", "prd", "design", "plan"];
+    // Base iteration summaries (evolution iterations only)
+    let base = match iteration.base_iteration_id.as_ref() {
+        Some(base_id) => match load_summary_manifest(iteration_store, base_id) {
+            Ok(manifest) => Some((iteration_store.iteration_path(base_id)?.join("summaries"), manifest)),
+            Err(e) => {
+                tracing::warn!("[Executor] Failed to load base summary manifest for {}: {}", base_id, e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    let mut manifest = SummaryManifest::default();
+    let mut summarized_count = 0;
 
-    for (idx, doc_type) in doc_types.iter().enumerate() {
+    for doc_type in SUMMARY_DOC_TYPES {
         let doc_path = artifacts_dir.join(format!("{}.md", doc_type));
 
         if !doc_path.exists() {
@@ -36,6 +95,28 @@ pub async fn generate_document_summaries(
         }
 
         let content = std::fs::read_to_string(&doc_path)?;
+        let hash = content_hash(&content);
+        let summary_path = summaries_dir.join(format!("{}.md", doc_type));
+
+        // Carry forward the base summary if the document did not change
+        if let Some((base_summaries_dir, base_manifest)) = &base {
+            if let Some(entry) = base_manifest.documents.get(doc_type) {
+                let base_summary_path = base_summaries_dir.join(format!("{}.md", doc_type));
+                if entry.content_hash == hash && base_summary_path.exists() {
+                    std::fs::copy(&base_summary_path, &summary_path)?;
+                    manifest.documents.insert(doc_type.to_string(), entry.clone());
+                    tracing::info!("[Executor] {} unchanged since base iteration, summary carried forward", doc_type);
+                    continue;
+                }
+            }
+        }
+
+        // Add delay between document summaries to avoid rate limiting
+        if summarized_count > 0 {
+            tracing::debug!("[Executor] Waiting 2 seconds before processing next document...");
+            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        }
+        summarized_count += 1;
 
         let summary_agent = crate::agents::create_summary_agent(
             model.clone(),
@@ -82,19 +163,108 @@ pub async fn generate_document_summaries(
         }
 
         let summary = extract_summary_from_response(&generated_text);
-        let summary_path = summaries_dir.join(format!("{}.md", doc_type));
         std::fs::write(&summary_path, summary)?;
 
+        manifest.documents.insert(
+            doc_type.to_string(),
+            SummaryManifestEntry {
+                content_hash: hash,
+                source_iteration_id: iteration.id.clone(),
+                summarized_at: chrono::Utc::now(),
+            },
+        );
+
         tracing::info!("[Executor] Generated summary for {}", doc_type);
+    }
 
-        // Add delay between document summaries to avoid rate limiting
-        if idx < doc_types.len() - 1 {
-            tracing::debug!("[Executor] Waiting 2 seconds before processing next document...");
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+    std::fs::write(
+        summaries_dir.join(SUMMARY_MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    tracing::info!(
+        "[Executor] Document summaries generation completed ({} summarized, {} carried forward)",
+        summarized_count,
+        manifest.documents.len().saturating_sub(summarized_count)
+    );
+    Ok(())
+}
+
+/// Apply the summary manifest to a saved knowledge snapshot: record document
+/// hashes, carry forward unchanged summaries from the base knowledge and
+/// append a lineage entry to project memory.
+fn finalize_knowledge_lineage(
+    iteration_store: &IterationStore,
+    iteration: &Iteration,
+) -> anyhow::Result<()> {
+    let manifest = load_summary_manifest(iteration_store, &iteration.id)?;
+
+    let memory_store = crate::persistence::MemoryStore::new();
+    let mut project_memory = memory_store.load_project_memory()?;
+
+    let Some(mut knowledge) = project_memory.get_iteration_knowledge(&iteration.id).cloned() else {
+        return Ok(());
+    };
+    let base_knowledge = iteration
+        .base_iteration_id
+        .as_deref()
+        .and_then(|id| project_memory.get_iteration_knowledge(id))
+        .cloned();
+
+    let mut changed_documents = Vec::new();
+    let mut carried_forward = Vec::new();
+
+    for doc_type in SUMMARY_DOC_TYPES {
+        let Some(entry) = manifest.documents.get(doc_type) else {
+            continue;
+        };
+        knowledge
+            .document_hashes
+            .insert(doc_type.to_string(), entry.content_hash.clone());
+
+        if entry.source_iteration_id == iteration.id {
+            changed_documents.push(doc_type.to_string());
+            continue;
+        }
+
+        carried_forward.push(doc_type.to_string());
+        if let Some(base) = &base_knowledge {
+            let (target, source) = match doc_type {
+                "idea" => (&mut knowledge.idea_summary, &base.idea_summary),
+                "prd" => (&mut knowledge.prd_summary, &base.prd_summary),
+                "design" => (&mut knowledge.design_summary, &base.design_summary),
+                _ => (&mut knowledge.plan_summary, &base.plan_summary),
+            };
+            if !source.is_empty() {
+                *target = source.clone();
+            }
         }
     }
 
-    tracing::info!("[Executor] Document summaries generation completed");
+    knowledge.base_iteration_id = iteration.base_iteration_id.clone();
+    knowledge.carried_forward = carried_forward.clone();
+
+    // Attach the deterministic code outline to the LLM's structure description
+    if let Ok(Some(index)) = memory_store.load_code_index(&iteration.id) {
+        let overview = index.overview(30);
+        knowledge.code_structure = if knowledge.code_structure.trim().is_empty() {
+            overview
+        } else {
+            format!("{}\n\n## Code Index\n{}", knowledge.code_structure.trim(), overview)
+        };
+    }
+
+    project_memory.record_knowledge_lineage(KnowledgeLineageEntry {
+        iteration_id: iteration.id.clone(),
+        iteration_number: iteration.number,
+        base_iteration_id: iteration.base_iteration_id.clone(),
+        changed_documents,
+        carried_forward,
+        created_at: knowledge.created_at,
+    });
+    project_memory.save_iteration_knowledge(knowledge);
+    memory_store.save_project_memory(&project_memory)?;
+
     Ok(())
 }
 
@@ -123,6 +293,16 @@ pub async fn generate_iteration_knowledge(
         return Ok(());
     }
 
+    // Build the deterministic code index first so the agent can query it
+    match crate::tools::load_or_build_code_index(&iteration.id) {
+        Ok(index) => tracing::info!(
+            "[Executor] Code index ready: {} files, {} edges",
+            index.files.len(),
+            index.edges.len()
+        ),
+        Err(e) => tracing::warn!("[Executor] Failed to build code index: {}", e),
+    }
+
     tracing::info!("[Executor] Creating knowledge generation agent...");
 
     let knowledge_agent = crate::agents::create_knowledge_generation_agent(
@@ -135,12 +315,32 @@ pub async fn generate_iteration_knowledge(
     tracing::info!("[Executor] Setting iteration ID for tool context...");
     crate::persistence::set_iteration_id(iteration.id.clone());
 
-    let prompt = "Please analyze this iteration and generate a comprehensive knowledge snapshot. Use the available tools to load document summaries, examine the codebase structure, and extract meaningful knowledge.";
+    let mut prompt = "Please analyze this iteration and generate a comprehensive knowledge snapshot. Use the available tools to load document summaries, examine the codebase structure, and extract meaningful knowledge.".to_string();
+
+    // Point the agent at what actually changed since the base iteration
+    if iteration.base_iteration_id.is_some() {
+        let manifest = load_summary_manifest(iteration_store, &iteration.id).unwrap_or_default();
+        let unchanged: Vec<&str> = SUMMARY_DOC_TYPES
+            .into_iter()
+            .filter(|doc| {
+                manifest
+                    .documents
+                    .get(*doc)
+                    .is_some_and(|e| e.source_iteration_id != iteration.id)
+            })
+            .collect();
+        if !unchanged.is_empty() {
+            prompt.push_str(&format!(
+                "\n\nThe following documents are unchanged since the base iteration: {}. Their summaries are carried forward automatically; load the base knowledge and focus on what changed.",
+                unchanged.join(", ")
+            ));
+        }
+    }
 
     tracing::info!("[Executor] Creating invocation context...");
 
     let iteration_dir = iteration_store.iteration_path(&iteration.id)?;
-    let ctx_content = Content::new("user").with_text(prompt);
+    let ctx_content = Content::new("user").with_text(&prompt);
     let dummy_project = crate::domain::Project::new("temp");
     let invocation_ctx = Arc::new(SimpleInvocationContext::new(
         &PipelineContext::new(dummy_project, iteration.clone(), iteration_dir.clone()),
@@ -188,6 +388,9 @@ pub async fn generate_iteration_knowledge(
         .get_iteration_knowledge(&iteration.id)
         .is_some()
     {
+        if let Err(e) = finalize_knowledge_lineage(iteration_store, iteration) {
+            tracing::warn!("[Executor] Failed to record knowledge lineage: {}", e);
+        }
         tracing::info!("[Executor] Iteration knowledge generated and saved successfully");
     } else {
         tracing::warn!(
@@ -221,12 +424,20 @@ pub async fn inject_project_knowledge(
     let mut iter_memory = memory_store.load_iteration_memory(&iteration.id)?;
 
     if let Some(knowledge) = base_knowledge {
+        let lineage = project_memory
+            .knowledge_lineage(base_iteration_id)
+            .iter()
+            .map(|e| format!("#{}", e.iteration_number))
+            .collect::<Vec<_>>()
+            .join(" → ");
+
         // Use knowledge summary (preferred)
         iter_memory.add_insight(
             "project_context",
             format!(
                 "## Base Iteration Knowledge (#{})\n\n\
                 **Iteration ID**: {}\n\n\
+                **Knowledge Lineage**: {}\n\n\
                 **Tech Stack**: {}\n\n\
                 **Project Vision**: {}\n\n\
                 **Key Requirements**: {}\n\n\
@@ -235,6 +446,7 @@ pub async fn inject_project_knowledge(
                 **Key Decisions**: {}",
                 knowledge.iteration_number,
                 knowledge.iteration_id,
+                if lineage.is_empty() { "-".to_string() } else { lineage },
                 knowledge.tech_stack.join(", "),
                 knowledge.idea_summary,
                 knowledge.prd_summary,
@@ -208,6 +208,17 @@ impl IterationExecutor {
 
             tracing::info!("[Executor] Stage updated: {} (iteration: {})", stage_name, iteration.id);
 
+            self.interaction
+                .show_progress(
+                    "stages".to_string(),
+                    crate::interaction::ProgressInfo {
+                        current: stage_num as u32,
+                        total: total_stages as u32,
+                        message: stage_name.clone(),
+                    },
+                )
+                .await;
+
             self.interaction
                 .show_message_with_context(
                     crate::interaction::MessageLevel::Info,
@@ -257,6 +268,7 @@ impl IterationExecutor {
                         tracing::info!("[Executor] Found stored feedback for stage '{}': {}",
                             stage_name, fb.details.chars().take(100).collect::<String>());
                         current_feedback = Some(fb.details.clone());
+                        crate::persistence::set_pending_revision_feedback(&stage_name, &fb.details);
                         // Consume the feedback immediately so it is not re-applied on a
                         // later attempt or a subsequent run of this stage.
                         if let Err(e) = crate::persistence::clear_stage_feedback(&stage_name) {
@@ -352,6 +364,7 @@ impl IterationExecutor {
 
                             iteration.complete_stage(&stage_name, artifact_path.clone());
                             self.iteration_store.save(&iteration)?;
+                            workspace::commit_stage(&workspace, iteration, &stage_name, current_feedback.as_deref());
 
                             let progress_msg = if feedback_loop_count > 0 {
                                 format!(
@@ -431,6 +444,7 @@ impl IterationExecutor {
                                         }
 
                                         feedback_loop_count += 1;
+                                        crate::persistence::set_pending_revision_feedback(&stage_name, &feedback);
                                         current_feedback = Some(feedback);
                                         self.interaction
                                             .show_message_with_context(
@@ -620,6 +634,42 @@ impl IterationExecutor {
         self.execute(project, iteration_id, Some(retry_stage), model).await
     }
 
+    /// Run one stage of an iteration on its current workspace, without
+    /// gates, stage bookkeeping or continuing to the following stages (used
+    /// by `cowork watch` to re-run the check agent)
+    pub async fn run_single_stage(
+        &self,
+        project: &Project,
+        iteration_id: &str,
+        stage_name: &str,
+        feedback: Option<&str>,
+        model: Option<Arc<dyn adk_core::Llm>>,
+    ) -> anyhow::Result<StageResult> {
+        let iteration = self.iteration_store.load(iteration_id)?;
+        let stage = super::create_stage_by_id(stage_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown stage '{}'", stage_name))?;
+        let workspace = self.iteration_store.workspace_path(iteration_id)?;
+
+        let model = match model {
+            Some(m) => m,
+            None => {
+                let llm_config = load_config()?;
+                create_llm_client(&llm_config.llm)?
+            }
+        };
+        set_execution_llm(model);
+        crate::persistence::set_iteration_id(iteration.id.clone());
+
+        let ctx = PipelineContext::new(project.clone(), iteration, workspace);
+        let result = match feedback {
+            Some(feedback) => stage.execute_with_feedback(&ctx, self.interaction.clone(), feedback).await,
+            None => stage.execute(&ctx, self.interaction.clone()).await,
+        };
+
+        clear_execution_llm();
+        Ok(result)
+    }
+
     // ========================================================================
     // Knowledge Generation Methods (delegated to knowledge module)
     // ========================================================================
@@ -3,6 +3,7 @@
 use std::sync::Arc;
 
 use crate::domain::{InheritanceMode, Iteration, IterationStatus};
+use crate::git::WorkspaceRepo;
 use crate::interaction::InteractiveBackend;
 use crate::persistence::IterationStore;
 
@@ -13,23 +14,121 @@ pub async fn prepare_workspace(
     iteration: &Iteration,
 ) -> anyhow::Result<std::path::PathBuf> {
     let workspace = iteration_store.ensure_workspace(&iteration.id)?;
+    let use_git = crate::git::is_git_enabled();
 
     // Only inherit from base when iteration is first starting (Draft status)
     if let Some(base_id) = &iteration.base_iteration_id {
         if iteration.status == IterationStatus::Draft {
-            inherit_from_base(
-                iteration_store,
-                interaction,
-                &workspace,
-                base_id,
-                iteration.inheritance,
-            ).await?;
+            let branched = use_git
+                && WorkspaceRepo::open(&workspace).is_none()
+                && branch_from_base(iteration_store, interaction, &workspace, iteration).await;
+            if !branched {
+                inherit_from_base(
+                    iteration_store,
+                    interaction,
+                    &workspace,
+                    base_id,
+                    iteration.inheritance,
+                ).await?;
+            }
         }
     }
 
+    // Remember the project root as it was when the iteration started, so
+    // delivery can tell the user's own later edits from the iteration's
+    if iteration.status == IterationStatus::Draft {
+        let captured = crate::delivery::project_root()
+            .and_then(|root| crate::delivery::ProjectBaseline::capture(&root))
+            .and_then(|baseline| baseline.save(&iteration_store.iteration_path(&iteration.id)?));
+        if let Err(e) = captured {
+            tracing::warn!("[Workspace] Failed to record project baseline: {}", e);
+        }
+    }
+
+    // Workspaces created before git integration (or whose base had no
+    // repository) start their history from their current contents
+    if use_git
+        && WorkspaceRepo::open(&workspace).is_none()
+        && let Err(e) = WorkspaceRepo::init(&workspace, iteration)
+    {
+        tracing::warn!("[Workspace] Failed to create git repository, continuing without: {}", e);
+    }
+
     Ok(workspace)
 }
 
+/// Inherit by branching from the base iteration's git history. Returns false
+/// when that is not possible and files should be copied instead.
+async fn branch_from_base(
+    iteration_store: &IterationStore,
+    interaction: &Arc<dyn InteractiveBackend>,
+    workspace: &std::path::Path,
+    iteration: &Iteration,
+) -> bool {
+    let Some(base_id) = iteration.base_iteration_id.as_deref() else {
+        return false;
+    };
+    let code_only = match iteration.inheritance {
+        InheritanceMode::None => return false,
+        InheritanceMode::Full => false,
+        InheritanceMode::Partial => true,
+    };
+    let Some(base_repo) = iteration_store
+        .workspace_path(base_id)
+        .ok()
+        .and_then(|path| WorkspaceRepo::open(&path))
+    else {
+        return false;
+    };
+
+    if let Err(e) = base_repo.commit_all(&format!("Uncommitted changes before {} was branched", iteration.id), None) {
+        tracing::warn!("[Workspace] Failed to commit pending changes of {}: {}", base_id, e);
+    }
+
+    match WorkspaceRepo::init_from_base(workspace, iteration, &base_repo, code_only) {
+        Ok(_) => {
+            interaction
+                .show_message(
+                    crate::interaction::MessageLevel::Info,
+                    format!(
+                        "Branched {} from iteration {}{}",
+                        crate::git::iteration_branch(&iteration.id),
+                        base_id,
+                        if code_only { " (code only)" } else { "" }
+                    ),
+                )
+                .await;
+            true
+        }
+        Err(e) => {
+            tracing::warn!("[Workspace] Failed to branch from {}, copying files instead: {}", base_id, e);
+            // Start the copy from a clean directory
+            let _ = std::fs::remove_dir_all(workspace.join(".git"));
+            false
+        }
+    }
+}
+
+/// Commit the workspace after a stage completed, if it is a git repository
+pub fn commit_stage(
+    workspace: &std::path::Path,
+    iteration: &Iteration,
+    stage_name: &str,
+    feedback: Option<&str>,
+) {
+    let Some(repo) = WorkspaceRepo::open(workspace) else {
+        return;
+    };
+    let agent = crate::tools::current_agent_name();
+    let message = crate::git::stage_commit_message(stage_name, iteration, &agent, feedback);
+    let author = (!agent.is_empty()).then_some(agent.as_str());
+    match repo.commit_all(&message, author) {
+        Ok(Some(commit)) => tracing::info!("[Workspace] Committed stage '{}' as {}", stage_name, &commit[..commit.len().min(12)]),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("[Workspace] Failed to commit stage '{}': {}", stage_name, e),
+    }
+}
+
 /// Inherit workspace from base iteration
 async fn inherit_from_base(
     iteration_store: &IterationStore,
@@ -126,6 +225,11 @@ async fn copy_dir_all(src: &std::path::Path, dst: &std::path::Path) -> anyhow::R
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
+        // The repository belongs to the base iteration
+        if entry.file_name() == ".git" {
+            continue;
+        }
+
         if ty.is_dir() {
             Box::pin(copy_dir_all(&src_path, &dst_path)).await?;
         } else {
@@ -148,8 +252,8 @@ async fn copy_code_files(src: &std::path::Path, dst: &std::path::Path) -> anyhow
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
-        // Skip artifacts directory
-        if entry.file_name() == "artifacts" {
+        // Skip artifacts directory and the base iteration's repository
+        if entry.file_name() == "artifacts" || entry.file_name() == ".git" {
             continue;
         }
 
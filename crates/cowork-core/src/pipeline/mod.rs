@@ -8,12 +8,14 @@ use crate::domain::{Iteration, Project};
 use crate::interaction::InteractiveBackend;
 
 pub mod executor;
+pub mod rewind;
 pub mod stages;
 pub mod stage_executor;
 
 pub use executor::*;
 pub use stages::*;
 pub use stage_executor::*;
+pub use rewind::RewindPlan;
 
 static GOTO_STAGE_SIGNAL: LazyLock<Mutex<Option<(String, String)>>> = LazyLock::new(|| Mutex::new(None));
 
@@ -161,6 +163,12 @@ pub fn get_stages_from_flow(start_stage: &str) -> Vec<Box<dyn Stage>> {
     get_stages_from(start_stage)
 }
 
+/// Stage IDs of the default flow, in execution order
+pub fn get_flow_stage_ids() -> Vec<String> {
+    // An unknown start stage selects the whole flow
+    get_stages_from_flow("").iter().map(|s| s.name().to_string()).collect()
+}
+
 /// Get Flow configuration for execution
 /// Returns the default flow's configuration or a default configuration
 pub fn get_flow_config() -> crate::config_definition::flow_definition::FlowConfig {
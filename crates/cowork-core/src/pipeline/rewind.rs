@@ -0,0 +1,134 @@
+// Rewind an iteration to an earlier stage
+//
+// The target stage and everything after it are forgotten: they are removed
+// from `completed_stages`, and the documents and data they produced are
+// archived (or deleted) so the re-run starts from a clean slate. Workspace
+// code is never touched.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::data::{Feedback, FeedbackType, Severity};
+use crate::domain::{Iteration, IterationStatus};
+use crate::persistence::{append_feedback, set_iteration_id, IterationStore};
+
+use super::get_flow_stage_ids;
+
+/// Files a stage writes, relative to the iteration directory
+fn stage_outputs(stage: &str) -> &'static [&'static str] {
+    match stage {
+        "idea" => &["artifacts/idea.md"],
+        "prd" => &["artifacts/prd.md", "data/requirements.json", "data/feature_list.json"],
+        "design" => &["artifacts/design.md", "data/design_spec.json"],
+        "plan" => &["artifacts/plan.md", "data/implementation_plan.json"],
+        "check" => &[
+            "artifacts/check_report.md",
+            "artifacts/traceability.md",
+            "artifacts/traceability.csv",
+            "artifacts/traceability.json",
+        ],
+        "delivery" => &["artifacts/delivery_report.md"],
+        _ => &[],
+    }
+}
+
+/// What a rewind would change; `apply` performs it
+#[derive(Debug, Clone, Serialize)]
+pub struct RewindPlan {
+    pub iteration_id: String,
+    pub target_stage: String,
+    /// Target stage and every later stage of the flow
+    pub stages: Vec<String>,
+    /// Of those, the stages the iteration had completed
+    pub discarded_stages: Vec<String>,
+    /// Existing output files of the rewound stages (relative paths)
+    pub files: Vec<String>,
+}
+
+impl RewindPlan {
+    pub fn prepare(iteration: &Iteration, target_stage: &str) -> Result<Self> {
+        if iteration.status == IterationStatus::Running {
+            anyhow::bail!(
+                "Iteration '{}' is running; wait for it to pause or finish before rewinding",
+                iteration.id
+            );
+        }
+
+        let flow = get_flow_stage_ids();
+        let start = flow.iter().position(|s| s == target_stage).ok_or_else(|| {
+            anyhow::anyhow!("Unknown stage '{}'. Valid stages: {}", target_stage, flow.join(", "))
+        })?;
+        let stages: Vec<String> = flow[start..].to_vec();
+
+        let discarded_stages = stages
+            .iter()
+            .filter(|s| iteration.completed_stages.contains(s))
+            .cloned()
+            .collect();
+
+        let iteration_dir = IterationStore::new().iteration_path(&iteration.id)?;
+        let files = stages
+            .iter()
+            .flat_map(|s| stage_outputs(s).iter())
+            .filter(|f| iteration_dir.join(f).exists())
+            .map(|f| f.to_string())
+            .collect();
+
+        Ok(Self {
+            iteration_id: iteration.id.clone(),
+            target_stage: target_stage.to_string(),
+            stages,
+            discarded_stages,
+            files,
+        })
+    }
+
+    /// Move (or delete, when `archive` is false) the output files, record
+    /// `feedback` for the target stage and reset the iteration. Returns the
+    /// archive directory when files were archived.
+    pub fn apply(&self, iteration: &mut Iteration, feedback: Option<&str>, archive: bool) -> Result<Option<PathBuf>> {
+        let store = IterationStore::new();
+        let iteration_dir = store.iteration_path(&self.iteration_id)?;
+
+        let archive_dir = (archive && !self.files.is_empty()).then(|| {
+            iteration_dir
+                .join("archive")
+                .join(format!("rewind-{}-{}", self.target_stage, Utc::now().format("%Y%m%d-%H%M%S")))
+        });
+
+        for file in &self.files {
+            let path = iteration_dir.join(file);
+            match &archive_dir {
+                Some(dir) => {
+                    let dest = dir.join(file);
+                    if let Some(parent) = dest.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::rename(&path, &dest)
+                        .with_context(|| format!("Failed to archive {}", path.display()))?;
+                }
+                None => std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?,
+            }
+        }
+
+        if let Some(details) = feedback.map(str::trim).filter(|f| !f.is_empty()) {
+            set_iteration_id(self.iteration_id.clone());
+            append_feedback(&Feedback {
+                stage: self.target_stage.clone(),
+                feedback_type: FeedbackType::Suggestion,
+                severity: Severity::Major,
+                details: details.to_string(),
+                suggested_fix: Some(format!("Rewound to the {} stage", self.target_stage)),
+                timestamp: Utc::now(),
+            })?;
+        }
+
+        iteration.rewind_to(&self.target_stage, &self.stages);
+        store.save(iteration)?;
+
+        Ok(archive_dir)
+    }
+}
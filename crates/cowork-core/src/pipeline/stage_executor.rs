@@ -108,6 +108,25 @@ pub async fn execute_stage_with_instruction(
 }
 
 pub async fn execute_stage_with_instruction_and_context(
+    ctx: &PipelineContext,
+    interaction: Arc<dyn InteractiveBackend>,
+    stage_name: &str,
+    instruction: &str,
+    feedback: Option<&str>,
+    extra_context: Option<&str>,
+) -> StageResult {
+    let result = run_stage_agent(ctx, interaction, stage_name, instruction, feedback, extra_context).await;
+
+    // Dev servers started with start_process do not outlive the stage
+    let stopped = crate::process::stop_all();
+    if stopped > 0 {
+        tracing::info!("[StageExecutor] Stopped {} background process(es) left by stage '{}'", stopped, stage_name);
+    }
+
+    result
+}
+
+async fn run_stage_agent(
     ctx: &PipelineContext,
     interaction: Arc<dyn InteractiveBackend>,
     stage_name: &str,
@@ -649,6 +668,62 @@ fn load_artifact_content(ctx: &PipelineContext, artifact_name: &str) -> Option<S
     None
 }
 
+/// Summarize structured data changes for an evolution iteration: what the
+/// base iteration changed relative to its own base, and what this iteration
+/// has changed so far (e.g. when the PRD stage is re-run after feedback)
+fn evolution_data_changes(ctx: &PipelineContext) -> Option<String> {
+    use crate::data::{DataSnapshot, IterationDataDiff};
+
+    let base_id = ctx.iteration.base_iteration_id.as_ref()?;
+    let mut sections = String::new();
+
+    if let Ok(base) = crate::persistence::IterationStore::new().load(base_id)
+        && let Some(grand_base_id) = &base.base_iteration_id
+        && let Ok(diff) = IterationDataDiff::between(grand_base_id, base_id)
+        && !diff.is_empty()
+    {
+        sections.push_str(&format!(
+            "### Changes made by base iteration #{} (vs its base {})\n{}\n",
+            base.number,
+            grand_base_id,
+            diff.summary(15)
+        ));
+    }
+
+    if let Ok(current) = DataSnapshot::load(&ctx.iteration.id)
+        && !current.is_empty()
+        && let Ok(base) = DataSnapshot::load(base_id)
+    {
+        // Only compare the data this iteration has produced so far
+        let base = DataSnapshot {
+            requirements: current.requirements.as_ref().and(base.requirements),
+            features: current.features.as_ref().and(base.features),
+            design: current.design.as_ref().and(base.design),
+            plan: current.plan.as_ref().and(base.plan),
+        };
+        let diff = IterationDataDiff::compute(base_id, &base, &ctx.iteration.id, &current);
+        if !diff.is_empty() {
+            sections.push_str(&format!(
+                "### Changes made so far in this iteration (vs base {})\n{}\n",
+                base_id,
+                diff.summary(15)
+            ));
+        }
+    }
+
+    if sections.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "═══════════════════════════════════════════════════════════════\n\
+         📊 DATA CHANGES ACROSS ITERATIONS\n\
+         ═══════════════════════════════════════════════════════════════\n\
+         {}\
+         ═══════════════════════════════════════════════════════════════\n\n",
+        sections
+    ))
+}
+
 /// Build prompt with iteration context and pre-injected artifacts
 fn build_prompt(
     ctx: &PipelineContext,
@@ -775,6 +850,13 @@ fn build_prompt(
         _ => {}
     }
 
+    // Evolution PRD: show how requirements/features/design/plan have evolved
+    if stage_name == "prd"
+        && let Some(changes) = evolution_data_changes(ctx)
+    {
+        prompt.push_str(&changes);
+    }
+
     // Build a human-readable note about which artifacts were preview-only.
     let preview_note = if preview_artifacts.is_empty() {
         String::new()
@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
 use crate::agents::{ExternalCodingAgent, StreamingTask};
-use crate::interaction::{InteractiveBackend, MessageContext, MessageLevel};
+use crate::interaction::{InteractiveBackend, MessageContext, MessageLevel, ProgressInfo};
 use crate::llm::config::load_config;
 use crate::pipeline::{PipelineContext, Stage, StageResult};
 use crate::instructions::coding::CODING_ACTOR_INSTRUCTION;
 use crate::pipeline::stage_executor::{execute_stage_with_instruction, execute_stage_with_instruction_and_context};
 use crate::acp::AgentMessage;
+use crate::acp::plan::{sync_plan_to_tasks, PlanStep};
+use crate::data::TaskStatus;
 
 /// Coding Stage - Generate code implementation using Agent with Instructions + Tools
 /// 
@@ -103,7 +105,7 @@ impl CodingStage {
         tracing::debug!(workspace = %workspace.display(), "creating ExternalCodingAgent");
         tracing::debug!(iteration_id = %ctx.iteration.id, base_id = ?ctx.iteration.base_iteration_id, inheritance = ?ctx.iteration.inheritance, "iteration context");
         let agent = match ExternalCodingAgent::new_with_iteration(&workspace, Some(ctx.iteration.clone())).await {
-            Ok(agent) => agent,
+            Ok(agent) => agent.with_interaction(interaction.clone()),
             Err(e) => {
                 interaction
                     .show_message_with_context(
@@ -185,6 +187,19 @@ impl CodingStage {
                             Some(AgentMessage::Error(text)) => {
                                 interaction_clone.show_message_with_context(MessageLevel::Error, format!("❌ {}", text), ctx_external.clone()).await;
                             }
+                            Some(AgentMessage::ToolCall { name, arguments }) => {
+                                interaction_clone.send_tool_call(&name, &arguments, AGENT_NAME_EXTERNAL).await;
+                            }
+                            Some(AgentMessage::ToolResult { name, result, success }) => {
+                                interaction_clone.send_tool_result(&name, &result, success, AGENT_NAME_EXTERNAL).await;
+                            }
+                            Some(AgentMessage::Diff { path, old_text, new_text }) => {
+                                let diff = render_diff(&path.display().to_string(), old_text.as_deref(), &new_text);
+                                interaction_clone.send_tool_result("diff", &diff, true, AGENT_NAME_EXTERNAL).await;
+                            }
+                            Some(AgentMessage::Plan(steps)) => {
+                                report_agent_plan(&interaction_clone, &steps, &ctx_external).await;
+                            }
                             Some(AgentMessage::Completed) => {
                                 interaction_clone.show_message_with_context(MessageLevel::Info, "✅ Task completed".to_string(), ctx_external.clone()).await;
                                 // Record that Completed was received so the outer code
@@ -373,4 +388,57 @@ impl Stage for CodingStage {
         
         execute_stage_with_instruction(ctx, interaction, "coding", CODING_ACTOR_INSTRUCTION, Some(feedback)).await
     }
-}
\ No newline at end of file
+}
+
+/// Unified diff of a file change made by the external agent
+fn render_diff(path: &str, old_text: Option<&str>, new_text: &str) -> String {
+    similar::TextDiff::from_lines(old_text.unwrap_or(""), new_text)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
+
+/// Show the external agent's plan as progress and mirror it into the
+/// implementation plan's task statuses
+async fn report_agent_plan(
+    interaction: &Arc<dyn InteractiveBackend>,
+    steps: &[PlanStep],
+    context: &MessageContext,
+) {
+    let done = steps.iter().filter(|s| s.status == TaskStatus::Completed).count();
+    let current = steps
+        .iter()
+        .find(|s| s.status == TaskStatus::InProgress)
+        .map(|s| s.content.clone())
+        .unwrap_or_default();
+    interaction
+        .show_progress(
+            "external_agent_plan".to_string(),
+            ProgressInfo {
+                current: done as u32,
+                total: steps.len() as u32,
+                message: current,
+            },
+        )
+        .await;
+
+    match sync_plan_to_tasks(steps) {
+        Ok(changes) => {
+            for change in changes {
+                let status = serde_json::to_value(change.status)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                interaction
+                    .show_message_with_context(
+                        MessageLevel::Info,
+                        format!("📋 Task {} → {}", change.task_id, status),
+                        context.clone(),
+                    )
+                    .await;
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "Failed to mirror external agent plan into tasks"),
+    }
+}
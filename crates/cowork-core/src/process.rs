@@ -0,0 +1,440 @@
+// Background processes started by agents (dev servers, watchers)
+//
+// `start_process` spawns a command through the project's sandbox, hands it a
+// free port in `PORT` (and in place of `{PORT}` in the command line) and waits
+// until it is ready: the port accepts connections, the output matches a ready
+// pattern, or the output announces a local URL. Output is kept in a bounded
+// buffer the agent can poll. Processes are process-group leaders so stopping
+// one also stops the workers it started; the stage executor stops whatever is
+// left when a stage ends.
+//
+// The port and process-tree helpers are shared with the GUI's project runner.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::{HashMap, VecDeque};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+use crate::sandbox::Sandbox;
+
+/// First port tried when the caller does not ask for one
+pub const DEFAULT_PORT: u16 = 3000;
+
+/// Output lines kept per process; older lines are dropped
+const MAX_OUTPUT_LINES: usize = 2000;
+
+/// Placeholder replaced by the allocated port in the command line
+const PORT_PLACEHOLDER: &str = "{PORT}";
+
+static PROCESSES: LazyLock<Mutex<HashMap<String, BackgroundProcess>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Local URL announced by a dev server, e.g. "Local: http://localhost:5173/"
+static URL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"https?://(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1?\]):(\d{2,5})").unwrap()
+});
+
+/// What to start and how to tell it is ready
+#[derive(Debug, Clone)]
+pub struct ProcessSpec {
+    /// Shell command line; `{PORT}` is replaced by the allocated port
+    pub command: String,
+    pub cwd: PathBuf,
+    /// Port the process must use; allocated from `DEFAULT_PORT` upwards when unset
+    pub port: Option<u16>,
+    /// Regular expression matched against each output line
+    pub ready_pattern: Option<String>,
+    pub ready_timeout: Duration,
+}
+
+/// Snapshot of a background process
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessInfo {
+    pub id: String,
+    pub command: String,
+    pub pid: Option<u32>,
+    pub port: u16,
+    pub url: String,
+    pub ready: bool,
+    pub running: bool,
+    pub exit_code: Option<i32>,
+}
+
+/// Output lines from a cursor onwards
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OutputChunk {
+    pub lines: Vec<String>,
+    /// Cursor to pass next time to get only newer lines
+    pub next: usize,
+    /// Lines dropped from the buffer before the requested cursor
+    pub dropped: usize,
+}
+
+struct BackgroundProcess {
+    command: String,
+    pid: Option<u32>,
+    port: u16,
+    state: Arc<Mutex<ProcessState>>,
+}
+
+#[derive(Default)]
+struct ProcessState {
+    lines: VecDeque<String>,
+    /// Lines ever written, including the ones dropped from `lines`
+    total: usize,
+    ready: bool,
+    /// Port announced in the output, when it differs from the allocated one
+    announced_port: Option<u16>,
+    exit: Option<Option<i32>>,
+}
+
+impl ProcessState {
+    fn push(&mut self, line: String) {
+        if self.announced_port.is_none()
+            && let Some(port) = URL_PATTERN
+                .captures(&line)
+                .and_then(|c| c[1].parse::<u16>().ok())
+        {
+            self.announced_port = Some(port);
+        }
+        if self.lines.len() == MAX_OUTPUT_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        self.total += 1;
+    }
+
+    fn since(&self, cursor: usize, max_lines: usize) -> OutputChunk {
+        let first = self.total - self.lines.len();
+        let start = cursor.max(first).min(self.total);
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .skip(start - first)
+            .take(max_lines)
+            .cloned()
+            .collect();
+        OutputChunk {
+            next: start + lines.len(),
+            dropped: first.saturating_sub(cursor),
+            lines,
+        }
+    }
+}
+
+impl BackgroundProcess {
+    fn info(&self, id: &str) -> ProcessInfo {
+        let state = self.state.lock().unwrap();
+        let port = state.announced_port.unwrap_or(self.port);
+        ProcessInfo {
+            id: id.to_string(),
+            command: self.command.clone(),
+            pid: self.pid,
+            port,
+            url: format!("http://127.0.0.1:{}", port),
+            ready: state.ready,
+            running: state.exit.is_none(),
+            exit_code: state.exit.flatten(),
+        }
+    }
+}
+
+/// Start a process in the background and wait until it is ready or the timeout passes
+///
+/// Fails if the process exits before it is ready; a process that is still
+/// starting when the timeout passes is left running with `ready: false`.
+pub async fn start(sandbox: &Sandbox, spec: ProcessSpec) -> Result<(ProcessInfo, OutputChunk)> {
+    let port = match spec.port {
+        Some(port) if !is_port_available(port) => {
+            anyhow::bail!("Port {} is already in use; stop whatever holds it or omit the port", port)
+        }
+        Some(port) => port,
+        None => find_available_port(DEFAULT_PORT)?,
+    };
+    let ready_pattern = spec
+        .ready_pattern
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .context("Invalid ready pattern")?;
+
+    let command = spec.command.replace(PORT_PLACEHOLDER, &port.to_string());
+    let mut cmd = sandbox.shell_command(&command, &spec.cwd)?;
+    cmd.env("PORT", port.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let mut child = cmd.spawn().with_context(|| format!("Failed to start '{}'", command))?;
+    let pid = child.id();
+    let state = Arc::new(Mutex::new(ProcessState::default()));
+
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(collect_output(stdout, "", Arc::clone(&state)));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(collect_output(stderr, "[stderr] ", Arc::clone(&state)));
+    }
+    let exit_state = Arc::clone(&state);
+    tokio::spawn(async move {
+        let code = child.wait().await.ok().and_then(|status| status.code());
+        exit_state.lock().unwrap().exit = Some(code);
+    });
+
+    let id = format!("proc-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    tracing::info!("[Process] Started {} (PID {:?}, port {}): {}", id, pid, port, command);
+    PROCESSES.lock().unwrap().insert(
+        id.clone(),
+        BackgroundProcess { command, pid, port, state: Arc::clone(&state) },
+    );
+
+    let deadline = tokio::time::Instant::now() + spec.ready_timeout;
+    loop {
+        let (exited, port) = {
+            let mut state = state.lock().unwrap();
+            if !state.ready
+                && let Some(pattern) = &ready_pattern
+            {
+                state.ready = state.lines.iter().any(|line| pattern.is_match(line));
+            }
+            (state.exit.is_some(), state.announced_port.unwrap_or(port))
+        };
+
+        if exited {
+            // Give the readers a moment to drain what the process printed last
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let output = state.lock().unwrap().since(0, MAX_OUTPUT_LINES);
+            PROCESSES.lock().unwrap().remove(&id);
+            let code = state.lock().unwrap().exit.flatten();
+            anyhow::bail!(
+                "Process exited before it was ready (exit code {:?}). Output:\n{}",
+                code,
+                output.lines.join("\n")
+            );
+        }
+
+        if ready_pattern.is_none() && port_accepts_connections(port) {
+            state.lock().unwrap().ready = true;
+        }
+        if state.lock().unwrap().ready || tokio::time::Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    let info = PROCESSES.lock().unwrap().get(&id).map(|p| p.info(&id)).context("Process vanished")?;
+    let output = state.lock().unwrap().since(0, MAX_OUTPUT_LINES);
+    Ok((info, output))
+}
+
+/// Output of a process from `cursor` onwards, at most `max_lines` lines
+pub fn read_output(id: &str, cursor: usize, max_lines: usize) -> Result<(ProcessInfo, OutputChunk)> {
+    let processes = PROCESSES.lock().unwrap();
+    let process = processes.get(id).with_context(|| format!("No background process '{}'", id))?;
+    let chunk = process.state.lock().unwrap().since(cursor, max_lines);
+    Ok((process.info(id), chunk))
+}
+
+/// Stop a process and everything it started
+pub fn stop(id: &str) -> Result<ProcessInfo> {
+    let process = PROCESSES
+        .lock()
+        .unwrap()
+        .remove(id)
+        .with_context(|| format!("No background process '{}'", id))?;
+    if let Some(pid) = process.pid
+        && process.state.lock().unwrap().exit.is_none()
+    {
+        kill_process_tree(pid);
+    }
+    tracing::info!("[Process] Stopped {}", id);
+    Ok(process.info(id))
+}
+
+/// Background processes that have not been stopped
+pub fn list() -> Vec<ProcessInfo> {
+    let processes = PROCESSES.lock().unwrap();
+    let mut infos: Vec<ProcessInfo> = processes.iter().map(|(id, p)| p.info(id)).collect();
+    infos.sort_by(|a, b| a.id.cmp(&b.id));
+    infos
+}
+
+/// Stop every background process; returns how many were stopped
+pub fn stop_all() -> usize {
+    let ids: Vec<String> = PROCESSES.lock().unwrap().keys().cloned().collect();
+    ids.iter().filter(|id| stop(id).is_ok()).count()
+}
+
+async fn collect_output(stream: impl AsyncRead + Unpin, prefix: &'static str, state: Arc<Mutex<ProcessState>>) {
+    let mut lines = BufReader::new(stream).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        state.lock().unwrap().push(format!("{}{}", prefix, line));
+    }
+}
+
+fn port_accepts_connections(port: u16) -> bool {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    TcpStream::connect_timeout(&addr, Duration::from_millis(200)).is_ok()
+}
+
+/// Find an available port, trying `preferred_port` first
+pub fn find_available_port(preferred_port: u16) -> Result<u16> {
+    if is_port_available(preferred_port) {
+        return Ok(preferred_port);
+    }
+
+    // Try the next hundred ports
+    let upper = preferred_port.saturating_add(100);
+    if let Some(port) = (preferred_port.saturating_add(1)..=upper).find(|&p| is_port_available(p)) {
+        return Ok(port);
+    }
+
+    // Fall back to a larger range
+    (8000..=9000)
+        .rev()
+        .find(|&p| is_port_available(p))
+        .context("No available port found in range 8000-9000")
+}
+
+/// Check if a port is free on the loopback interface
+pub fn is_port_available(port: u16) -> bool {
+    TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port))).is_ok()
+}
+
+/// Kill a process and all its descendants (the entire process tree).
+///
+/// On Windows, uses `taskkill /T /F` which walks the process tree and force-kills
+/// all descendants. This is critical because `child.kill()` only terminates the
+/// direct child (e.g. `cmd.exe`), leaving the real dev server (`bun.exe`/`node.exe`)
+/// orphaned and still holding the port.
+///
+/// On Unix, kills the entire process group. The child must have been spawned with
+/// `process_group(0)` so that it leads its own group; the PGID equals the child PID.
+pub fn kill_process_tree(pid: u32) {
+    #[cfg(target_os = "windows")]
+    {
+        // /T = kill tree (all descendants), /F = force
+        let result = std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        match result {
+            Ok(status) if status.success() => {
+                tracing::info!("[Process] taskkill succeeded for PID {}", pid);
+            }
+            Ok(status) => {
+                tracing::debug!(
+                    "[Process] taskkill for PID {} exited with non-success status: {:?}",
+                    pid,
+                    status
+                );
+            }
+            Err(e) => {
+                tracing::warn!("[Process] taskkill failed for PID {}: {}", pid, e);
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        // Kill the process group (negative PID targets the whole group).
+        // SIGTERM first for graceful shutdown, then SIGKILL to force.
+        let pgid = format!("-{}", pid);
+        let _ = std::process::Command::new("kill")
+            .args(["-TERM", &pgid])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        std::thread::sleep(Duration::from_millis(100));
+        let _ = std::process::Command::new("kill")
+            .args(["-KILL", &pgid])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        // Fallback: kill the direct process too
+        let _ = std::process::Command::new("kill")
+            .args(["-KILL", &pid.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sandbox::SandboxPolicy;
+
+    #[test]
+    fn test_port_allocation_skips_ports_in_use() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let taken = listener.local_addr().unwrap().port();
+
+        assert!(!is_port_available(taken));
+        let port = find_available_port(taken).unwrap();
+        assert_ne!(port, taken);
+        assert!(is_port_available(port));
+    }
+
+    #[test]
+    fn test_output_buffer_cursor() {
+        let mut state = ProcessState::default();
+        for i in 0..MAX_OUTPUT_LINES + 5 {
+            state.push(format!("line {}", i));
+        }
+        state.push("  ➜  Local:   http://localhost:5173/".to_string());
+
+        let chunk = state.since(0, 2);
+        assert_eq!(chunk.dropped, 6);
+        assert_eq!(chunk.lines, vec!["line 6", "line 7"]);
+        assert_eq!(chunk.next, 8);
+        assert_eq!(state.since(chunk.next, 1).lines, vec!["line 8"]);
+        assert_eq!(state.announced_port, Some(5173));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_start_read_and_stop() {
+        let workspace = tempfile::tempdir().unwrap();
+        let policy = SandboxPolicy { enabled: false, ..SandboxPolicy::default() };
+        let sandbox = Sandbox::new(policy, workspace.path()).unwrap();
+
+        let spec = ProcessSpec {
+            command: "echo listening on {PORT}; echo \"port=$PORT\"; sleep 30".to_string(),
+            cwd: workspace.path().to_path_buf(),
+            port: None,
+            ready_pattern: Some("^listening".to_string()),
+            ready_timeout: Duration::from_secs(10),
+        };
+        let (info, output) = start(&sandbox, spec).await.unwrap();
+        assert!(info.ready && info.running);
+        assert_eq!(output.lines[0], format!("listening on {}", info.port));
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let (_, output) = read_output(&info.id, 1, 10).unwrap();
+        assert_eq!(output.lines, vec![format!("port={}", info.port)]);
+
+        stop(&info.id).unwrap();
+        assert!(read_output(&info.id, 0, 10).is_err());
+
+        let failing = ProcessSpec {
+            command: "echo boom; exit 3".to_string(),
+            cwd: workspace.path().to_path_buf(),
+            port: None,
+            ready_pattern: None,
+            ready_timeout: Duration::from_secs(10),
+        };
+        let err = start(&sandbox, failing).await.unwrap_err().to_string();
+        assert!(err.contains("exit code Some(3)") && err.contains("boom"), "{}", err);
+    }
+}
@@ -173,6 +173,15 @@ impl RuntimeSecurityChecker {
         }
     }
     
+    /// The dangerous pattern a command matches, if any
+    pub fn dangerous_pattern(&self, command: &str) -> Option<&str> {
+        let cmd_lower = command.to_lowercase();
+        self.dangerous_patterns
+            .iter()
+            .find(|pattern| pattern.is_match(&cmd_lower))
+            .map(|pattern| pattern.as_str())
+    }
+
     /// Check if a single command is safe
     pub fn is_command_safe(&self, command: &str) -> bool {
         if command.is_empty() {
@@ -182,10 +191,8 @@ impl RuntimeSecurityChecker {
         let cmd_lower = command.to_lowercase();
         
         // 1. Check dangerous patterns
-        for pattern in &self.dangerous_patterns {
-            if pattern.is_match(&cmd_lower) {
-                return false;
-            }
+        if self.dangerous_pattern(&cmd_lower).is_some() {
+            return false;
         }
         
         // 2. Check if first command is allowed
@@ -0,0 +1,524 @@
+// Sandboxed execution of commands run on behalf of agents
+//
+// `run_command`, `check_tests`, `check_lint`, `start_process` and ACP
+// terminals build their processes through `Sandbox`. Every command gets a
+// clean environment and resource limits, and runs in its own process group
+// so a timeout kills everything it started. On Linux the network is off by default: with
+// bubblewrap (`bwrap` on PATH) the filesystem is also read-only apart from
+// the workspace, a private /tmp and the policy's writable paths; without it
+// the command gets a private network namespace but writes are not confined.
+//
+// The policy is per project, in `.cowork-v2/sandbox.json`. Deny rules and
+// the dangerous patterns of `RuntimeSecurityChecker` apply even when the
+// sandbox is disabled.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Output, Stdio};
+use std::time::Duration;
+
+use crate::runtime_security::RuntimeSecurityChecker;
+
+/// Policy file in the project's `.cowork-v2` directory
+pub const SANDBOX_POLICY_FILE: &str = "sandbox.json";
+
+/// Environment variables sandboxed commands inherit, besides the policy's `env`
+const PASSTHROUGH_ENV: &[&str] = &[
+    "PATH", "HOME", "USER", "LOGNAME", "SHELL", "LANG", "LC_ALL", "LC_CTYPE", "TERM", "TMPDIR",
+    "CARGO_HOME", "RUSTUP_HOME", "RUSTUP_TOOLCHAIN", "GOPATH", "GOROOT", "GOCACHE", "JAVA_HOME",
+    "NVM_DIR", "PYENV_ROOT", "VIRTUAL_ENV",
+    // Needed by cmd.exe and most Windows toolchains
+    "SystemRoot", "ComSpec", "PATHEXT", "USERPROFILE", "APPDATA", "LOCALAPPDATA", "TEMP", "TMP",
+];
+
+/// Per-project sandbox policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxPolicy {
+    /// Isolate commands; when false they run as plain child processes
+    pub enabled: bool,
+    /// Allow network access (only enforced on Linux)
+    pub network: bool,
+    /// Paths writable besides the workspace; `~` is the home directory
+    pub writable_paths: Vec<String>,
+    /// Extra environment variables passed through to commands
+    pub env: Vec<String>,
+    /// Data segment limit in MiB, 0 for none
+    pub memory_mb: u64,
+    /// CPU time limit in seconds, 0 for none
+    pub cpu_secs: u64,
+    /// Largest file a command may write in MiB, 0 for none
+    pub file_size_mb: u64,
+    /// Regular expressions; matching commands are refused
+    pub deny: Vec<String>,
+}
+
+impl Default for SandboxPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            network: false,
+            // Package manager caches, so builds work offline from what is already there
+            writable_paths: vec!["~/.cargo".to_string(), "~/.npm".to_string(), "~/.cache".to_string()],
+            env: Vec::new(),
+            memory_mb: 8192,
+            cpu_secs: 1800,
+            file_size_mb: 2048,
+            deny: Vec::new(),
+        }
+    }
+}
+
+impl SandboxPolicy {
+    /// Path of the current project's policy file
+    pub fn path() -> Result<PathBuf> {
+        Ok(crate::persistence::get_cowork_dir()?.join(SANDBOX_POLICY_FILE))
+    }
+
+    /// Load the current project's policy, or the defaults when it has none
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid sandbox policy in {}", path.display()))
+    }
+
+    /// Save as the current project's policy
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// How strongly commands are isolated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Isolation {
+    /// Sandbox disabled by the policy
+    Off,
+    /// Clean environment and resource limits
+    Limits,
+    /// Limits plus a private network namespace
+    NoNetwork,
+    /// Bubblewrap: read-only filesystem outside the writable paths
+    Bubblewrap,
+}
+
+impl Isolation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Isolation::Off => "off",
+            Isolation::Limits => "limits",
+            Isolation::NoNetwork => "no_network",
+            Isolation::Bubblewrap => "bubblewrap",
+        }
+    }
+}
+
+/// Why a sandboxed command did not run to completion
+#[derive(Debug, thiserror::Error)]
+pub enum SandboxError {
+    #[error("Command denied: {0}")]
+    Denied(String),
+
+    #[error("Sandbox unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("Command timed out after {0}s")]
+    Timeout(u64),
+
+    #[error("Failed to run command: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Builds processes confined to one workspace
+pub struct Sandbox {
+    policy: SandboxPolicy,
+    workspace: PathBuf,
+    deny: Vec<Regex>,
+    security: RuntimeSecurityChecker,
+}
+
+impl Sandbox {
+    /// Sandbox for `workspace` under the current project's policy
+    pub fn for_workspace(workspace: impl Into<PathBuf>) -> Result<Self> {
+        Self::new(SandboxPolicy::load()?, workspace)
+    }
+
+    pub fn new(policy: SandboxPolicy, workspace: impl Into<PathBuf>) -> Result<Self> {
+        let deny = policy
+            .deny
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).with_context(|| format!("Invalid deny pattern in sandbox policy: {}", pattern))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            policy,
+            workspace: workspace.into(),
+            deny,
+            security: RuntimeSecurityChecker::new(),
+        })
+    }
+
+    pub fn policy(&self) -> &SandboxPolicy {
+        &self.policy
+    }
+
+    /// Same sandbox with the host network, for servers that must be reachable from outside it
+    pub fn with_network(mut self) -> Self {
+        self.policy.network = true;
+        self
+    }
+
+    /// Isolation commands get, or why they cannot be isolated as the policy asks
+    pub fn isolation(&self) -> Result<Isolation, SandboxError> {
+        if !self.policy.enabled {
+            return Ok(Isolation::Off);
+        }
+        if !cfg!(target_os = "linux") {
+            return Ok(Isolation::Limits);
+        }
+        if bubblewrap_available() {
+            return Ok(Isolation::Bubblewrap);
+        }
+        if self.policy.network {
+            return Ok(Isolation::Limits);
+        }
+        if network_namespace_available() {
+            return Ok(Isolation::NoNetwork);
+        }
+        Err(SandboxError::Unavailable(
+            "cannot turn the network off: install bubblewrap (bwrap), enable unprivileged user namespaces, \
+             or set \"network\": true in .cowork-v2/sandbox.json"
+                .to_string(),
+        ))
+    }
+
+    /// Refuse commands matching a deny rule or a dangerous pattern
+    pub fn check(&self, command_line: &str) -> Result<(), SandboxError> {
+        if let Some(rule) = self.deny.iter().find(|rule| rule.is_match(command_line)) {
+            return Err(SandboxError::Denied(format!(
+                "'{}' matches deny rule '{}' in .cowork-v2/sandbox.json",
+                command_line,
+                rule.as_str()
+            )));
+        }
+        if let Some(pattern) = self.security.dangerous_pattern(command_line) {
+            return Err(SandboxError::Denied(format!(
+                "'{}' matches the dangerous command pattern '{}'",
+                command_line, pattern
+            )));
+        }
+        Ok(())
+    }
+
+    /// Process running `program` with `args` in `cwd`; the caller sets up stdio
+    pub fn command(&self, program: &str, args: &[String], cwd: &Path) -> Result<tokio::process::Command, SandboxError> {
+        let isolation = self.isolation()?;
+
+        let mut cmd = if isolation == Isolation::Bubblewrap {
+            let mut cmd = tokio::process::Command::new("bwrap");
+            cmd.args(self.bubblewrap_args(cwd)).arg("--").arg(program).args(args);
+            cmd
+        } else {
+            let mut cmd = tokio::process::Command::new(program);
+            cmd.args(args);
+            cmd
+        };
+        cmd.current_dir(cwd);
+
+        if isolation != Isolation::Off {
+            cmd.env_clear();
+            for name in PASSTHROUGH_ENV.iter().copied().chain(self.policy.env.iter().map(String::as_str)) {
+                if let Some(value) = std::env::var_os(name) {
+                    cmd.env(name, value);
+                }
+            }
+        }
+
+        #[cfg(unix)]
+        self.confine(&mut cmd, isolation == Isolation::NoNetwork);
+
+        Ok(cmd)
+    }
+
+    /// Shell command line (`sh -c`, or `cmd /C` on Windows), checked first
+    pub fn shell_command(&self, command: &str, cwd: &Path) -> Result<tokio::process::Command, SandboxError> {
+        self.check(command)?;
+        let (shell, flag) = if cfg!(target_os = "windows") { ("cmd", "/C") } else { ("sh", "-c") };
+        self.command(shell, &[flag.to_string(), command.to_string()], cwd)
+    }
+
+    /// Run a shell command to completion, killing everything it started on timeout
+    pub async fn run(&self, command: &str, cwd: &Path, timeout: Duration) -> Result<Output, SandboxError> {
+        let mut cmd = self.shell_command(command, cwd)?;
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let child = cmd.spawn()?;
+        let pid = child.id();
+        match tokio::time::timeout(timeout, child.wait_with_output()).await {
+            Ok(output) => Ok(output?),
+            Err(_) => {
+                kill_process_group(pid);
+                Err(SandboxError::Timeout(timeout.as_secs()))
+            }
+        }
+    }
+
+    fn bubblewrap_args(&self, cwd: &Path) -> Vec<String> {
+        let mut args: Vec<String> = ["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc", "--tmpfs", "/tmp"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let workspace = self.workspace.canonicalize().unwrap_or_else(|_| self.workspace.clone());
+        let writable = std::iter::once(workspace).chain(self.policy.writable_paths.iter().map(|p| expand_home(p)));
+        for path in writable.filter(|p| p.exists()) {
+            let path = path.to_string_lossy().to_string();
+            args.extend(["--bind".to_string(), path.clone(), path]);
+        }
+
+        args.extend(["--unshare-pid", "--die-with-parent", "--new-session"].map(String::from));
+        if !self.policy.network {
+            args.push("--unshare-net".to_string());
+        }
+        args.extend(["--chdir".to_string(), cwd.to_string_lossy().to_string()]);
+        args
+    }
+
+    /// Process group, resource limits and (without bubblewrap) the network namespace
+    #[cfg(unix)]
+    fn confine(&self, cmd: &mut tokio::process::Command, isolate_network: bool) {
+        let limits = if self.policy.enabled {
+            [
+                self.policy.memory_mb.saturating_mul(1024 * 1024),
+                self.policy.cpu_secs,
+                self.policy.file_size_mb.saturating_mul(1024 * 1024),
+            ]
+        } else {
+            [0; 3]
+        };
+
+        #[cfg(target_os = "linux")]
+        let id_maps = isolate_network.then(IdMaps::current);
+        #[cfg(not(target_os = "linux"))]
+        let _ = isolate_network;
+
+        // SAFETY: the closure only makes async-signal-safe system calls and
+        // does not allocate; everything it needs is prepared beforehand
+        unsafe {
+            cmd.pre_exec(move || {
+                libc::setpgid(0, 0);
+                #[cfg(target_os = "linux")]
+                libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+
+                let [data, cpu, fsize] = limits;
+                set_limit(libc::RLIMIT_DATA as _, data);
+                set_limit(libc::RLIMIT_CPU as _, cpu);
+                set_limit(libc::RLIMIT_FSIZE as _, fsize);
+
+                #[cfg(target_os = "linux")]
+                if let Some(maps) = &id_maps {
+                    maps.unshare_network()?;
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+/// Kill the process group a sandboxed command leads
+pub fn kill_process_group(pid: Option<u32>) {
+    #[cfg(unix)]
+    if let Some(pid) = pid.and_then(|pid| i32::try_from(pid).ok()) {
+        // SAFETY: plain signal delivery to the command's own group
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = pid;
+}
+
+/// Whether bubblewrap is installed and can create namespaces here
+pub fn bubblewrap_available() -> bool {
+    static AVAILABLE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        cfg!(target_os = "linux")
+            && std::process::Command::new("bwrap")
+                .args(["--ro-bind", "/", "/", "--dev", "/dev", "--unshare-net", "true"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|s| s.success())
+    })
+}
+
+/// Whether commands can be given a private network namespace without bubblewrap
+pub fn network_namespace_available() -> bool {
+    static AVAILABLE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::process::CommandExt;
+
+            let maps = IdMaps::current();
+            let mut cmd = std::process::Command::new("true");
+            cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+            // SAFETY: see `Sandbox::confine`
+            unsafe {
+                cmd.pre_exec(move || maps.unshare_network());
+            }
+            cmd.status().is_ok_and(|s| s.success())
+        }
+        #[cfg(not(target_os = "linux"))]
+        false
+    })
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(unix)]
+fn set_limit(resource: i32, value: u64) {
+    if value == 0 {
+        return;
+    }
+    let limit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    };
+    // SAFETY: setrlimit only reads the struct; failures leave the old limit
+    unsafe {
+        libc::setrlimit(resource as _, &limit);
+    }
+}
+
+/// Identity mapping for a new user namespace, prepared before fork
+#[cfg(target_os = "linux")]
+struct IdMaps {
+    /// Root can create a network namespace without a user namespace
+    root: bool,
+    uid_map: Vec<u8>,
+    gid_map: Vec<u8>,
+}
+
+#[cfg(target_os = "linux")]
+impl IdMaps {
+    fn current() -> Self {
+        // SAFETY: these calls cannot fail
+        let (uid, gid) = unsafe { (libc::geteuid(), libc::getegid()) };
+        Self {
+            root: uid == 0,
+            uid_map: format!("{} {} 1", uid, uid).into_bytes(),
+            gid_map: format!("{} {} 1", gid, gid).into_bytes(),
+        }
+    }
+
+    /// Move the calling (forked) process into a private network namespace
+    fn unshare_network(&self) -> std::io::Result<()> {
+        let flags = if self.root {
+            libc::CLONE_NEWNET
+        } else {
+            libc::CLONE_NEWUSER | libc::CLONE_NEWNET
+        };
+        // SAFETY: unshare only affects the calling process
+        if unsafe { libc::unshare(flags) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if !self.root {
+            write_proc_file(c"/proc/self/setgroups", b"deny")?;
+            write_proc_file(c"/proc/self/uid_map", &self.uid_map)?;
+            write_proc_file(c"/proc/self/gid_map", &self.gid_map)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn write_proc_file(path: &std::ffi::CStr, data: &[u8]) -> std::io::Result<()> {
+    // SAFETY: raw open/write/close on a valid path and buffer
+    unsafe {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let written = libc::write(fd, data.as_ptr().cast(), data.len());
+        let error = std::io::Error::last_os_error();
+        libc::close(fd);
+        if written < 0 {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_denies_with_reason() {
+        let policy = SandboxPolicy {
+            deny: vec![r"^git\s+push".to_string()],
+            ..SandboxPolicy::default()
+        };
+        let sandbox = Sandbox::new(policy, ".").unwrap();
+
+        assert!(sandbox.check("cargo test").is_ok());
+        let denied = sandbox.check("git push origin main").unwrap_err().to_string();
+        assert!(denied.contains("deny rule"), "{}", denied);
+        let denied = sandbox.check("rm -rf /").unwrap_err().to_string();
+        assert!(denied.contains("dangerous"), "{}", denied);
+
+        let invalid = SandboxPolicy {
+            deny: vec!["(".to_string()],
+            ..SandboxPolicy::default()
+        };
+        assert!(Sandbox::new(invalid, ".").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_with_clean_env_and_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        // Network stays on so the test does not depend on namespaces
+        let policy = SandboxPolicy {
+            network: true,
+            ..SandboxPolicy::default()
+        };
+        let sandbox = Sandbox::new(policy, dir.path()).unwrap();
+
+        // SAFETY: test-only variable, read back by the child below
+        unsafe { std::env::set_var("COWORK_SANDBOX_TEST_SECRET", "leaked") };
+        let output = sandbox
+            .run("echo \"[$COWORK_SANDBOX_TEST_SECRET]\" > out.txt && cat out.txt", dir.path(), Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "[]");
+
+        let timed_out = sandbox.run("sleep 5", dir.path(), Duration::from_millis(200)).await;
+        assert!(matches!(timed_out, Err(SandboxError::Timeout(_))));
+    }
+}
@@ -0,0 +1,190 @@
+// Code Index Tools - Query the deterministic code structure index
+//
+// Lets agents locate symbols, inspect a file's outline and walk the import
+// graph without reading whole files.
+
+use adk_core::{Tool, ToolContext};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::code_index::{CodeIndex, SymbolKind};
+use crate::persistence::{IterationStore, MemoryStore};
+
+const DEFAULT_RESULT_LIMIT: usize = 50;
+
+/// Load the stored code index for an iteration, rebuilding it from the
+/// workspace when it is missing or older than the workspace files
+pub fn load_or_build_code_index(iteration_id: &str) -> anyhow::Result<CodeIndex> {
+    let workspace = IterationStore::new().workspace_path(iteration_id)?;
+    let memory_store = MemoryStore::new();
+
+    if let Some(index) = memory_store.load_code_index(iteration_id)?
+        && !index.is_stale(&workspace)
+    {
+        return Ok(index);
+    }
+
+    let index = CodeIndex::build(iteration_id, &workspace)?;
+    memory_store.save_code_index(&index)?;
+    Ok(index)
+}
+
+// ============================================================================
+// QueryCodeIndexTool
+// ============================================================================
+
+pub struct QueryCodeIndexTool {
+    iteration_id: String,
+}
+
+impl QueryCodeIndexTool {
+    pub fn new(iteration_id: String) -> Self {
+        Self { iteration_id }
+    }
+}
+
+#[async_trait]
+impl Tool for QueryCodeIndexTool {
+    fn name(&self) -> &str {
+        "query_code_index"
+    }
+
+    fn description(&self) -> &str {
+        "Query the code structure index of the workspace (Rust, TypeScript/JavaScript, Python, Go). \
+         Use 'symbols' to find where a function/type is declared (file + line), 'file' to get a file's \
+         public symbols and imports, 'dependencies'/'dependents' to walk the import graph, and \
+         'overview' for a project outline. Prefer this over reading whole files to locate code."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "mode": {
+                    "type": "string",
+                    "description": "Query mode",
+                    "enum": ["symbols", "file", "dependencies", "dependents", "overview"],
+                    "default": "symbols"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Symbol name or substring to search for (mode 'symbols')"
+                },
+                "kind": {
+                    "type": "string",
+                    "description": "Filter by symbol kind (mode 'symbols')",
+                    "enum": ["function", "method", "struct", "class", "enum", "trait", "interface", "type_alias", "constant", "variable", "module"]
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Relative file path (modes 'file', 'dependencies', 'dependents') or path prefix filter (mode 'symbols')"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of results (default: 50)"
+                }
+            },
+            "required": []
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let mode = args.get("mode").and_then(|v| v.as_str()).unwrap_or("symbols");
+        let name = args.get("name").and_then(|v| v.as_str());
+        let path = args.get("path").and_then(|v| v.as_str());
+        let limit = args
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_RESULT_LIMIT);
+
+        let kind = match args.get("kind").and_then(|v| v.as_str()) {
+            Some(k) => Some(SymbolKind::parse(k).ok_or_else(|| {
+                adk_core::AdkError::tool(format!("Unknown symbol kind: {}", k))
+            })?),
+            None => None,
+        };
+
+        let index = load_or_build_code_index(&self.iteration_id)
+            .map_err(|e| adk_core::AdkError::tool(format!("Failed to load code index: {}", e)))?;
+
+        let require_path = || {
+            path.ok_or_else(|| adk_core::AdkError::tool(format!("'path' is required for mode '{}'", mode)))
+        };
+
+        match mode {
+            "symbols" => {
+                let matches = index.find_symbols(name, kind, path);
+                let total = matches.len();
+                let results: Vec<Value> = matches
+                    .into_iter()
+                    .take(limit)
+                    .map(|(file, symbol)| {
+                        json!({
+                            "name": symbol.name,
+                            "kind": symbol.kind,
+                            "path": file.path,
+                            "line": symbol.line,
+                            "module": file.module,
+                            "signature": symbol.signature
+                        })
+                    })
+                    .collect();
+
+                Ok(json!({
+                    "status": "success",
+                    "mode": mode,
+                    "total_matches": total,
+                    "truncated": total > results.len(),
+                    "symbols": results
+                }))
+            }
+            "file" => {
+                let path = require_path()?;
+                match index.file(path) {
+                    Some(file) => Ok(json!({
+                        "status": "success",
+                        "mode": mode,
+                        "file": file,
+                        "dependents": index.dependents_of(path)
+                    })),
+                    None => Ok(json!({
+                        "status": "not_found",
+                        "message": format!("File '{}' is not in the code index (unsupported language or ignored)", path)
+                    })),
+                }
+            }
+            "dependencies" => {
+                let path = require_path()?;
+                let edges = index.dependencies_of(path);
+                Ok(json!({
+                    "status": "success",
+                    "mode": mode,
+                    "path": path,
+                    "internal": edges.iter().filter(|e| e.internal).map(|e| &e.to).collect::<Vec<_>>(),
+                    "external": edges.iter().filter(|e| !e.internal).map(|e| &e.to).collect::<Vec<_>>()
+                }))
+            }
+            "dependents" => {
+                let path = require_path()?;
+                Ok(json!({
+                    "status": "success",
+                    "mode": mode,
+                    "path": path,
+                    "dependents": index.dependents_of(path)
+                }))
+            }
+            "overview" => Ok(json!({
+                "status": "success",
+                "mode": mode,
+                "total_files": index.files.len(),
+                "overview": index.overview(limit)
+            })),
+            other => Err(adk_core::AdkError::tool(format!(
+                "Unknown mode '{}'. Use one of: symbols, file, dependencies, dependents, overview",
+                other
+            ))),
+        }
+    }
+}
@@ -627,27 +627,33 @@ impl Tool for GetPlanTool {
     }
 
     async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
-        let plan = load_implementation_plan().map_err(|e| AdkError::tool(e.to_string()))?;
+        get_plan(args.get("status_filter").and_then(|v| v.as_str())).map_err(|e| AdkError::tool(e.to_string()))
+    }
+}
 
-        if let Some(status_filter) = args.get("status_filter").and_then(|v| v.as_str()) {
-            let status = match status_filter {
-                "pending" => TaskStatus::Pending,
-                "in_progress" => TaskStatus::InProgress,
-                "completed" => TaskStatus::Completed,
-                _ => TaskStatus::Pending,
-            };
+/// The implementation plan, optionally only the tasks with one status (also
+/// served over MCP)
+pub fn get_plan(status_filter: Option<&str>) -> anyhow::Result<Value> {
+    let plan = load_implementation_plan()?;
 
-            let filtered_tasks: Vec<&Task> = plan.tasks.iter()
-                .filter(|t| t.status == status)
-                .collect();
+    if let Some(status_filter) = status_filter {
+        let status = match status_filter {
+            "pending" => TaskStatus::Pending,
+            "in_progress" => TaskStatus::InProgress,
+            "completed" => TaskStatus::Completed,
+            _ => TaskStatus::Pending,
+        };
 
-            Ok(json!({
-                "tasks": filtered_tasks,
-                "milestones": plan.milestones
-            }))
-        } else {
-            Ok(serde_json::to_value(plan).map_err(|e| AdkError::tool(e.to_string()))?)
-        }
+        let filtered_tasks: Vec<&Task> = plan.tasks.iter()
+            .filter(|t| t.status == status)
+            .collect();
+
+        Ok(json!({
+            "tasks": filtered_tasks,
+            "milestones": plan.milestones
+        }))
+    } else {
+        Ok(serde_json::to_value(plan)?)
     }
 }
 
@@ -1,4 +1,4 @@
-// Deployment tools for copying code from workspace to project path - Fixed with UNC path handling
+// Deployment tools for delivering code from workspace to project path
 
 use adk_core::{Tool, ToolContext};
 use async_trait::async_trait;
@@ -6,18 +6,16 @@ use serde_json::{json, Value};
 use std::sync::Arc;
 use std::fs;
 
+use crate::delivery::{
+    ChangeKind, DeliveryPlan, DeliverySnapshot, FileChange, ProjectBaseline, DELIVERY_CONFLICT_MARKER,
+    DELIVERY_REVIEW_MARKER,
+};
+use crate::interaction::{InputOption, InputResponse, InteractiveBackend, MessageLevel};
 use crate::persistence::IterationStore;
 use crate::persistence::get_iteration_id;
 
-/// Helper function to strip UNC path prefix on Windows
-fn strip_unc_prefix(path: &std::path::Path) -> std::path::PathBuf {
-    let path_str = path.display().to_string();
-    if path_str.starts_with(r"\\?\\") {
-        std::path::PathBuf::from(&path_str[4..])
-    } else {
-        path.to_path_buf()
-    }
-}
+/// Diff lines shown in the transcript per reviewed file
+const DIFF_PREVIEW_LINES: usize = 40;
 
 /// CopyWorkspaceToProjectTool - Copy code from iteration workspace to project path
 /// This should be used in Delivery stage to finalize the project
@@ -30,9 +28,11 @@ impl Tool for CopyWorkspaceToProjectTool {
     }
 
     fn description(&self) -> &str {
-        "Copy all code files from iteration workspace to the project root directory. \
+        "Deliver the iteration workspace to the project root directory. \
          This is used in the Delivery stage to finalize the project. \
-         Only copies source code files (html, css, js, etc.), not configuration or hidden files."
+         The user reviews every added, modified and deleted file before it is applied, \
+         and a rollback snapshot is written first. \
+         When the project root is a git repository, the iteration is published as a branch instead."
     }
 
     fn parameters_schema(&self) -> Option<Value> {
@@ -69,7 +69,7 @@ impl Tool for CopyWorkspaceToProjectTool {
             .map_err(|e| adk_core::AdkError::tool(format!("Failed to get workspace path: {}", e)))?;
 
         // Get project root directory
-        let project_root = std::env::current_dir()
+        let project_root = crate::delivery::project_root()
             .map_err(|e| adk_core::AdkError::tool(format!("Failed to get project root: {}", e)))?;
 
         // Check if workspace exists
@@ -83,7 +83,7 @@ impl Tool for CopyWorkspaceToProjectTool {
         // Check if workspace is empty (safety check)
         let workspace_has_files = workspace_dir.read_dir()
             .ok()
-            .map(|mut entries| entries.next().is_some())
+            .map(|mut entries| entries.any(|e| e.is_ok_and(|e| e.file_name() != ".git")))
             .unwrap_or(false);
 
         if !workspace_has_files {
@@ -95,229 +95,243 @@ impl Tool for CopyWorkspaceToProjectTool {
             }));
         }
 
-        println!("[Delivery] Copying files from workspace to project root...");
-        println!("[Delivery] Workspace: {}", workspace_dir.display());
-        println!("[Delivery] Workspace absolute: {}", workspace_dir.canonicalize().unwrap_or_else(|_| workspace_dir.clone()).display());
-        println!("[Delivery] Project root: {}", project_root.display());
-        println!("[Delivery] Project root absolute: {}", project_root.canonicalize().unwrap_or_else(|_| project_root.clone()).display());
-        println!("[Delivery] Iteration ID: {}", iteration_id);
-
-        // File extensions to copy (source code files)
-        let extensions_to_copy = vec![
-            ".html", ".htm", ".css", ".js", ".jsx", ".ts", ".tsx",
-            ".json", ".md", ".txt", ".svg", ".png", ".jpg", ".jpeg"
-        ];
-
-        // Protected files and directories that should NEVER be deleted
-        let protected_paths = vec![
-            ".cowork-v2",
-            ".git",
-            ".litho",
-            "litho.docs",
-            ".gitignore",
-            ".gitattributes",
-            ".zed",
-            "AGENT.md",
-            ".vscode",
-            ".idea",
-            "config.toml",
-            "Cargo.toml",
-            "Cargo.lock",
-            "README.md",
-            "LICENSE",
-            ".gitignore",
-            ".DS_Store",
-        ];
-
-        // Step 1: Clean up obsolete files in project root
-        println!("[Delivery] Step 1: Cleaning up obsolete files in project root...");
-        let mut deleted_files = Vec::new();
-        let mut protected_skipped = Vec::new();
-
-        for entry in walkdir::WalkDir::new(&project_root)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
+        // Git projects receive the iteration as a branch plus a patch series
+        if crate::git::is_branch_delivery_enabled()
+            && crate::git::is_work_tree(&project_root)
+            && let Some(repo) = crate::git::WorkspaceRepo::open(&workspace_dir)
         {
-            let dest_path = entry.path();
+            return deliver_as_branch(&repo, &iteration_id, &project_root)
+                .map_err(|e| adk_core::AdkError::tool(format!("Git delivery failed: {}", e)));
+        }
 
-            // Skip directories (we'll delete them when they become empty)
-            if dest_path.is_dir() {
-                continue;
-            }
+        deliver_reviewed(&iteration_id, &workspace_dir, &project_root)
+            .await
+            .map_err(|e| adk_core::AdkError::tool(format!("Delivery failed: {}", e)))
+    }
+}
 
-            // Get relative path from project root
-            // Handle UNC path prefixes on Windows
-            let dest_path_stripped = strip_unc_prefix(&dest_path);
-            let project_root_stripped = strip_unc_prefix(&project_root);
-            
-            let rel_path = dest_path_stripped.strip_prefix(&project_root_stripped)
-                .unwrap_or(&dest_path_stripped);
-
-            // Check if this is a protected file/directory
-            let path_str = rel_path.to_string_lossy().to_string();
-            let is_protected = protected_paths.iter().any(|protected| {
-                // Exact match for files
-                if path_str == *protected {
-                    return true;
-                }
-                // Check if it's inside a protected directory
-                path_str.starts_with(&format!("{}/", protected))
-            });
-
-            if is_protected {
-                protected_skipped.push(path_str.clone());
-                println!("[Delivery] Skipped protected: {}", path_str);
-                continue;
-            }
+/// How the remaining files are decided during a review
+#[derive(Clone, Copy, PartialEq)]
+enum ReviewMode {
+    Ask,
+    AcceptRest,
+    RejectRest,
+}
 
-            // IMPORTANT: Skip files that are inside .cowork-v2 directory
-            // These are workspace files and should NOT be deleted
-            // Check for both forward and backward slashes
-            if path_str.starts_with(".cowork-v2/") || path_str.starts_with(".cowork-v2\\") {
-                protected_skipped.push(path_str.clone());
-                println!("[Delivery] Skipped workspace file: {}", path_str);
-                continue;
-            }
-
-            // Check if file exists in workspace
-            let src_path = workspace_dir.join(&rel_path);
-            let src_path_exists = src_path.exists();
-
-            println!("[Delivery] Checking file: {} -> Workspace path: {} (exists: {})",
-                     path_str, src_path.display(), src_path_exists);
-
-            if !src_path_exists {
-                // File doesn't exist in workspace, delete it
-                println!("[Delivery] File {} not found in workspace, marking for deletion", path_str);
-                match fs::remove_file(&dest_path) {
-                    Ok(_) => {
-                        deleted_files.push(path_str.clone());
-                        println!("[Delivery] Deleted obsolete file: {}", path_str);
-                    }
-                    Err(e) => {
-                        println!("[Delivery] Warning: Failed to delete {}: {}", path_str, e);
-                    }
-                }
+/// Ask about each change; returns the accepted ones, or `None` when the
+/// user cancelled the delivery
+async fn review_changes<'a>(
+    interaction: &Arc<dyn InteractiveBackend + Send + Sync>,
+    plan: &'a DeliveryPlan,
+) -> Option<Vec<&'a FileChange>> {
+    let options = vec![
+        InputOption {
+            id: "accept".to_string(),
+            label: "Apply".to_string(),
+            description: Some("Apply this change to the project".to_string()),
+        },
+        InputOption {
+            id: "reject".to_string(),
+            label: "Skip".to_string(),
+            description: Some("Leave the project file as it is".to_string()),
+        },
+        InputOption {
+            id: "accept_rest".to_string(),
+            label: "Apply all remaining".to_string(),
+            description: Some("Conflicting files are still asked".to_string()),
+        },
+        InputOption {
+            id: "reject_rest".to_string(),
+            label: "Skip all remaining".to_string(),
+            description: None,
+        },
+    ];
+
+    let mut accepted = Vec::new();
+    let mut mode = ReviewMode::Ask;
+    let total = plan.changes.len();
+
+    for (i, change) in plan.changes.iter().enumerate() {
+        let decided = match mode {
+            ReviewMode::AcceptRest if !change.conflict => Some(true),
+            ReviewMode::RejectRest => Some(false),
+            _ => None,
+        };
+        if let Some(accept) = decided {
+            if accept {
+                accepted.push(change);
             }
+            continue;
         }
 
-        // Clean up empty directories
-        for entry in walkdir::WalkDir::new(&project_root)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().is_dir())
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-        {
-            let dir_path = entry.path();
-
-            // Skip protected directories
-            let dir_path_stripped = strip_unc_prefix(&dir_path);
-            let project_root_stripped = strip_unc_prefix(&project_root);
-            
-            let rel_path = dir_path_stripped.strip_prefix(&project_root_stripped)
-                .unwrap_or(&dir_path_stripped);
-
-            let path_str = rel_path.to_string_lossy().to_string();
-            let is_protected = protected_paths.iter().any(|protected| {
-                path_str == *protected || path_str.starts_with(&format!("{}/", protected))
-            });
-
-            if is_protected {
-                continue;
-            }
-
-            // IMPORTANT: Skip directories inside .cowork-v2
-            if path_str.starts_with(".cowork-v2/") {
-                continue;
-            }
+        let mut prompt = format!(
+            "{}{}] [{}/{}] {} {} (+{} -{})",
+            DELIVERY_REVIEW_MARKER,
+            change.path,
+            i + 1,
+            total,
+            change.kind.label(),
+            change.path,
+            change.lines_added,
+            change.lines_removed
+        );
+        if change.conflict {
+            prompt.push_str(&format!(
+                "\n⚠️ {} The project file was changed since this iteration started; applying overwrites those changes.",
+                DELIVERY_CONFLICT_MARKER
+            ));
+        }
 
-            // Check if directory is empty
-            if dir_path.read_dir().ok().map(|mut it| it.next().is_none()).unwrap_or(false) {
-                match fs::remove_dir(&dir_path) {
-                    Ok(_) => {
-                        println!("[Delivery] Deleted empty directory: {}", path_str);
-                    }
-                    Err(e) => {
-                        println!("[Delivery] Warning: Failed to delete directory {}: {}", path_str, e);
-                    }
+        let preview: Vec<&str> = change.diff.lines().take(DIFF_PREVIEW_LINES).collect();
+        let more = change.diff.lines().count().saturating_sub(DIFF_PREVIEW_LINES);
+        let mut message = preview.join("\n");
+        if more > 0 {
+            message.push_str(&format!("\n… {} more lines", more));
+        }
+        interaction.show_message(MessageLevel::Info, message).await;
+
+        match interaction.request_input(&prompt, options.clone(), Some(change.diff.clone())).await {
+            Ok(InputResponse::Selection(id)) => match id.as_str() {
+                "accept" => accepted.push(change),
+                "accept_rest" => {
+                    accepted.push(change);
+                    mode = ReviewMode::AcceptRest;
+                }
+                "reject_rest" => mode = ReviewMode::RejectRest,
+                _ => {}
+            },
+            Ok(InputResponse::Text(text)) => {
+                if matches!(text.trim().to_lowercase().as_str(), "y" | "yes" | "apply" | "accept") {
+                    accepted.push(change);
                 }
             }
+            Ok(InputResponse::Cancel) | Err(_) => return None,
         }
+    }
+    Some(accepted)
+}
 
-        // Step 2: Copy files from workspace to project root
-        println!("[Delivery] Step 2: Copying files from workspace...");
-        let mut copied_files = Vec::new();
-        let mut skipped_files = Vec::new();
-
-        for entry in walkdir::WalkDir::new(&workspace_dir)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let src_path = entry.path();
-
-            // Skip directories
-            if src_path.is_dir() {
-                continue;
-            }
-
-            // Get relative path from workspace
-            // Handle UNC path prefixes on Windows
-            let src_path_stripped = strip_unc_prefix(&src_path);
-            let workspace_dir_stripped = strip_unc_prefix(&workspace_dir);
-            
-            let rel_path = src_path_stripped.strip_prefix(&workspace_dir_stripped)
-                .unwrap_or(&src_path_stripped);
-
-            // Check if file should be copied
-            let should_copy = rel_path.extension()
-                .and_then(|ext| ext.to_str())
-                .map(|ext| extensions_to_copy.iter().any(|e| *e == format!(".{}", ext)))
-                .unwrap_or(false);
-
-            if !should_copy {
-                skipped_files.push(rel_path.to_string_lossy().to_string());
-                continue;
-            }
-
-            // Determine destination path
-            let dest_path = project_root.join(rel_path);
+/// Review the workspace/project diff file by file and apply the accepted
+/// changes after writing a rollback snapshot
+async fn deliver_reviewed(
+    iteration_id: &str,
+    workspace_dir: &std::path::Path,
+    project_root: &std::path::Path,
+) -> anyhow::Result<Value> {
+    let iteration_dir = IterationStore::new().iteration_path(iteration_id)?;
+    let baseline = ProjectBaseline::load(&iteration_dir)?;
+    let plan = DeliveryPlan::compute(workspace_dir, project_root, baseline.as_ref())?;
+
+    if plan.changes.is_empty() {
+        return Ok(json!({
+            "status": "success",
+            "message": "The project root already matches the workspace; nothing to deliver.",
+            "project_root": project_root.to_string_lossy().to_string()
+        }));
+    }
 
-            // Create parent directories if needed
-            if let Some(parent) = dest_path.parent() {
-                fs::create_dir_all(parent)
-                    .map_err(|e| adk_core::AdkError::tool(format!("Failed to create directory: {}", e)))?;
+    let accepted = match super::hitl_content_tools::get_interaction_backend() {
+        Some(interaction) => {
+            interaction
+                .show_message(
+                    MessageLevel::Info,
+                    format!(
+                        "📦 Delivery review: {} added, {} modified, {} deleted, {} conflicting{}",
+                        plan.changes.iter().filter(|c| c.kind == ChangeKind::Added).count(),
+                        plan.changes.iter().filter(|c| c.kind == ChangeKind::Modified).count(),
+                        plan.changes.iter().filter(|c| c.kind == ChangeKind::Deleted).count(),
+                        plan.changes.iter().filter(|c| c.conflict).count(),
+                        if plan.has_baseline { "" } else { " (no baseline recorded; conflicts cannot be detected)" }
+                    ),
+                )
+                .await;
+            match review_changes(&interaction, &plan).await {
+                Some(accepted) => accepted,
+                None => {
+                    return Ok(json!({
+                        "status": "cancelled",
+                        "message": "Delivery was cancelled by the user; the project root was not changed."
+                    }));
+                }
             }
-
-            // Copy file
-            fs::copy(&src_path, &dest_path)
-                .map_err(|e| adk_core::AdkError::tool(format!("Failed to copy {}: {}", rel_path.display(), e)))?;
-
-            copied_files.push(rel_path.to_string_lossy().to_string());
-            println!("[Delivery] Copied: {}", rel_path.display());
         }
+        // Nobody to ask: never overwrite the user's own changes
+        None => plan.changes.iter().filter(|c| !c.conflict).collect(),
+    };
+
+    let skipped: Vec<&FileChange> = plan
+        .changes
+        .iter()
+        .filter(|c| !accepted.iter().any(|a| a.path == c.path))
+        .collect();
+
+    if accepted.is_empty() {
+        return Ok(json!({
+            "status": "success",
+            "message": "No changes were accepted; the project root was not changed.",
+            "skipped": skipped
+        }));
+    }
 
-        println!("[Delivery] Deployment complete: {} files deleted, {} files copied, {} files skipped, {} protected files skipped",
-                 deleted_files.len(), copied_files.len(), skipped_files.len(), protected_skipped.len());
+    let snapshot = plan.apply(&accepted, &DeliverySnapshot::new_dir(&iteration_dir))?;
+    println!(
+        "[Delivery] Applied {} changes, skipped {}; rollback snapshot: {}",
+        accepted.len(),
+        skipped.len(),
+        snapshot.dir.display()
+    );
+
+    Ok(json!({
+        "status": "success",
+        "message": format!(
+            "Applied {} of {} changes to the project root ({} skipped). Undo with `cowork rollback-delivery {}`.",
+            accepted.len(),
+            plan.changes.len(),
+            skipped.len(),
+            iteration_id
+        ),
+        "applied": accepted,
+        "skipped": skipped,
+        "rollback_snapshot": snapshot.dir.to_string_lossy().to_string(),
+        "project_root": project_root.to_string_lossy().to_string()
+    }))
+}
 
-        Ok(json!({
-            "status": "success",
-            "message": format!(
-                "Deployed {} files from workspace to project root ({} deleted, {} protected)",
-                copied_files.len(),
-                deleted_files.len(),
-                protected_skipped.len()
-            ),
-            "deleted_files": deleted_files,
-            "copied_files": copied_files,
-            "skipped_files": skipped_files,
-            "protected_files": protected_skipped,
-            "workspace": workspace_dir.to_string_lossy().to_string(),
-            "project_root": project_root.to_string_lossy().to_string()
-        }))
+/// Publish the workspace history as `cowork/<iteration>` in the project
+/// repository and export the iteration's commits as patches next to its
+/// artifacts. The project's working tree is not touched.
+fn deliver_as_branch(
+    repo: &crate::git::WorkspaceRepo,
+    iteration_id: &str,
+    project_root: &std::path::Path,
+) -> anyhow::Result<Value> {
+    repo.commit_all(&format!("delivery: final changes\n\nIteration: {}\n", iteration_id), None)?;
+
+    let branch = crate::git::iteration_branch(iteration_id);
+    let commit = repo.publish_branch(project_root, &branch)?;
+
+    let patches_dir = IterationStore::new().iteration_path(iteration_id)?.join("patches");
+    if patches_dir.exists() {
+        fs::remove_dir_all(&patches_dir)?;
     }
+    let patches = repo.export_patches(&patches_dir)?;
+    let commits = repo.iteration_commits()?;
+
+    println!("[Delivery] Published branch {} ({}) with {} commits", branch, commit, commits.len());
+
+    Ok(json!({
+        "status": "success",
+        "mode": "git_branch",
+        "message": format!(
+            "Published {} commits as branch '{}' in the project repository. Review and merge it with `git merge {}` (add --allow-unrelated-histories for the first iteration), or apply the patches with `git am`.",
+            commits.len(),
+            branch,
+            branch
+        ),
+        "branch": branch,
+        "commit": commit,
+        "commits": commits.iter().map(|(hash, subject)| json!({"hash": hash, "subject": subject})).collect::<Vec<_>>(),
+        "patches": patches.iter().map(|p| p.to_string_lossy().to_string()).collect::<Vec<_>>(),
+        "project_root": project_root.to_string_lossy().to_string()
+    }))
 }
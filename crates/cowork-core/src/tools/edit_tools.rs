@@ -0,0 +1,835 @@
+// Edit Tools - Change part of a file instead of rewriting all of it
+//
+// `edit_file` replaces one exact occurrence of a snippet; `apply_patch` applies
+// a unified diff to one or more files. Both fall back to whitespace-insensitive
+// matching when the text does not match exactly, and explain what did not
+// match so the agent can re-read the file and retry. A patch is applied to
+// every file in memory first: if any hunk fails, nothing is written.
+
+use adk_core::{Tool, ToolContext};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::file_tools::validate_path_security_within_workspace;
+use super::get_required_string_param;
+use crate::persistence::{get_iteration_id, IterationStore};
+
+/// Context lines a hunk may lose at each end and still apply
+const MAX_CONTEXT_FUZZ: usize = 2;
+
+/// File lines shown around the closest match when a snippet or hunk is not found
+const NEARBY_LINES: usize = 3;
+
+fn workspace_dir(action: &str) -> adk_core::Result<PathBuf> {
+    let iteration_id = get_iteration_id().ok_or_else(|| {
+        adk_core::AdkError::tool(format!(
+            "Iteration ID not set. Cannot {} without an active iteration.",
+            action
+        ))
+    })?;
+    IterationStore::new()
+        .workspace_path(&iteration_id)
+        .map_err(|e| adk_core::AdkError::tool(format!("Failed to get workspace path: {}", e)))
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+/// How strictly lines are compared, from exact to whitespace-insensitive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Strictness {
+    Exact,
+    IgnoreTrailingSpace,
+    IgnoreSurroundingSpace,
+}
+
+impl Strictness {
+    const ALL: [Strictness; 3] = [
+        Strictness::Exact,
+        Strictness::IgnoreTrailingSpace,
+        Strictness::IgnoreSurroundingSpace,
+    ];
+
+    fn lines_equal(self, a: &str, b: &str) -> bool {
+        match self {
+            Strictness::Exact => a == b,
+            Strictness::IgnoreTrailingSpace => a.trim_end() == b.trim_end(),
+            Strictness::IgnoreSurroundingSpace => a.trim() == b.trim(),
+        }
+    }
+}
+
+/// Text split into lines, remembering the line ending to write back
+struct Lines {
+    lines: Vec<String>,
+    eol: &'static str,
+    trailing_newline: bool,
+}
+
+impl Lines {
+    fn parse(text: &str) -> Self {
+        let eol = if text.contains("\r\n") { "\r\n" } else { "\n" };
+        let trailing_newline = text.is_empty() || text.ends_with('\n');
+        let lines = text
+            .lines()
+            .map(|l| l.strip_suffix('\r').unwrap_or(l).to_string())
+            .collect();
+        Self { lines, eol, trailing_newline }
+    }
+
+    fn render(&self) -> String {
+        let mut text = self.lines.join(self.eol);
+        if self.trailing_newline && !self.lines.is_empty() {
+            text.push_str(self.eol);
+        }
+        text
+    }
+
+    fn matches_at(&self, at: usize, block: &[String], strictness: Strictness) -> bool {
+        at + block.len() <= self.lines.len()
+            && block
+                .iter()
+                .zip(&self.lines[at..])
+                .all(|(b, l)| strictness.lines_equal(l, b))
+    }
+
+    /// All positions where `block` matches
+    fn find_all(&self, block: &[String], strictness: Strictness) -> Vec<usize> {
+        if block.is_empty() || block.len() > self.lines.len() {
+            return Vec::new();
+        }
+        (0..=self.lines.len() - block.len())
+            .filter(|&at| self.matches_at(at, block, strictness))
+            .collect()
+    }
+
+    /// Position from `from` onwards where `block` matches, closest to `hint` first
+    fn find_near(&self, block: &[String], hint: usize, from: usize, strictness: Strictness) -> Option<usize> {
+        let last = self.lines.len().checked_sub(block.len())?;
+        let hint = hint.clamp(from.min(last), last);
+        (0..=last)
+            .flat_map(|distance| [hint.checked_sub(distance), hint.checked_add(distance)])
+            .flatten()
+            .filter(|&at| at >= from && at <= last)
+            .find(|&at| self.matches_at(at, block, strictness))
+    }
+
+    /// Start of the region most similar to `block`, for error messages
+    fn closest(&self, block: &[String]) -> Option<usize> {
+        let len = block.len().min(self.lines.len());
+        (0..=self.lines.len() - len)
+            .map(|at| {
+                let score = block
+                    .iter()
+                    .zip(&self.lines[at..at + len])
+                    .filter(|(b, l)| !b.trim().is_empty() && b.trim() == l.trim())
+                    .count();
+                (score, at)
+            })
+            .filter(|&(score, _)| score > 0)
+            .max_by_key(|&(score, at)| (score, std::cmp::Reverse(at)))
+            .map(|(_, at)| at)
+    }
+
+    /// Numbered file lines around `at`, for error messages
+    fn excerpt(&self, at: usize, len: usize) -> String {
+        let start = at.saturating_sub(NEARBY_LINES);
+        let end = (at + len + NEARBY_LINES).min(self.lines.len());
+        (start..end)
+            .map(|i| format!("{:>5} | {}", i + 1, self.lines[i]))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn to_lines(text: &str) -> Vec<String> {
+    Lines::parse(text).lines
+}
+
+/// Leading whitespace of the first non-blank line
+fn indentation(lines: &[String]) -> &str {
+    lines
+        .iter()
+        .find(|l| !l.trim().is_empty())
+        .map(|l| &l[..l.len() - l.trim_start().len()])
+        .unwrap_or("")
+}
+
+/// Re-indent `new` from the indentation `old` was written with to the one the file has
+fn reindent(new: Vec<String>, written: &str, actual: &str) -> Vec<String> {
+    if written == actual {
+        return new;
+    }
+    new.into_iter()
+        .map(|line| match line.strip_prefix(written) {
+            Some(rest) => format!("{}{}", actual, rest),
+            None => line,
+        })
+        .collect()
+}
+
+// ============================================================================
+// Search / replace
+// ============================================================================
+
+/// Result of a search/replace
+#[derive(Debug)]
+struct Replacement {
+    content: String,
+    occurrences: usize,
+    first_line: usize,
+    whitespace_insensitive: bool,
+}
+
+/// Replace `old` with `new` in `content`; `old` must occur exactly once unless `replace_all`
+fn replace_in(content: &str, old: &str, new: &str, replace_all: bool) -> Result<Replacement, String> {
+    if old.is_empty() {
+        return Err("old_string is empty. Give the exact text to replace, or use write_file to create a file.".to_string());
+    }
+    if old == new {
+        return Err("old_string and new_string are identical; nothing to change.".to_string());
+    }
+
+    let mut file = Lines::parse(content);
+    let (old, new) = if file.eol == "\r\n" {
+        (old.replace("\r\n", "\n").replace('\n', "\r\n"), new.replace("\r\n", "\n").replace('\n', "\r\n"))
+    } else {
+        (old.to_string(), new.to_string())
+    };
+
+    // Exact substring match first
+    let positions: Vec<usize> = content.match_indices(&old).map(|(i, _)| i).collect();
+    if positions.len() == 1 || (replace_all && !positions.is_empty()) {
+        let first_line = content[..positions[0]].matches('\n').count() + 1;
+        let content = if replace_all { content.replace(&old, &new) } else { content.replacen(&old, &new, 1) };
+        return Ok(Replacement { content, occurrences: positions.len(), first_line, whitespace_insensitive: false });
+    }
+    if positions.len() > 1 {
+        let lines: Vec<String> = positions
+            .iter()
+            .map(|&i| (content[..i].matches('\n').count() + 1).to_string())
+            .collect();
+        return Err(format!(
+            "old_string occurs {} times (lines {}). Include more surrounding lines to make it unique, or set replace_all to true.",
+            positions.len(),
+            lines.join(", ")
+        ));
+    }
+
+    // Then line by line, ignoring whitespace at the ends of lines
+    let old_lines = to_lines(&old);
+    for strictness in [Strictness::IgnoreTrailingSpace, Strictness::IgnoreSurroundingSpace] {
+        let found = file.find_all(&old_lines, strictness);
+        match found.len() {
+            0 => continue,
+            1 => {}
+            n if !replace_all => {
+                let lines: Vec<String> = found.iter().map(|at| (at + 1).to_string()).collect();
+                return Err(format!(
+                    "old_string matches {} places when ignoring whitespace (lines {}). Include more surrounding lines to make it unique, or set replace_all to true.",
+                    n,
+                    lines.join(", ")
+                ));
+            }
+            _ => {}
+        }
+
+        // Replace from the end so earlier positions stay valid
+        for &at in found.iter().rev() {
+            let actual = file.lines[at..at + old_lines.len()].to_vec();
+            let new_lines = reindent(to_lines(&new), indentation(&old_lines), indentation(&actual));
+            file.lines.splice(at..at + old_lines.len(), new_lines);
+        }
+        return Ok(Replacement {
+            content: file.render(),
+            occurrences: found.len(),
+            first_line: found[0] + 1,
+            whitespace_insensitive: true,
+        });
+    }
+
+    let mut message = "old_string was not found in the file, even ignoring whitespace.".to_string();
+    if let Some(at) = file.closest(&old_lines) {
+        message.push_str(&format!(
+            " The closest text is around line {}:\n{}\nRe-read the file and copy old_string exactly.",
+            at + 1,
+            file.excerpt(at, old_lines.len())
+        ));
+    } else {
+        message.push_str(" Re-read the file with read_file and copy old_string exactly.");
+    }
+    Err(message)
+}
+
+// ============================================================================
+// Unified diff
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    Context,
+    Remove,
+    Add,
+}
+
+#[derive(Debug)]
+struct Hunk {
+    header: String,
+    /// 1-based start line on the old side, when the header has one
+    old_start: Option<usize>,
+    lines: Vec<(LineKind, String)>,
+    /// Whether the file ends with a newline after this hunk, when it says so
+    /// with "\ No newline at end of file"
+    newline_at_end: Option<bool>,
+}
+
+impl Hunk {
+    fn old_side(&self, lines: &[(LineKind, String)]) -> Vec<String> {
+        lines.iter().filter(|(k, _)| *k != LineKind::Add).map(|(_, l)| l.clone()).collect()
+    }
+
+    fn new_side(&self, lines: &[(LineKind, String)]) -> Vec<String> {
+        lines.iter().filter(|(k, _)| *k != LineKind::Remove).map(|(_, l)| l.clone()).collect()
+    }
+
+    /// Lines with up to `front` and `back` leading/trailing context lines dropped,
+    /// or None when the hunk has fewer context lines at that end
+    fn trimmed(&self, front: usize, back: usize) -> Option<&[(LineKind, String)]> {
+        let leading = self.lines.iter().take_while(|(k, _)| *k == LineKind::Context).count();
+        let trailing = self.lines.iter().rev().take_while(|(k, _)| *k == LineKind::Context).count();
+        (front <= leading && back <= trailing && front + back < self.lines.len())
+            .then(|| &self.lines[front..self.lines.len() - back])
+    }
+}
+
+#[derive(Debug)]
+struct FilePatch {
+    /// None for /dev/null
+    old_path: Option<String>,
+    new_path: Option<String>,
+    hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    fn display_path(&self) -> &str {
+        self.new_path.as_deref().or(self.old_path.as_deref()).unwrap_or("?")
+    }
+}
+
+fn parse_patch_path(line: &str) -> Option<String> {
+    // Drop a tab-separated timestamp and the a/ or b/ prefix
+    let path = line.split('\t').next().unwrap_or(line).trim();
+    if path == "/dev/null" {
+        return None;
+    }
+    let path = path.strip_prefix("a/").or_else(|| path.strip_prefix("b/")).unwrap_or(path);
+    Some(path.to_string())
+}
+
+fn parse_hunk_start(header: &str) -> Option<usize> {
+    // "@@ -12,7 +12,9 @@ fn main" → 12
+    let old = header.strip_prefix("@@")?.trim_start().strip_prefix('-')?;
+    old.split([',', ' ']).next()?.parse().ok()
+}
+
+fn parse_patch(patch: &str) -> Result<Vec<FilePatch>, String> {
+    let lines: Vec<&str> = patch.lines().map(|l| l.strip_suffix('\r').unwrap_or(l)).collect();
+    let is_file_header = |i: usize| {
+        lines[i].starts_with("--- ") && lines.get(i + 1).is_some_and(|next| next.starts_with("+++ "))
+    };
+
+    let mut files: Vec<FilePatch> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if is_file_header(i) {
+            files.push(FilePatch {
+                old_path: parse_patch_path(&line[4..]),
+                new_path: parse_patch_path(&lines[i + 1][4..]),
+                hunks: Vec::new(),
+            });
+            i += 2;
+            continue;
+        }
+        if line.starts_with("@@") {
+            let file = files
+                .last_mut()
+                .ok_or_else(|| format!("Hunk '{}' comes before any '--- a/<path>' / '+++ b/<path>' header.", line))?;
+            let mut hunk = Hunk {
+                header: line.to_string(),
+                old_start: parse_hunk_start(line),
+                lines: Vec::new(),
+                newline_at_end: None,
+            };
+            i += 1;
+            while i < lines.len() && !lines[i].starts_with("@@") && !lines[i].starts_with("diff ") && !is_file_header(i) {
+                let body = lines[i];
+                match body.chars().next() {
+                    Some(' ') => hunk.lines.push((LineKind::Context, body[1..].to_string())),
+                    Some('-') => hunk.lines.push((LineKind::Remove, body[1..].to_string())),
+                    Some('+') => hunk.lines.push((LineKind::Add, body[1..].to_string())),
+                    Some('\\') => match hunk.lines.last() {
+                        // The old side had no newline; the new side has one unless marked too
+                        Some((LineKind::Remove, _)) => {
+                            hunk.newline_at_end.get_or_insert(true);
+                        }
+                        _ => hunk.newline_at_end = Some(false),
+                    },
+                    // Blank context lines often lose their leading space
+                    None => hunk.lines.push((LineKind::Context, String::new())),
+                    Some(_) => {
+                        return Err(format!(
+                            "Line {} of the patch ('{}') in hunk '{}' does not start with ' ', '-' or '+'.",
+                            i + 1,
+                            body,
+                            hunk.header
+                        ));
+                    }
+                }
+                i += 1;
+            }
+            // Blank lines at the very end of the patch are not part of the hunk
+            while i == lines.len() && hunk.lines.last().is_some_and(|(k, l)| *k == LineKind::Context && l.is_empty()) {
+                hunk.lines.pop();
+            }
+            if hunk.lines.iter().all(|(k, _)| *k == LineKind::Context) {
+                return Err(format!("Hunk '{}' in {} changes nothing.", hunk.header, file.display_path()));
+            }
+            file.hunks.push(hunk);
+            continue;
+        }
+        // diff --git, index, mode lines and commentary
+        i += 1;
+    }
+
+    if files.is_empty() {
+        return Err("No file headers found. A unified diff needs '--- a/<path>' and '+++ b/<path>' lines followed by '@@' hunks.".to_string());
+    }
+    if let Some(file) = files.iter().find(|f| f.hunks.is_empty()) {
+        return Err(format!("{} has a header but no '@@' hunks.", file.display_path()));
+    }
+    Ok(files)
+}
+
+/// How a hunk was placed
+#[derive(Debug, Default)]
+struct HunkReport {
+    /// Lines between where the header said and where it applied
+    offset: isize,
+    /// Context lines dropped to make it match
+    fuzz: usize,
+    whitespace_insensitive: bool,
+}
+
+/// Apply the hunks of one file to its content
+fn apply_hunks(path: &str, content: &str, hunks: &[Hunk]) -> Result<(String, Vec<HunkReport>), String> {
+    let mut file = Lines::parse(content);
+    let mut reports = Vec::new();
+    // Lines added minus lines removed by the hunks applied so far
+    let mut shift: isize = 0;
+    // Hunks apply in order; the next cannot start before the previous ended
+    let mut from = 0;
+
+    for (n, hunk) in hunks.iter().enumerate() {
+        let hint = hunk
+            .old_start
+            .map(|start| (start.saturating_sub(1) as isize + shift).max(0) as usize)
+            .unwrap_or(from);
+
+        let placed = (0..=MAX_CONTEXT_FUZZ)
+            .flat_map(|fuzz| (0..=fuzz).map(move |front| (front, fuzz - front)))
+            .filter_map(|(front, back)| hunk.trimmed(front, back).map(|lines| (front + back, front, lines)))
+            .find_map(|(fuzz, front, lines)| {
+                let old = hunk.old_side(lines);
+                if old.is_empty() {
+                    // Pure insertion: trust the header
+                    return Some((hint.clamp(from, file.lines.len()), fuzz, front, lines, Strictness::Exact));
+                }
+                Strictness::ALL.into_iter().find_map(|strictness| {
+                    let at = file.find_near(&old, hint.saturating_add(front), from, strictness)?;
+                    Some((at, fuzz, front, lines, strictness))
+                })
+            });
+
+        let Some((at, fuzz, front, lines, strictness)) = placed else {
+            let old = hunk.old_side(&hunk.lines);
+            let mut message = format!(
+                "Hunk {} of {} ('{}') does not match the file. Expected these lines:\n{}",
+                n + 1,
+                path,
+                hunk.header,
+                old.iter().map(|l| format!("      | {}", l)).collect::<Vec<_>>().join("\n")
+            );
+            if let Some(near) = file.closest(&old) {
+                message.push_str(&format!("\nClosest text in the file, around line {}:\n{}", near + 1, file.excerpt(near, old.len())));
+            }
+            message.push_str("\nRe-read the file and regenerate this hunk against its current content, or use edit_file.");
+            return Err(message);
+        };
+
+        let old = hunk.old_side(lines);
+        let actual = file.lines[at..at + old.len()].to_vec();
+        let new = if strictness == Strictness::Exact {
+            hunk.new_side(lines)
+        } else {
+            // Keep the file's own whitespace on context lines
+            let mut new = Vec::new();
+            let mut actual_lines = actual.iter();
+            for (kind, line) in lines {
+                match kind {
+                    LineKind::Context => new.push(actual_lines.next().cloned().unwrap_or_default()),
+                    LineKind::Remove => {
+                        actual_lines.next();
+                    }
+                    LineKind::Add => new.push(line.clone()),
+                }
+            }
+            new
+        };
+
+        let header_at = hint.saturating_add(front) as isize;
+        reports.push(HunkReport {
+            offset: at as isize - header_at,
+            fuzz,
+            whitespace_insensitive: strictness != Strictness::Exact,
+        });
+        shift += new.len() as isize - old.len() as isize;
+        from = at + new.len();
+        file.lines.splice(at..at + old.len(), new);
+
+        if let Some(newline) = hunk.newline_at_end
+            && from == file.lines.len()
+        {
+            file.trailing_newline = newline;
+        }
+    }
+
+    Ok((file.render(), reports))
+}
+
+/// What applying a patch does to one file
+#[derive(Debug)]
+enum FileChange {
+    Write { path: PathBuf, content: String, created: bool },
+    Delete { path: PathBuf },
+}
+
+/// Check and apply every file of a patch in memory
+fn plan_patch(patch: &str, workspace_dir: &Path) -> Result<(Vec<FileChange>, Vec<Value>), String> {
+    let files = parse_patch(patch)?;
+    let mut changes = Vec::new();
+    let mut summary = Vec::new();
+
+    for file in &files {
+        let display = file.display_path().to_string();
+        let mut resolve = |path: &str| -> Result<PathBuf, String> {
+            validate_path_security_within_workspace(path, workspace_dir).map(|p| workspace_dir.join(p))
+        };
+        let old_full = file.old_path.as_deref().map(&mut resolve).transpose()?;
+        let new_full = file.new_path.as_deref().map(&mut resolve).transpose()?;
+
+        let original = match &old_full {
+            Some(path) => fs::read_to_string(path).map_err(|e| {
+                format!("Cannot read {}: {}. Paths in the patch are relative to the workspace root.", display, e)
+            })?,
+            None => {
+                if let Some(path) = &new_full
+                    && path.exists()
+                {
+                    return Err(format!(
+                        "{} already exists but the patch creates it from /dev/null. Diff against the current file instead.",
+                        display
+                    ));
+                }
+                String::new()
+            }
+        };
+
+        let (content, reports) = apply_hunks(&display, &original, &file.hunks)?;
+        let fuzzy = reports.iter().filter(|r| r.fuzz > 0 || r.whitespace_insensitive).count();
+        let moved = reports.iter().filter(|r| r.offset != 0).count();
+
+        match (&old_full, &new_full) {
+            (Some(old), None) => {
+                changes.push(FileChange::Delete { path: old.clone() });
+                summary.push(json!({ "path": display, "action": "deleted" }));
+            }
+            (old, Some(new)) => {
+                changes.push(FileChange::Write { path: new.clone(), content, created: old.is_none() });
+                if let Some(old) = old
+                    && old != new
+                {
+                    changes.push(FileChange::Delete { path: old.clone() });
+                }
+                summary.push(json!({
+                    "path": display,
+                    "action": match old {
+                        None => "created",
+                        Some(old) if old != new => "renamed",
+                        Some(_) => "modified",
+                    },
+                    "hunks": reports.len(),
+                    "hunks_at_other_lines": moved,
+                    "hunks_matched_fuzzily": fuzzy
+                }));
+            }
+            (None, None) => return Err("A patch header has /dev/null on both sides.".to_string()),
+        }
+    }
+
+    Ok((changes, summary))
+}
+
+fn write_changes(changes: &[FileChange]) -> std::io::Result<()> {
+    for change in changes {
+        match change {
+            FileChange::Write { path, content, created } => {
+                if *created && let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)?;
+            }
+            FileChange::Delete { path } => fs::remove_file(path)?,
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// EditFileTool
+// ============================================================================
+
+pub struct EditFileTool;
+
+#[async_trait]
+impl Tool for EditFileTool {
+    fn name(&self) -> &str {
+        "edit_file"
+    }
+
+    fn description(&self) -> &str {
+        "Replace one occurrence of old_string with new_string in an existing file, without resending the whole file. \
+         old_string must match exactly one place (include a few surrounding lines to make it unique) unless \
+         replace_all is true. Falls back to ignoring indentation and trailing spaces if there is no exact match. \
+         SECURITY: Only works within current directory."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File to edit (must be relative path within current directory)"
+                },
+                "old_string": {
+                    "type": "string",
+                    "description": "Exact text to replace, copied from the file"
+                },
+                "new_string": {
+                    "type": "string",
+                    "description": "Replacement text"
+                },
+                "replace_all": {
+                    "type": "boolean",
+                    "description": "Replace every occurrence instead of requiring a unique one (default: false)"
+                }
+            },
+            "required": ["path", "old_string", "new_string"]
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let path = get_required_string_param(&args, "path")?;
+        let old_string = get_required_string_param(&args, "old_string")?;
+        let new_string = get_required_string_param(&args, "new_string")?;
+        let replace_all = args.get("replace_all").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        super::notify_tool_call("edit_file", &json!({"path": path}));
+
+        let workspace_dir = workspace_dir("edit files")?;
+        let safe_path = match validate_path_security_within_workspace(path, &workspace_dir) {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(json!({
+                    "status": "security_error",
+                    "message": e
+                }));
+            }
+        };
+        let full_path = workspace_dir.join(&safe_path);
+
+        let content = match fs::read_to_string(&full_path) {
+            Ok(content) => content,
+            Err(e) => {
+                return Ok(json!({
+                    "status": "error",
+                    "message": format!("Cannot read {}: {}. Use write_file to create new files.", path, e)
+                }));
+            }
+        };
+
+        let result = match replace_in(&content, old_string, new_string, replace_all) {
+            Ok(replacement) => match fs::write(&full_path, &replacement.content) {
+                Ok(()) => {
+                    println!("✏️  Editing file: {} (line {})", path, replacement.first_line);
+                    Ok(json!({
+                        "status": "success",
+                        "path": path,
+                        "replacements": replacement.occurrences,
+                        "line": replacement.first_line,
+                        "whitespace_insensitive_match": replacement.whitespace_insensitive,
+                        "lines": replacement.content.lines().count()
+                    }))
+                }
+                Err(e) => Ok(json!({
+                    "status": "error",
+                    "message": format!("Failed to write file: {}", e)
+                })),
+            },
+            Err(message) => Ok(json!({
+                "status": "no_match",
+                "path": path,
+                "message": message
+            })),
+        };
+
+        let status = result.as_ref().map(|r| r["status"].clone()).unwrap_or_default();
+        super::notify_tool_result("edit_file", &Ok(json!({"status": status})));
+        result
+    }
+}
+
+// ============================================================================
+// ApplyPatchTool
+// ============================================================================
+
+pub struct ApplyPatchTool;
+
+#[async_trait]
+impl Tool for ApplyPatchTool {
+    fn name(&self) -> &str {
+        "apply_patch"
+    }
+
+    fn description(&self) -> &str {
+        "Apply a unified diff (as produced by `diff -u` or `git diff`) to files in the workspace. \
+         Use '--- a/<path>' / '+++ b/<path>' headers with '@@' hunks; /dev/null creates or deletes a file. \
+         Hunks are located near their line numbers, tolerating shifted lines, whitespace differences and \
+         slightly wrong context. If any hunk fails nothing is written. \
+         SECURITY: Only works within current directory."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff; paths are relative to the workspace root"
+                }
+            },
+            "required": ["patch"]
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let patch = get_required_string_param(&args, "patch")?;
+
+        super::notify_tool_call("apply_patch", &json!({}));
+
+        let workspace_dir = workspace_dir("apply patches")?;
+        let result = match plan_patch(patch, &workspace_dir) {
+            Ok((changes, files)) => match write_changes(&changes) {
+                Ok(()) => {
+                    println!("🩹 Applied patch to {} file(s)", files.len());
+                    Ok(json!({
+                        "status": "success",
+                        "files": files
+                    }))
+                }
+                Err(e) => Ok(json!({
+                    "status": "error",
+                    "message": format!("Failed to write patched files: {}", e)
+                })),
+            },
+            Err(message) => Ok(json!({
+                "status": "failed",
+                "message": message,
+                "files_changed": 0
+            })),
+        };
+
+        let status = result.as_ref().map(|r| r["status"].clone()).unwrap_or_default();
+        super::notify_tool_result("apply_patch", &Ok(json!({"status": status})));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n\nfn other() {\n    let a = 1;\n}\n";
+
+    #[test]
+    fn test_replace_requires_unique_match() {
+        let err = replace_in(SOURCE, "let a = 1;", "let a = 3;", false).unwrap_err();
+        assert!(err.contains("occurs 2 times (lines 2, 8)"), "{}", err);
+
+        let all = replace_in(SOURCE, "let a = 1;", "let a = 3;", true).unwrap();
+        assert_eq!(all.occurrences, 2);
+
+        let one = replace_in(SOURCE, "    let a = 1;\n    let b = 2;", "    let a = 3;\n    let b = 2;", false).unwrap();
+        assert_eq!(one.first_line, 2);
+        assert!(one.content.contains("let a = 3;") && one.content.ends_with("let a = 1;\n}\n"));
+    }
+
+    #[test]
+    fn test_replace_ignores_indentation_and_reports_closest_text() {
+        let edit = replace_in(SOURCE, "let b = 2;\nprintln!(\"{}\", a + b);", "let b = 5;\nprintln!(\"{}\", a * b);", false).unwrap();
+        assert!(edit.whitespace_insensitive);
+        assert!(edit.content.contains("    let b = 5;\n    println!(\"{}\", a * b);\n"));
+
+        let err = replace_in(SOURCE, "    let b = 3;\n    println!(\"{}\", a + b);", "x", false).unwrap_err();
+        assert!(err.contains("closest text is around line 3"), "{}", err);
+    }
+
+    #[test]
+    fn test_patch_applies_with_offset_and_fuzz() {
+        // Header line numbers are off by two and the first context line is stale
+        let patch = "--- a/main.rs\n+++ b/main.rs\n@@ -7,4 +7,4 @@\n fn mainn() {\n     let a = 1;\n-    let b = 2;\n+    let b = 40;\n     println!(\"{}\", a + b);\n";
+        let files = parse_patch(patch).unwrap();
+        let (content, reports) = apply_hunks("main.rs", SOURCE, &files[0].hunks).unwrap();
+        assert!(content.contains("    let b = 40;\n"));
+        assert_eq!(reports[0].fuzz, 1);
+
+        let bad = "--- a/main.rs\n+++ b/main.rs\n@@ -2,2 +2,2 @@\n     let x = 1;\n-    let y = 2;\n+    let y = 3;\n";
+        let files = parse_patch(bad).unwrap();
+        let err = apply_hunks("main.rs", SOURCE, &files[0].hunks).unwrap_err();
+        assert!(err.contains("Hunk 1 of main.rs") && err.contains("Re-read the file"), "{}", err);
+    }
+
+    #[test]
+    fn test_patch_is_all_or_nothing() {
+        let workspace = tempfile::tempdir().unwrap();
+        fs::write(workspace.path().join("main.rs"), SOURCE.replace('\n', "\r\n")).unwrap();
+
+        let patch = "--- /dev/null\n+++ b/src/new.rs\n@@ -0,0 +1,2 @@\n+pub fn new() {}\n+\n--- a/main.rs\n+++ b/main.rs\n@@ -8 +8 @@\n-    let a = 1;\n+    let a = 2;\n";
+        let (changes, summary) = plan_patch(patch, workspace.path()).unwrap();
+        write_changes(&changes).unwrap();
+        assert_eq!(summary[0]["action"], "created");
+        assert_eq!(fs::read_to_string(workspace.path().join("src/new.rs")).unwrap(), "pub fn new() {}\n\n");
+        let main = fs::read_to_string(workspace.path().join("main.rs")).unwrap();
+        assert!(main.ends_with("fn other() {\r\n    let a = 2;\r\n}\r\n"), "{:?}", main);
+
+        let failing = "--- a/main.rs\n+++ b/main.rs\n@@ -1 +1 @@\n-fn main() {\n+fn start() {\n--- a/missing.rs\n+++ b/missing.rs\n@@ -1 +1 @@\n-a\n+b\n";
+        let err = plan_patch(failing, workspace.path()).unwrap_err();
+        assert!(err.contains("missing.rs"), "{}", err);
+        assert!(fs::read_to_string(workspace.path().join("main.rs")).unwrap().starts_with("fn main()"));
+    }
+}
@@ -11,6 +11,7 @@ use walkdir::WalkDir;
 use super::get_required_string_param;
 use crate::persistence::IterationStore;
 use crate::persistence::get_iteration_id;
+use crate::sandbox::{Sandbox, SandboxError};
 
 // ============================================================================
 // Helper Functions
@@ -37,7 +38,7 @@ pub fn strip_unc_prefix(path: &Path) -> PathBuf {
 /// 1. Must be relative path (no absolute paths like /tmp, C:\)
 /// 2. Must not escape workspace directory (no ..)
 /// 3. Must be within the provided workspace directory
-fn validate_path_security_within_workspace(
+pub(crate) fn validate_path_security_within_workspace(
     path: &str,
     workspace_dir: &Path,
 ) -> Result<PathBuf, String> {
@@ -574,7 +575,9 @@ impl Tool for RunCommandTool {
         "Execute a shell command and return the output. \
          WARNING: This tool will REJECT commands that start long-running services \
          (like http.server, npm dev, etc.) as they would block execution. \
-         Use this for: building, testing, linting - NOT for starting servers."
+         Use this for: building, testing, linting - use start_process for servers. \
+         Commands run sandboxed: network access is off and writes outside the workspace \
+         may fail unless the project's sandbox policy allows them."
     }
 
     fn parameters_schema(&self) -> Option<Value> {
@@ -600,7 +603,7 @@ impl Tool for RunCommandTool {
                 "message": format!(
                     "BLOCKED: This command appears to start a long-running service: '{}'. \
                      Starting services would block the agent. \
-                     If you need to verify the code works, just create the files - don't start servers.",
+                     Use start_process to run it in the background, then http_probe to check it.",
                     command
                 )
             }));
@@ -618,30 +621,14 @@ impl Tool for RunCommandTool {
             adk_core::AdkError::tool(format!("Failed to get workspace path: {}", e))
         })?;
 
+        let sandbox = Sandbox::for_workspace(&workspace_dir).map_err(|e| {
+            adk_core::AdkError::tool(format!("Failed to load sandbox policy: {:#}", e))
+        })?;
+        let isolation = sandbox.isolation().map(|i| i.as_str()).unwrap_or("unavailable");
+
         // Execute command with timeout in workspace directory
-        #[cfg(target_os = "windows")]
-        let output = tokio::time::timeout(
-            std::time::Duration::from_secs(30),
-            tokio::process::Command::new("cmd")
-                .args(["/C", command])
-                .current_dir(&workspace_dir)
-                .output(),
-        )
-        .await;
-
-        #[cfg(not(target_os = "windows"))]
-        let output = tokio::time::timeout(
-            std::time::Duration::from_secs(30),
-            tokio::process::Command::new("sh")
-                .arg("-c")
-                .arg(command)
-                .current_dir(&workspace_dir)
-                .output(),
-        )
-        .await;
-
-        match output {
-            Ok(Ok(output)) => {
+        match sandbox.run(command, &workspace_dir, std::time::Duration::from_secs(30)).await {
+            Ok(output) => {
                 let stdout = String::from_utf8_lossy(&output.stdout).to_string();
                 let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
@@ -650,17 +637,22 @@ impl Tool for RunCommandTool {
                     "exit_code": output.status.code(),
                     "stdout": stdout,
                     "stderr": stderr,
-                    "workspace": workspace_dir.to_string_lossy().to_string()
+                    "workspace": workspace_dir.to_string_lossy().to_string(),
+                    "sandbox": isolation
                 }))
             }
-            Ok(Err(e)) => Ok(json!({
-                "status": "error",
-                "message": format!("Failed to execute command: {}", e)
+            Err(SandboxError::Denied(reason)) => Ok(json!({
+                "status": "rejected",
+                "message": format!("BLOCKED: {}", reason)
             })),
-            Err(_) => Ok(json!({
+            Err(SandboxError::Timeout(_)) => Ok(json!({
                 "status": "timeout",
                 "message": "Command execution timeout (30s limit)"
             })),
+            Err(e) => Ok(json!({
+                "status": "error",
+                "message": e.to_string()
+            })),
         }
     }
 }
@@ -1,27 +1,79 @@
 // MCP (Model Context Protocol) Tools Integration
 //
-// This module manages remote MCP server connections and exposes their tools
-// to Cowork Forge agents.
+// This module manages MCP server connections (streamable HTTP or spawned
+// stdio processes) and exposes their tools to Cowork Forge agents.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
-use adk_core::Toolset;
-use adk_tool::McpHttpClientBuilder;
+use adk_core::{ReadonlyContext, Tool, Toolset};
+use adk_tool::McpToolset;
 use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::header::{HeaderName, HeaderValue};
+use rmcp::ServiceExt;
+use rmcp::transport::{
+    StreamableHttpClientTransport, TokioChildProcess,
+    streamable_http_client::StreamableHttpClientTransportConfig,
+};
 use tracing;
 
-use crate::llm::config::McpConfig;
+use crate::llm::config::{McpConfig, McpServerEntry, McpTransportKind};
 
-/// Configuration for a remote MCP server
+/// Tool references starting with this prefix select MCP servers for an agent:
+/// `mcp:<server>` attaches every tool of a server, `mcp:<server>/<tool>` one tool
+pub const MCP_TOOL_PREFIX: &str = "mcp:";
+
+/// Default connection timeout
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// How to reach an MCP server
+#[derive(Debug, Clone)]
+pub enum McpServerTransport {
+    /// Streamable HTTP endpoint, with extra headers sent on every request
+    Http {
+        endpoint: String,
+        headers: HashMap<String, String>,
+    },
+    /// Program spawned by Cowork, speaking JSON-RPC over stdin/stdout
+    Stdio {
+        command: String,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    },
+}
+
+/// Configuration for an MCP server
 #[derive(Debug, Clone)]
 pub struct McpServerConfig {
     /// Server name/identifier
     pub name: String,
-    /// Server endpoint URL
-    pub endpoint: String,
-    /// Request timeout (default: 30s)
+    /// How to connect
+    pub transport: McpServerTransport,
+    /// Connection timeout (default: 60s)
     pub timeout_secs: Option<u64>,
+    /// Agent ids allowed to use this server; empty allows every agent
+    pub agents: Vec<String>,
+}
+
+impl McpServerConfig {
+    /// Endpoint URL or command line, for logs and diagnostics
+    pub fn target(&self) -> String {
+        match &self.transport {
+            McpServerTransport::Http { endpoint, .. } => endpoint.clone(),
+            McpServerTransport::Stdio { command, args, .. } => {
+                std::iter::once(command.as_str()).chain(args.iter().map(String::as_str)).collect::<Vec<_>>().join(" ")
+            }
+        }
+    }
+}
+
+/// A tool advertised by an MCP server
+#[derive(Debug, Clone)]
+pub struct McpToolInfo {
+    pub name: String,
+    pub description: String,
 }
 
 /// Connected MCP toolset with its configuration
@@ -29,6 +81,10 @@ pub struct McpServerConfig {
 pub struct ConnectedMcpToolset {
     pub name: String,
     pub toolset: Arc<dyn Toolset>,
+    /// Tools the server advertised when it connected
+    pub tools: Vec<McpToolInfo>,
+    /// Agent ids allowed to use this server; empty allows every agent
+    pub agents: Vec<String>,
 }
 
 impl Clone for ConnectedMcpToolset {
@@ -36,11 +92,20 @@ impl Clone for ConnectedMcpToolset {
         Self {
             name: self.name.clone(),
             toolset: Arc::clone(&self.toolset),
+            tools: self.tools.clone(),
+            agents: self.agents.clone(),
         }
     }
 }
 
-/// MCP Toolset Manager - manages all remote MCP connections
+impl ConnectedMcpToolset {
+    /// Whether the server's allowlist admits this agent
+    pub fn allows_agent(&self, agent_id: &str) -> bool {
+        self.agents.is_empty() || self.agents.iter().any(|a| a == agent_id)
+    }
+}
+
+/// MCP Toolset Manager - manages all MCP connections
 pub struct McpManager {
     /// List of connected MCP toolsets
     toolsets: Vec<ConnectedMcpToolset>,
@@ -70,18 +135,15 @@ impl McpManager {
             tracing::info!(
                 "Connecting to MCP server: {} at {}",
                 config.name,
-                config.endpoint
+                config.target()
             );
 
-            let toolset = Self::connect_to_server(config).await?;
-            let connected = ConnectedMcpToolset {
-                name: config.name.clone(),
-                toolset,
-            };
+            let connected = Self::connect_to_server(config).await?;
 
             tracing::info!(
-                "Successfully connected to MCP server: {}, tools available",
-                config.name
+                "Successfully connected to MCP server: {}, {} tools available",
+                config.name,
+                connected.tools.len()
             );
 
             connected_toolsets.push(connected.clone());
@@ -92,24 +154,59 @@ impl McpManager {
     }
 
     /// Connect to a single MCP server
-    async fn connect_to_server(config: &McpServerConfig) -> Result<Arc<dyn Toolset>> {
-        let builder = McpHttpClientBuilder::new(&config.endpoint);
-
-        // Set timeout
-        let builder = if let Some(timeout_secs) = config.timeout_secs {
-            builder.timeout(Duration::from_secs(timeout_secs))
-        } else {
-            builder.timeout(Duration::from_secs(60))
+    async fn connect_to_server(config: &McpServerConfig) -> Result<ConnectedMcpToolset> {
+        let timeout = Duration::from_secs(config.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+
+        let connect = async {
+            let client = match &config.transport {
+                McpServerTransport::Http { endpoint, headers } => {
+                    let mut custom_headers = HashMap::new();
+                    for (name, value) in headers {
+                        let name = HeaderName::try_from(name.as_str())
+                            .with_context(|| format!("Invalid header name: {}", name))?;
+                        let value = HeaderValue::try_from(value.as_str())
+                            .with_context(|| format!("Invalid value for header {}", name))?;
+                        custom_headers.insert(name, value);
+                    }
+                    let transport = StreamableHttpClientTransport::from_config(
+                        StreamableHttpClientTransportConfig::with_uri(endpoint.as_str())
+                            .custom_headers(custom_headers),
+                    );
+                    ().serve(transport).await?
+                }
+                McpServerTransport::Stdio { command, args, env } => {
+                    let mut cmd = tokio::process::Command::new(command);
+                    cmd.args(args).envs(env);
+                    let transport = TokioChildProcess::new(cmd)
+                        .with_context(|| format!("Failed to spawn {}", command))?;
+                    ().serve(transport).await?
+                }
+            };
+
+            let tools = client
+                .list_all_tools()
+                .await?
+                .into_iter()
+                .map(|tool| McpToolInfo {
+                    name: tool.name.to_string(),
+                    description: tool.description.map(|d| d.to_string()).unwrap_or_default(),
+                })
+                .collect::<Vec<_>>();
+
+            anyhow::Ok((client, tools))
         };
 
-        // Connect to the MCP server
-        let toolset = builder
-            .connect()
+        let (client, tools) = tokio::time::timeout(timeout, connect)
             .await
+            .with_context(|| format!("Timed out connecting to MCP server: {}", config.name))?
             .with_context(|| format!("Failed to connect to MCP server: {}", config.name))?;
 
-        // Convert to Arc<dyn Toolset>
-        Ok(Arc::new(toolset))
+        Ok(ConnectedMcpToolset {
+            name: config.name.clone(),
+            toolset: Arc::new(McpToolset::new(client).with_name(&config.name)),
+            tools,
+            agents: config.agents.clone(),
+        })
     }
 
     /// Get all connected toolsets
@@ -136,11 +233,15 @@ pub fn create_mcp_configs_from_config(mcp_config: &McpConfig) -> Vec<McpServerCo
     if !mcp_config.tavily_api_key.is_empty() {
         configs.push(McpServerConfig {
             name: "tavily".to_string(),
-            endpoint: format!(
-                "https://mcp.tavily.com/mcp/?tavilyApiKey={}",
-                mcp_config.tavily_api_key
-            ),
+            transport: McpServerTransport::Http {
+                endpoint: format!(
+                    "https://mcp.tavily.com/mcp/?tavilyApiKey={}",
+                    mcp_config.tavily_api_key
+                ),
+                headers: HashMap::new(),
+            },
             timeout_secs: Some(60),
+            agents: Vec::new(),
         });
         tracing::info!("Tavily MCP server configured");
     }
@@ -149,16 +250,54 @@ pub fn create_mcp_configs_from_config(mcp_config: &McpConfig) -> Vec<McpServerCo
     if mcp_config.deepwiki_enabled {
         configs.push(McpServerConfig {
             name: "deepwiki".to_string(),
-            endpoint: "https://mcp.deepwiki.com/mcp".to_string(),
+            transport: McpServerTransport::Http {
+                endpoint: "https://mcp.deepwiki.com/mcp".to_string(),
+                headers: HashMap::new(),
+            },
             timeout_secs: Some(60),
+            agents: Vec::new(),
         });
         tracing::info!("DeepWiki MCP server configured");
     }
 
+    // User-configured servers
+    for entry in &mcp_config.servers {
+        if configs.iter().any(|c| c.name == entry.name) {
+            tracing::warn!("Duplicate MCP server name '{}', skipping", entry.name);
+            continue;
+        }
+        configs.push(server_config_from_entry(entry));
+        tracing::info!("MCP server '{}' configured", entry.name);
+    }
+
     configs
 }
 
+fn server_config_from_entry(entry: &McpServerEntry) -> McpServerConfig {
+    let transport = match entry.transport {
+        McpTransportKind::Http => McpServerTransport::Http {
+            endpoint: entry.url.clone(),
+            headers: entry.headers.clone(),
+        },
+        McpTransportKind::Stdio => McpServerTransport::Stdio {
+            command: entry.command.clone(),
+            args: entry.args.clone(),
+            env: entry.env.clone(),
+        },
+    };
+
+    McpServerConfig {
+        name: entry.name.clone(),
+        transport,
+        timeout_secs: entry.timeout_secs,
+        agents: entry.agents.clone(),
+    }
+}
+
 /// Create MCP toolsets from configuration
+///
+/// Servers that fail to connect are logged and skipped so one broken entry
+/// does not take the others down with it.
 pub async fn create_mcp_toolsets_from_config(
     mcp_config: &McpConfig,
 ) -> Result<Vec<ConnectedMcpToolset>> {
@@ -169,10 +308,166 @@ pub async fn create_mcp_toolsets_from_config(
         return Ok(Vec::new());
     }
 
-    let mut manager = McpManager::new();
+    let mut connected = Vec::new();
     for config in configs {
+        let name = config.name.clone();
+        let mut manager = McpManager::new();
         manager.add_config(config);
+        match manager.connect_all().await {
+            Ok(toolsets) => connected.extend(toolsets),
+            Err(e) => tracing::warn!("Skipping MCP server '{}': {:#}", name, e),
+        }
+    }
+
+    Ok(connected)
+}
+
+/// Pick the MCP toolsets an agent gets from its tool ids.
+///
+/// A server must admit the agent through its allowlist. When the agent lists
+/// `mcp:` references, only the referenced servers (or single tools) are
+/// attached; otherwise every server that admits it is.
+pub fn select_mcp_toolsets<'a>(
+    connected: &[ConnectedMcpToolset],
+    agent_id: &str,
+    tool_ids: impl IntoIterator<Item = &'a str>,
+) -> Vec<Arc<dyn Toolset>> {
+    // server name -> requested tool names (None: the whole server)
+    let mut requested: HashMap<&str, Option<HashSet<String>>> = HashMap::new();
+    for reference in tool_ids.into_iter().filter_map(|id| id.strip_prefix(MCP_TOOL_PREFIX)) {
+        match reference.split_once('/') {
+            Some((server, tool)) => {
+                if let Some(tools) = requested.entry(server).or_insert_with(|| Some(HashSet::new())) {
+                    tools.insert(tool.to_string());
+                }
+            }
+            None => {
+                requested.insert(reference, None);
+            }
+        }
+    }
+
+    for server in requested.keys() {
+        match connected.iter().find(|c| c.name == *server) {
+            None => tracing::warn!("[MCP] Agent '{}' references unknown MCP server '{}'", agent_id, server),
+            Some(c) if !c.allows_agent(agent_id) => {
+                tracing::warn!("[MCP] MCP server '{}' does not allow agent '{}'", server, agent_id)
+            }
+            Some(_) => {}
+        }
+    }
+
+    connected
+        .iter()
+        .filter(|c| c.allows_agent(agent_id))
+        .filter_map(|c| {
+            if requested.is_empty() {
+                return Some(c.toolset.clone());
+            }
+            match requested.get(c.name.as_str())? {
+                None => Some(c.toolset.clone()),
+                Some(tools) => Some(Arc::new(McpToolSubset {
+                    inner: c.toolset.clone(),
+                    tools: tools.clone(),
+                }) as Arc<dyn Toolset>),
+            }
+        })
+        .collect()
+}
+
+/// Exposes only the named tools of an MCP toolset
+struct McpToolSubset {
+    inner: Arc<dyn Toolset>,
+    tools: HashSet<String>,
+}
+
+#[async_trait]
+impl Toolset for McpToolSubset {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn tools(&self, ctx: Arc<dyn ReadonlyContext>) -> adk_core::Result<Vec<Arc<dyn Tool>>> {
+        let tools = self.inner.tools(ctx).await?;
+        Ok(tools.into_iter().filter(|t| self.tools.contains(t.name())).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NamedToolset(String);
+
+    #[async_trait]
+    impl Toolset for NamedToolset {
+        fn name(&self) -> &str {
+            &self.0
+        }
+
+        async fn tools(&self, _ctx: Arc<dyn ReadonlyContext>) -> adk_core::Result<Vec<Arc<dyn Tool>>> {
+            Ok(Vec::new())
+        }
     }
 
-    manager.connect_all().await
+    fn connected(name: &str, agents: &[&str]) -> ConnectedMcpToolset {
+        ConnectedMcpToolset {
+            name: name.to_string(),
+            toolset: Arc::new(NamedToolset(name.to_string())),
+            tools: Vec::new(),
+            agents: agents.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    fn names(toolsets: Vec<Arc<dyn Toolset>>) -> Vec<String> {
+        toolsets.iter().map(|t| t.name().to_string()).collect()
+    }
+
+    #[test]
+    fn test_select_mcp_toolsets() {
+        let servers = vec![connected("search", &[]), connected("github", &["coding_actor"])];
+
+        // No references: every server whose allowlist admits the agent
+        assert_eq!(names(select_mcp_toolsets(&servers, "prd_actor", ["read_file"])), ["search"]);
+        assert_eq!(names(select_mcp_toolsets(&servers, "coding_actor", [])), ["search", "github"]);
+
+        // References narrow the set, but never past the allowlist
+        assert_eq!(names(select_mcp_toolsets(&servers, "coding_actor", ["mcp:github/create_issue"])), ["github"]);
+        assert!(select_mcp_toolsets(&servers, "prd_actor", ["mcp:github"]).is_empty());
+    }
+
+    #[test]
+    fn test_create_mcp_configs_from_servers() {
+        let config: McpConfig = toml::from_str(
+            r#"
+            deepwiki_enabled = true
+
+            [[servers]]
+            name = "github"
+            url = "https://example.com/mcp"
+            headers = { Authorization = "Bearer token" }
+            agents = ["coding_actor"]
+
+            [[servers]]
+            name = "files"
+            transport = "stdio"
+            command = "npx"
+            args = ["-y", "@modelcontextprotocol/server-filesystem", "."]
+            timeout_secs = 10
+            "#,
+        )
+        .unwrap();
+
+        let configs = create_mcp_configs_from_config(&config);
+        let names: Vec<_> = configs.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["deepwiki", "github", "files"]);
+
+        assert!(matches!(
+            &configs[1].transport,
+            McpServerTransport::Http { headers, .. } if headers["Authorization"] == "Bearer token"
+        ));
+        assert_eq!(configs[1].agents, ["coding_actor"]);
+        assert_eq!(configs[2].target(), "npx -y @modelcontextprotocol/server-filesystem .");
+        assert_eq!(configs[2].timeout_secs, Some(10));
+    }
 }
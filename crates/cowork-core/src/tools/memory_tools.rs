@@ -45,8 +45,8 @@ impl Tool for QueryMemoryTool {
             "properties": {
                 "scope": {
                     "type": "string",
-                    "description": "Memory scope: 'project' (project-level only), 'iteration' (current iteration only), or 'smart' (merged, recommended)",
-                    "enum": ["project", "iteration", "smart"],
+                    "description": "Memory scope: 'project' (project-level only), 'iteration' (current iteration only), 'global' (organization-wide conventions shared across projects, if enabled), or 'smart' (merged, recommended)",
+                    "enum": ["project", "iteration", "global", "smart"],
                     "default": "smart"
                 },
                 "query_type": {
@@ -72,51 +72,66 @@ impl Tool for QueryMemoryTool {
     }
 
     async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
-        let scope_str = args.get("scope").and_then(|v| v.as_str()).unwrap_or("smart");
-        let query_type_str = args.get("query_type").and_then(|v| v.as_str()).unwrap_or("all");
-        let keywords: Vec<String> = args.get("keywords")
-            .and_then(|v| v.as_array())
-            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-            .unwrap_or_default();
-        let limit = args.get("limit").and_then(|v| v.as_i64()).unwrap_or(20) as usize;
-
-        let scope = match scope_str {
-            "project" => MemoryScope::Project,
-            "iteration" => MemoryScope::Iteration,
-            "smart" => MemoryScope::Smart,
-            _ => MemoryScope::Smart,
-        };
-
-        let query_type = match query_type_str {
-            "decisions" => MemoryQueryType::Decisions,
-            "patterns" => MemoryQueryType::Patterns,
-            "insights" => MemoryQueryType::Insights,
-            "all" => MemoryQueryType::All,
-            _ => MemoryQueryType::All,
-        };
-
-        let query = MemoryQuery {
-            scope,
-            query_type,
-            keywords: keywords.clone(),
-            limit: Some(limit),
-        };
-
-        let store = MemoryStore::new();
-        
-        let result = store.query(&query, Some(&self.iteration_id))
-            .map_err(|e| adk_core::AdkError::tool(format!("Failed to query memory: {}", e)))?;
+        query_memory(&args, &self.iteration_id)
+            .map_err(|e| adk_core::AdkError::tool(format!("Failed to query memory: {}", e)))
+    }
+}
 
-        Ok(json!({
-            "decisions": result.decisions,
-            "patterns": result.patterns,
-            "insights": result.insights,
-            "total_decisions": result.decisions.len(),
-            "total_patterns": result.patterns.len(),
-            "total_insights": result.insights.len(),
-            "context_string": result.to_context_string()
-        }))
+/// Run a `query_memory` call for `iteration_id` (also served over MCP)
+pub fn query_memory(args: &Value, iteration_id: &str) -> anyhow::Result<Value> {
+    let scope_str = args.get("scope").and_then(|v| v.as_str()).unwrap_or("smart");
+    let query_type_str = args.get("query_type").and_then(|v| v.as_str()).unwrap_or("all");
+    let keywords: Vec<String> = args.get("keywords")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_default();
+    let limit = args.get("limit").and_then(|v| v.as_i64()).unwrap_or(20) as usize;
+
+    let scope = match scope_str {
+        "project" => MemoryScope::Project,
+        "iteration" => MemoryScope::Iteration,
+        "global" => MemoryScope::Global,
+        "smart" => MemoryScope::Smart,
+        _ => MemoryScope::Smart,
+    };
+
+    let query_type = match query_type_str {
+        "decisions" => MemoryQueryType::Decisions,
+        "patterns" => MemoryQueryType::Patterns,
+        "insights" => MemoryQueryType::Insights,
+        "all" => MemoryQueryType::All,
+        _ => MemoryQueryType::All,
+    };
+
+    let query = MemoryQuery {
+        scope,
+        query_type,
+        keywords: keywords.clone(),
+        limit: Some(limit),
+    };
+
+    let store = MemoryStore::new();
+    
+    let result = store.query(&query, Some(iteration_id))?;
+
+    if matches!(scope, MemoryScope::Global) && !crate::persistence::is_global_memory_enabled() {
+        return Ok(json!({
+            "decisions": [],
+            "patterns": [],
+            "insights": [],
+            "message": "Global memory is disabled. Set `[memory] global_enabled = true` in config.toml to enable it."
+        }));
     }
+
+    Ok(json!({
+        "decisions": result.decisions,
+        "patterns": result.patterns,
+        "insights": result.insights,
+        "total_decisions": result.decisions.len(),
+        "total_patterns": result.patterns.len(),
+        "total_insights": result.insights.len(),
+        "context_string": result.to_context_string()
+    }))
 }
 
 // ============================================================================
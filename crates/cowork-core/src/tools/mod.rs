@@ -15,7 +15,8 @@ pub fn set_current_agent_name(name: &str) {
     }
 }
 
-fn get_current_agent_name() -> String {
+/// Display name of the agent currently running tools
+pub(crate) fn current_agent_name() -> String {
     CURRENT_AGENT_NAME.read().map(|g| g.clone()).unwrap_or_default()
 }
 
@@ -63,7 +64,7 @@ where
 
 /// Notify about a tool call (call this before tool execution)
 pub fn notify_tool_call(tool_name: &str, args: &Value) {
-    let agent_name = get_current_agent_name();
+    let agent_name = current_agent_name();
     let args_str = if args.is_object() {
         let keys: Vec<&str> = args.as_object().unwrap().keys().map(|s| s.as_str()).collect();
         format!("{:?}", keys)
@@ -81,7 +82,7 @@ pub fn notify_tool_call(tool_name: &str, args: &Value) {
 
 /// Notify about a tool result (call this after tool execution)
 pub fn notify_tool_result(tool_name: &str, result: &Result<Value, AdkError>) {
-    let agent_name = get_current_agent_name();
+    let agent_name = current_agent_name();
     match result {
         Ok(v) => {
             let preview = if v.is_object() {
@@ -116,6 +117,8 @@ pub fn notify_tool_result(tool_name: &str, result: &Result<Value, AdkError>) {
 
 // Core tools
 pub mod file_tools;
+pub mod edit_tools;
+pub mod search_tools;
 pub mod hitl_content_tools;
 pub mod test_lint_tools;
 
@@ -144,6 +147,9 @@ pub mod memory_tools;
 // Knowledge generation tools
 pub mod knowledge_tools;
 
+// Code structure index tools
+pub mod code_index_tools;
+
 // Project Manager Agent tools
 pub mod pm_tools;
 
@@ -153,8 +159,13 @@ pub mod legacy_project_analyzer_tools;
 // MCP (Model Context Protocol) tools - remote MCP server integration
 pub mod mcp_tools;
 
+// Background process tools (dev servers, smoke tests)
+pub mod process_tools;
+
 // Re-exports
 pub use file_tools::*;
+pub use edit_tools::*;
+pub use search_tools::*;
 pub use hitl_content_tools::*;
 pub use test_lint_tools::*;
 pub use data_tools::*;
@@ -166,6 +177,8 @@ pub use deployment_tools::*;
 pub use goto_stage_tool::*;
 pub use memory_tools::*;
 pub use knowledge_tools::*;
+pub use code_index_tools::*;
 pub use pm_tools::*;
 pub use legacy_project_analyzer_tools::*;
-pub use mcp_tools::*;
\ No newline at end of file
+pub use mcp_tools::*;
+pub use process_tools::*;
\ No newline at end of file
@@ -0,0 +1,414 @@
+// Process Tools - Run dev servers in the background and smoke-test them
+//
+// `run_command` refuses long-running services because they would block the
+// agent. These tools start them in the background instead, read their
+// output, probe them over HTTP and stop them. Whatever is still running when
+// the stage ends is stopped by the stage executor.
+
+use adk_core::{Tool, ToolContext};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{get_optional_string_param, get_required_string_param};
+use crate::persistence::{get_iteration_id, IterationStore};
+use crate::process::{self, ProcessSpec};
+use crate::sandbox::Sandbox;
+
+const DEFAULT_READY_TIMEOUT_SECS: u64 = 60;
+const MAX_READY_TIMEOUT_SECS: u64 = 300;
+const DEFAULT_OUTPUT_LINES: usize = 200;
+const DEFAULT_PROBE_TIMEOUT_SECS: u64 = 10;
+/// Characters of the response body returned by `http_probe`
+const PROBE_BODY_LIMIT: usize = 2000;
+
+fn workspace_dir() -> adk_core::Result<std::path::PathBuf> {
+    let iteration_id = get_iteration_id().ok_or_else(|| {
+        adk_core::AdkError::tool(
+            "Iteration ID not set. Cannot manage processes without an active iteration.".to_string(),
+        )
+    })?;
+    IterationStore::new()
+        .workspace_path(&iteration_id)
+        .map_err(|e| adk_core::AdkError::tool(format!("Failed to get workspace path: {}", e)))
+}
+
+// ============================================================================
+// StartProcessTool
+// ============================================================================
+
+pub struct StartProcessTool;
+
+#[async_trait]
+impl Tool for StartProcessTool {
+    fn name(&self) -> &str {
+        "start_process"
+    }
+
+    fn description(&self) -> &str {
+        "Start a long-running command (dev server, API server, watcher) in the background in the workspace. \
+         A free port is passed in the PORT environment variable and replaces {PORT} in the command \
+         (e.g. 'npx vite --port {PORT}'). Waits until the port accepts connections, the output matches \
+         ready_pattern, or the output announces a local URL. Returns a process id for read_process_output, \
+         http_probe and stop_process. Processes still running at the end of the stage are stopped."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "command": {
+                    "type": "string",
+                    "description": "Shell command to start; {PORT} is replaced by the allocated port"
+                },
+                "port": {
+                    "type": "integer",
+                    "description": "Port the command must use (fails if taken). Omit to allocate a free one"
+                },
+                "ready_pattern": {
+                    "type": "string",
+                    "description": "Regular expression matched against output lines to detect readiness (default: port accepts connections)"
+                },
+                "ready_timeout_secs": {
+                    "type": "integer",
+                    "description": "Seconds to wait for readiness (default 60, max 300)"
+                }
+            },
+            "required": ["command"]
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let command = get_required_string_param(&args, "command")?;
+        let port = args
+            .get("port")
+            .and_then(|v| v.as_u64())
+            .map(|p| u16::try_from(p).map_err(|_| adk_core::AdkError::tool(format!("Invalid port: {}", p))))
+            .transpose()?;
+        let ready_timeout = args
+            .get("ready_timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_READY_TIMEOUT_SECS)
+            .min(MAX_READY_TIMEOUT_SECS);
+
+        let workspace_dir = workspace_dir()?;
+        // The host network is kept so the server can be probed from here
+        let sandbox = Sandbox::for_workspace(&workspace_dir)
+            .map_err(|e| adk_core::AdkError::tool(format!("Failed to load sandbox policy: {:#}", e)))?
+            .with_network();
+
+        let spec = ProcessSpec {
+            command: command.to_string(),
+            cwd: workspace_dir,
+            port,
+            ready_pattern: get_optional_string_param(&args, "ready_pattern"),
+            ready_timeout: Duration::from_secs(ready_timeout),
+        };
+
+        match process::start(&sandbox, spec).await {
+            Ok((info, output)) => Ok(json!({
+                "status": if info.ready { "ready" } else { "starting" },
+                "message": if info.ready {
+                    format!("Process {} is ready at {}", info.id, info.url)
+                } else {
+                    format!(
+                        "Process {} is still starting after {}s; check read_process_output before probing",
+                        info.id, ready_timeout
+                    )
+                },
+                "process": info,
+                "output": output.lines,
+                "next_cursor": output.next
+            })),
+            Err(e) => Ok(json!({
+                "status": "failed",
+                "message": format!("{:#}", e)
+            })),
+        }
+    }
+}
+
+// ============================================================================
+// ReadProcessOutputTool
+// ============================================================================
+
+pub struct ReadProcessOutputTool;
+
+#[async_trait]
+impl Tool for ReadProcessOutputTool {
+    fn name(&self) -> &str {
+        "read_process_output"
+    }
+
+    fn description(&self) -> &str {
+        "Read the output (stdout and stderr) of a background process started with start_process, \
+         and whether it is still running. Pass the returned next_cursor to get only new lines."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Process id returned by start_process"
+                },
+                "cursor": {
+                    "type": "integer",
+                    "description": "Line cursor to read from (default 0: everything still buffered)"
+                },
+                "max_lines": {
+                    "type": "integer",
+                    "description": "Maximum number of lines to return (default 200)"
+                }
+            },
+            "required": ["id"]
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let id = get_required_string_param(&args, "id")?;
+        let cursor = args.get("cursor").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+        let max_lines = args
+            .get("max_lines")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_OUTPUT_LINES);
+
+        match process::read_output(id, cursor, max_lines) {
+            Ok((info, output)) => Ok(json!({
+                "status": if info.running { "running" } else { "exited" },
+                "process": info,
+                "output": output.lines,
+                "next_cursor": output.next,
+                "dropped_lines": output.dropped
+            })),
+            Err(e) => Ok(json!({
+                "status": "error",
+                "message": e.to_string(),
+                "running_processes": process::list()
+            })),
+        }
+    }
+}
+
+// ============================================================================
+// HttpProbeTool
+// ============================================================================
+
+pub struct HttpProbeTool;
+
+#[async_trait]
+impl Tool for HttpProbeTool {
+    fn name(&self) -> &str {
+        "http_probe"
+    }
+
+    fn description(&self) -> &str {
+        "Send an HTTP request to a locally running server and return the status code and the start of the body. \
+         Give either a full local url (http://127.0.0.1:<port>/...) or the id of a process from start_process \
+         plus a path. Only localhost addresses are allowed."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Process id returned by start_process (its port is used)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to request on the process's port (default '/')"
+                },
+                "url": {
+                    "type": "string",
+                    "description": "Full local URL, instead of id + path"
+                },
+                "method": {
+                    "type": "string",
+                    "enum": ["GET", "HEAD", "POST", "PUT", "DELETE"],
+                    "description": "HTTP method (default GET)"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Request body; sent as JSON when it parses as JSON"
+                },
+                "expect_status": {
+                    "type": "integer",
+                    "description": "Expected status code (default: any 2xx/3xx passes)"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Request timeout in seconds (default 10)"
+                }
+            }
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let url = match (get_optional_string_param(&args, "url"), get_optional_string_param(&args, "id")) {
+            (Some(url), _) => url,
+            (None, Some(id)) => {
+                let (info, _) = process::read_output(&id, 0, 0).map_err(|e| adk_core::AdkError::tool(e.to_string()))?;
+                let path = get_optional_string_param(&args, "path").unwrap_or_else(|| "/".to_string());
+                format!("{}/{}", info.url, path.trim_start_matches('/'))
+            }
+            (None, None) => {
+                return Err(adk_core::AdkError::tool("Either 'url' or 'id' is required".to_string()));
+            }
+        };
+
+        let url = match local_url(&url) {
+            Ok(url) => url,
+            Err(message) => return Ok(json!({ "status": "rejected", "message": message })),
+        };
+
+        let method = get_optional_string_param(&args, "method").unwrap_or_else(|| "GET".to_string());
+        let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+            .map_err(|_| adk_core::AdkError::tool(format!("Invalid HTTP method: {}", method)))?;
+        let timeout = args.get("timeout_secs").and_then(|v| v.as_u64()).unwrap_or(DEFAULT_PROBE_TIMEOUT_SECS);
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| adk_core::AdkError::tool(format!("Failed to create HTTP client: {}", e)))?;
+
+        let mut request = client.request(method.clone(), url.clone());
+        if let Some(body) = get_optional_string_param(&args, "body") {
+            request = match serde_json::from_str::<Value>(&body) {
+                Ok(json_body) => request.json(&json_body),
+                Err(_) => request.body(body),
+            };
+        }
+
+        let started = std::time::Instant::now();
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return Ok(json!({
+                    "status": "failed",
+                    "url": url.to_string(),
+                    "message": format!("Request failed: {}", e)
+                }));
+            }
+        };
+
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body = response.text().await.unwrap_or_default();
+        let truncated = body.chars().count() > PROBE_BODY_LIMIT;
+
+        let passed = match args.get("expect_status").and_then(|v| v.as_u64()) {
+            Some(expected) => u64::from(status.as_u16()) == expected,
+            None => status.is_success() || status.is_redirection(),
+        };
+
+        Ok(json!({
+            "status": if passed { "passed" } else { "failed" },
+            "url": url.to_string(),
+            "method": method.as_str(),
+            "status_code": status.as_u16(),
+            "content_type": content_type,
+            "location": location,
+            "elapsed_ms": started.elapsed().as_millis() as u64,
+            "body": body.chars().take(PROBE_BODY_LIMIT).collect::<String>(),
+            "body_truncated": truncated
+        }))
+    }
+}
+
+/// Parse `url`, accepting only loopback hosts
+fn local_url(url: &str) -> Result<reqwest::Url, String> {
+    let mut parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("Only http and https URLs can be probed, got '{}'", url));
+    }
+    match parsed.host_str() {
+        Some("localhost" | "127.0.0.1" | "[::1]") => Ok(parsed),
+        // Servers listening on all interfaces are reached through loopback
+        Some("0.0.0.0") => {
+            parsed
+                .set_host(Some("127.0.0.1"))
+                .map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+            Ok(parsed)
+        }
+        _ => Err(format!(
+            "Only local servers can be probed (localhost / 127.0.0.1), got '{}'",
+            url
+        )),
+    }
+}
+
+// ============================================================================
+// StopProcessTool
+// ============================================================================
+
+pub struct StopProcessTool;
+
+#[async_trait]
+impl Tool for StopProcessTool {
+    fn name(&self) -> &str {
+        "stop_process"
+    }
+
+    fn description(&self) -> &str {
+        "Stop a background process started with start_process, together with everything it started."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Process id returned by start_process"
+                }
+            },
+            "required": ["id"]
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let id = get_required_string_param(&args, "id")?;
+        match process::stop(id) {
+            Ok(info) => Ok(json!({
+                "status": "stopped",
+                "process": info
+            })),
+            Err(e) => Ok(json!({
+                "status": "error",
+                "message": e.to_string(),
+                "running_processes": process::list()
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_only_local_urls() {
+        assert!(local_url("http://localhost:3000/health").is_ok());
+        assert_eq!(
+            local_url("http://0.0.0.0:8080/").unwrap().as_str(),
+            "http://127.0.0.1:8080/"
+        );
+        assert!(local_url("https://example.com/").is_err());
+        assert!(local_url("file:///etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,511 @@
+// Search Tools - grep and glob over the workspace
+//
+// `search_code` finds lines matching a regular expression and returns them
+// with surrounding context; `find_files` lists files matching a glob. Both
+// honour `.gitignore`, skip dependency and VCS directories, and cap their
+// results so agents can locate code without reading whole files.
+
+use adk_core::{Tool, ToolContext};
+use async_trait::async_trait;
+use globset::{GlobBuilder, GlobMatcher};
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::file_tools::validate_path_security_within_workspace;
+use super::{get_optional_string_param, get_required_string_param};
+use crate::persistence::{get_iteration_id, IterationStore};
+
+/// Directories never searched, even without a `.gitignore`
+const SKIPPED_DIRS: &[&str] = &[".git", ".cowork-v2", "node_modules", "target"];
+
+const DEFAULT_CONTEXT_LINES: usize = 2;
+const MAX_CONTEXT_LINES: usize = 10;
+const DEFAULT_MAX_MATCHES: usize = 100;
+const MAX_MATCHES: usize = 500;
+const DEFAULT_MAX_FILES: usize = 200;
+const MAX_FILES: usize = 1000;
+
+/// Files larger than this are not searched
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+/// Matched lines are cut to this many characters
+const MAX_LINE_CHARS: usize = 300;
+
+/// Where the search tools look
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchScope {
+    /// The current iteration's workspace
+    Workspace,
+    /// A project given by the `project_path` argument (legacy project analyzer)
+    Project,
+}
+
+impl SearchScope {
+    fn root(self, args: &Value) -> adk_core::Result<PathBuf> {
+        match self {
+            SearchScope::Workspace => {
+                let iteration_id = get_iteration_id().ok_or_else(|| {
+                    adk_core::AdkError::tool(
+                        "Iteration ID not set. Cannot search without an active iteration.".to_string(),
+                    )
+                })?;
+                IterationStore::new()
+                    .workspace_path(&iteration_id)
+                    .map_err(|e| adk_core::AdkError::tool(format!("Failed to get workspace path: {}", e)))
+            }
+            SearchScope::Project => Ok(PathBuf::from(get_required_string_param(args, "project_path")?)),
+        }
+    }
+
+    /// Schema properties shared by both tools
+    fn properties(self, mut properties: Value) -> Value {
+        if self == SearchScope::Project {
+            properties["project_path"] = json!({
+                "type": "string",
+                "description": "Absolute path to the project root directory"
+            });
+        }
+        properties
+    }
+
+    fn required(self, required: &[&str]) -> Vec<String> {
+        let mut required: Vec<String> = required.iter().map(|s| s.to_string()).collect();
+        if self == SearchScope::Project {
+            required.insert(0, "project_path".to_string());
+        }
+        required
+    }
+}
+
+/// Directory to search: `path` under `root`, validated like file tool paths
+fn search_dir(root: &Path, args: &Value) -> Result<PathBuf, String> {
+    match get_optional_string_param(args, "path").filter(|p| !p.trim().is_empty() && p.trim() != ".") {
+        Some(path) => validate_path_security_within_workspace(&path, root).map(|p| root.join(p)),
+        None => Ok(root.to_path_buf()),
+    }
+}
+
+/// Files under `dir` in path order, honouring ignore files
+fn walk_files(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    ignore::WalkBuilder::new(dir)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|e| !SKIPPED_DIRS.iter().any(|d| e.file_name() == *d))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .map(|e| e.into_path())
+}
+
+/// `path` relative to `root`, `/`-separated
+fn relative(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Glob matched against the relative path, or the file name when it has no `/`
+fn compile_glob(pattern: &str) -> Result<(GlobMatcher, bool), String> {
+    let glob = GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| format!("Invalid glob '{}': {}", pattern, e))?;
+    Ok((glob.compile_matcher(), !pattern.contains('/')))
+}
+
+fn glob_matches(glob: &(GlobMatcher, bool), relative: &str) -> bool {
+    let (matcher, name_only) = glob;
+    if *name_only {
+        matcher.is_match(relative.rsplit('/').next().unwrap_or(relative))
+    } else {
+        matcher.is_match(relative)
+    }
+}
+
+fn truncate_line(line: &str) -> String {
+    if line.chars().count() > MAX_LINE_CHARS {
+        format!("{}…", line.chars().take(MAX_LINE_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+// ============================================================================
+// Search
+// ============================================================================
+
+struct SearchOptions {
+    regex: Regex,
+    glob: Option<(GlobMatcher, bool)>,
+    context_lines: usize,
+    max_matches: usize,
+}
+
+/// Matches in one file, with context merged into `ripgrep`-style blocks
+#[derive(Debug, serde::Serialize)]
+struct FileMatches {
+    file: String,
+    lines: Vec<usize>,
+    /// "12: text" for matched lines, "12- text" for context, "--" between blocks
+    snippet: String,
+}
+
+#[derive(Debug)]
+struct SearchOutcome {
+    files: Vec<FileMatches>,
+    matches: usize,
+    files_searched: usize,
+    truncated: bool,
+}
+
+fn search(root: &Path, dir: &Path, options: &SearchOptions) -> SearchOutcome {
+    let mut outcome = SearchOutcome { files: Vec::new(), matches: 0, files_searched: 0, truncated: false };
+
+    for path in walk_files(dir) {
+        let rel = relative(&path, root);
+        if options.glob.as_ref().is_some_and(|g| !glob_matches(g, &rel)) {
+            continue;
+        }
+        if std::fs::metadata(&path).map(|m| m.len() > MAX_FILE_BYTES).unwrap_or(true) {
+            continue;
+        }
+        // Skips binary and non-UTF-8 files
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if content.contains('\0') {
+            continue;
+        }
+        outcome.files_searched += 1;
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut matched = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            if options.regex.is_match(line) {
+                if outcome.matches == options.max_matches {
+                    outcome.truncated = true;
+                    break;
+                }
+                matched.push(i);
+                outcome.matches += 1;
+            }
+        }
+        if !matched.is_empty() {
+            outcome.files.push(FileMatches {
+                file: rel,
+                lines: matched.iter().map(|i| i + 1).collect(),
+                snippet: snippet(&lines, &matched, options.context_lines),
+            });
+        }
+        if outcome.truncated {
+            break;
+        }
+    }
+    outcome
+}
+
+fn snippet(lines: &[&str], matched: &[usize], context: usize) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut shown_until = 0;
+    for &i in matched {
+        let start = i.saturating_sub(context);
+        if !out.is_empty() && start > shown_until {
+            out.push("--".to_string());
+        }
+        let start = start.max(shown_until);
+        let end = (i + context + 1).min(lines.len());
+        for (j, line) in lines.iter().enumerate().take(end).skip(start) {
+            let marker = if matched.binary_search(&j).is_ok() { ':' } else { '-' };
+            out.push(format!("{}{} {}", j + 1, marker, truncate_line(line)));
+        }
+        shown_until = shown_until.max(end);
+    }
+    out.join("\n")
+}
+
+// ============================================================================
+// SearchCodeTool
+// ============================================================================
+
+pub struct SearchCodeTool {
+    scope: SearchScope,
+}
+
+impl SearchCodeTool {
+    pub fn new(scope: SearchScope) -> Self {
+        Self { scope }
+    }
+}
+
+#[async_trait]
+impl Tool for SearchCodeTool {
+    fn name(&self) -> &str {
+        "search_code"
+    }
+
+    fn description(&self) -> &str {
+        "Search file contents with a regular expression (like grep/ripgrep) and return matching lines \
+         with line numbers and surrounding context. Respects .gitignore and skips node_modules, target \
+         and .git. Use it to find definitions, usages and strings instead of reading whole files."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": self.scope.properties(json!({
+                "pattern": {
+                    "type": "string",
+                    "description": "Regular expression (Rust regex syntax), e.g. 'fn\\s+handle_' or 'useState\\('"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory to search, relative to the root (default: everything)"
+                },
+                "glob": {
+                    "type": "string",
+                    "description": "Only search files matching this glob, e.g. '*.ts' or 'src/**/*.rs'"
+                },
+                "fixed_string": {
+                    "type": "boolean",
+                    "description": "Treat pattern as literal text instead of a regex (default: false)"
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Ignore case (default: false)"
+                },
+                "context_lines": {
+                    "type": "integer",
+                    "description": "Lines of context before and after each match (default 2, max 10)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of matching lines (default 100, max 500)"
+                }
+            })),
+            "required": self.scope.required(&["pattern"])
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let pattern = get_required_string_param(&args, "pattern")?;
+        let root = self.scope.root(&args)?;
+        let dir = match search_dir(&root, &args) {
+            Ok(dir) => dir,
+            Err(e) => return Ok(json!({ "status": "security_error", "message": e })),
+        };
+        if !dir.is_dir() {
+            return Ok(json!({
+                "status": "error",
+                "message": format!("Directory not found: {}", relative(&dir, &root))
+            }));
+        }
+
+        let fixed = args.get("fixed_string").and_then(|v| v.as_bool()).unwrap_or(false);
+        let source = if fixed { regex::escape(pattern) } else { pattern.to_string() };
+        let regex = match RegexBuilder::new(&source)
+            .case_insensitive(args.get("case_insensitive").and_then(|v| v.as_bool()).unwrap_or(false))
+            .build()
+        {
+            Ok(regex) => regex,
+            Err(e) => {
+                return Ok(json!({
+                    "status": "error",
+                    "message": format!("Invalid regex '{}': {}. Set fixed_string to true to search for literal text.", pattern, e)
+                }));
+            }
+        };
+        let glob = match get_optional_string_param(&args, "glob").map(|g| compile_glob(&g)).transpose() {
+            Ok(glob) => glob,
+            Err(e) => return Ok(json!({ "status": "error", "message": e })),
+        };
+
+        let options = SearchOptions {
+            regex,
+            glob,
+            context_lines: args
+                .get("context_lines")
+                .and_then(|v| v.as_u64())
+                .map_or(DEFAULT_CONTEXT_LINES, |n| (n as usize).min(MAX_CONTEXT_LINES)),
+            max_matches: args
+                .get("max_results")
+                .and_then(|v| v.as_u64())
+                .map_or(DEFAULT_MAX_MATCHES, |n| (n as usize).clamp(1, MAX_MATCHES)),
+        };
+
+        let outcome = tokio::task::spawn_blocking(move || search(&root, &dir, &options))
+            .await
+            .map_err(|e| adk_core::AdkError::tool(format!("Search failed: {}", e)))?;
+
+        Ok(json!({
+            "status": "success",
+            "matches": outcome.matches,
+            "files_with_matches": outcome.files.len(),
+            "files_searched": outcome.files_searched,
+            "truncated": outcome.truncated,
+            "message": if outcome.truncated {
+                "Result limit reached; narrow the pattern, path or glob to see the rest".to_string()
+            } else if outcome.matches == 0 {
+                format!("No matches for '{}'", pattern)
+            } else {
+                format!("{} match(es) in {} file(s)", outcome.matches, outcome.files.len())
+            },
+            "results": outcome.files
+        }))
+    }
+}
+
+// ============================================================================
+// FindFilesTool
+// ============================================================================
+
+pub struct FindFilesTool {
+    scope: SearchScope,
+}
+
+impl FindFilesTool {
+    pub fn new(scope: SearchScope) -> Self {
+        Self { scope }
+    }
+}
+
+/// Relative paths of files matching `glob`, at most `max`; second value is the total found
+fn find_files(root: &Path, dir: &Path, glob: &(GlobMatcher, bool), max: usize) -> (Vec<String>, usize) {
+    let mut found = Vec::new();
+    let mut total = 0;
+    for path in walk_files(dir) {
+        let rel = relative(&path, root);
+        if glob_matches(glob, &rel) {
+            total += 1;
+            if found.len() < max {
+                found.push(rel);
+            }
+        }
+    }
+    (found, total)
+}
+
+#[async_trait]
+impl Tool for FindFilesTool {
+    fn name(&self) -> &str {
+        "find_files"
+    }
+
+    fn description(&self) -> &str {
+        "Find files whose path matches a glob, e.g. '**/*.tsx', 'src/**/mod.rs' or 'test_*.py' \
+         (a pattern without '/' matches file names anywhere). Respects .gitignore and skips \
+         node_modules, target and .git."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": self.scope.properties(json!({
+                "pattern": {
+                    "type": "string",
+                    "description": "Glob matched against paths relative to the root, or file names if it has no '/'"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory to search, relative to the root (default: everything)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of paths to return (default 200, max 1000)"
+                }
+            })),
+            "required": self.scope.required(&["pattern"])
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let pattern = get_required_string_param(&args, "pattern")?;
+        let root = self.scope.root(&args)?;
+        let dir = match search_dir(&root, &args) {
+            Ok(dir) => dir,
+            Err(e) => return Ok(json!({ "status": "security_error", "message": e })),
+        };
+        let glob = match compile_glob(pattern) {
+            Ok(glob) => glob,
+            Err(e) => return Ok(json!({ "status": "error", "message": e })),
+        };
+        let max = args
+            .get("max_results")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_MAX_FILES, |n| (n as usize).clamp(1, MAX_FILES));
+
+        let (files, total) = tokio::task::spawn_blocking(move || find_files(&root, &dir, &glob, max))
+            .await
+            .map_err(|e| adk_core::AdkError::tool(format!("Search failed: {}", e)))?;
+
+        Ok(json!({
+            "status": "success",
+            "total": total,
+            "truncated": total > files.len(),
+            "files": files
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn fixture() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/ui")).unwrap();
+        fs::create_dir_all(root.join("node_modules/lib")).unwrap();
+        fs::create_dir_all(root.join("dist")).unwrap();
+        fs::write(root.join(".gitignore"), "dist/\n").unwrap();
+        fs::write(root.join("src/main.rs"), "use ui::render;\n\nfn main() {\n    render();\n}\n\nfn render_all() {}\n").unwrap();
+        fs::write(root.join("src/ui/app.tsx"), "export function render() {\n  return null;\n}\n").unwrap();
+        fs::write(root.join("node_modules/lib/index.js"), "function render() {}\n").unwrap();
+        fs::write(root.join("dist/bundle.js"), "function render() {}\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_search_code_with_context_and_caps() {
+        let dir = fixture();
+        let root = dir.path();
+        let options = SearchOptions {
+            regex: Regex::new(r"render\(").unwrap(),
+            glob: None,
+            context_lines: 1,
+            max_matches: 10,
+        };
+
+        let outcome = search(root, root, &options);
+        let files: Vec<&str> = outcome.files.iter().map(|f| f.file.as_str()).collect();
+        assert_eq!(files, vec!["src/main.rs", "src/ui/app.tsx"]);
+        assert_eq!(outcome.files[0].lines, vec![4]);
+        assert_eq!(outcome.files[0].snippet, "3- fn main() {\n4:     render();\n5- }");
+
+        let capped = SearchOptions { max_matches: 1, glob: Some(compile_glob("*.tsx").unwrap()), ..options };
+        let outcome = search(root, root, &capped);
+        assert_eq!(outcome.files.len(), 1);
+        assert_eq!(outcome.files[0].file, "src/ui/app.tsx");
+        assert!(!outcome.truncated);
+    }
+
+    #[test]
+    fn test_find_files_by_name_and_path_glob() {
+        let dir = fixture();
+        let root = dir.path();
+
+        let (files, total) = find_files(root, root, &compile_glob("*.js").unwrap(), 10);
+        assert!(files.is_empty() && total == 0);
+
+        let (files, _) = find_files(root, root, &compile_glob("src/**/*.{rs,tsx}").unwrap(), 10);
+        assert_eq!(files, vec!["src/main.rs", "src/ui/app.tsx"]);
+
+        let (files, total) = find_files(root, &root.join("src"), &compile_glob("*").unwrap(), 1);
+        assert_eq!((files.len(), total), (1, 2));
+    }
+}
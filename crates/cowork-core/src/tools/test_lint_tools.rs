@@ -4,6 +4,9 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::Arc;
 use std::path::Path;
+use std::time::Duration;
+
+use crate::sandbox::{Sandbox, SandboxError};
 
 // ============================================================================
 // CheckTestsTool
@@ -43,48 +46,41 @@ impl Tool for CheckTestsTool {
             .and_then(|v| v.as_str())
             .unwrap_or(".");
 
-        // Auto-detect test command if not provided
-        let test_command = if let Some(cmd) = args.get("test_command").and_then(|v| v.as_str()) {
-            cmd.to_string()
-        } else {
-            detect_test_command(path)?
-        };
-
-        // Execute test command (platform-aware)
-        let output = if cfg!(target_os = "windows") {
-            tokio::process::Command::new("cmd")
-                .args(["/C", &test_command])
-                .current_dir(path)
-                .output()
-                .await
-        } else {
-            tokio::process::Command::new("sh")
-                .arg("-c")
-                .arg(&test_command)
-                .current_dir(path)
-                .output()
-                .await
-        }
+        let test_command = args.get("test_command").and_then(|v| v.as_str());
+        run_tests(path, test_command).await
+    }
+}
+
+/// Run the project's tests (auto-detected unless `test_command` is given)
+/// and summarize the result as `check_tests` reports it
+pub async fn run_tests(path: &str, test_command: Option<&str>) -> adk_core::Result<Value> {
+    // Auto-detect test command if not provided
+    let test_command = match test_command {
+        Some(cmd) => cmd.to_string(),
+        None => detect_test_command(path)?,
+    };
+
+    let output = run_shell(&test_command, path)
+        .await
         .map_err(|e| adk_core::AdkError::tool(format!("Failed to run tests: {}", e)))?;
 
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let success = output.status.success();
-
-        // Parse test results
-        let (passed, failed, total) = parse_test_output(&stdout, &stderr);
-
-        Ok(json!({
-            "status": if success { "passed" } else { "failed" },
-            "command": test_command,
-            "exit_code": output.status.code(),
-            "tests_passed": passed,
-            "tests_failed": failed,
-            "tests_total": total,
-            "stdout": stdout,
-            "stderr": stderr
-        }))
-    }
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let success = output.status.success();
+
+    // Parse test results
+    let (passed, failed, total) = parse_test_output(&stdout, &stderr);
+
+    Ok(json!({
+        "status": if success { "passed" } else { "failed" },
+        "command": test_command,
+        "exit_code": output.status.code(),
+        "tests_passed": passed,
+        "tests_failed": failed,
+        "tests_total": total,
+        "stdout": stdout,
+        "stderr": stderr
+    }))
 }
 
 // ============================================================================
@@ -133,54 +129,57 @@ impl Tool for CheckLintTool {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        // Auto-detect lint command if not provided
-        let lint_command = if let Some(cmd) = args.get("lint_command").and_then(|v| v.as_str()) {
-            cmd.to_string()
-        } else {
-            detect_lint_command(path, fix)?
-        };
-
-        // Execute lint command (platform-aware)
-        let output = if cfg!(target_os = "windows") {
-            tokio::process::Command::new("cmd")
-                .args(["/C", &lint_command])
-                .current_dir(path)
-                .output()
-                .await
-        } else {
-            tokio::process::Command::new("sh")
-                .arg("-c")
-                .arg(&lint_command)
-                .current_dir(path)
-                .output()
-                .await
-        }
+        let lint_command = args.get("lint_command").and_then(|v| v.as_str());
+        run_lint(path, lint_command, fix).await
+    }
+}
+
+/// Run the project's linter (auto-detected unless `lint_command` is given)
+/// and summarize the result as `check_lint` reports it
+pub async fn run_lint(path: &str, lint_command: Option<&str>, fix: bool) -> adk_core::Result<Value> {
+    // Auto-detect lint command if not provided
+    let lint_command = match lint_command {
+        Some(cmd) => cmd.to_string(),
+        None => detect_lint_command(path, fix)?,
+    };
+
+    let output = run_shell(&lint_command, path)
+        .await
         .map_err(|e| adk_core::AdkError::tool(format!("Failed to run linter: {}", e)))?;
 
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let success = output.status.success();
-
-        // Parse lint results
-        let (warnings, errors) = parse_lint_output(&stdout, &stderr);
-
-        Ok(json!({
-            "status": if success { "clean" } else { "issues_found" },
-            "command": lint_command,
-            "exit_code": output.status.code(),
-            "warnings": warnings,
-            "errors": errors,
-            "total_issues": warnings + errors,
-            "stdout": stdout,
-            "stderr": stderr
-        }))
-    }
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let success = output.status.success();
+
+    // Parse lint results
+    let (warnings, errors) = parse_lint_output(&stdout, &stderr);
+
+    Ok(json!({
+        "status": if success { "clean" } else { "issues_found" },
+        "command": lint_command,
+        "exit_code": output.status.code(),
+        "warnings": warnings,
+        "errors": errors,
+        "total_issues": warnings + errors,
+        "stdout": stdout,
+        "stderr": stderr
+    }))
 }
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// Longest a test or lint run may take
+const CHECK_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Run a shell command in `path` inside the project's sandbox
+async fn run_shell(command: &str, path: &str) -> Result<std::process::Output, SandboxError> {
+    let sandbox = Sandbox::for_workspace(path)
+        .map_err(|e| SandboxError::Unavailable(format!("{:#}", e)))?;
+    sandbox.run(command, Path::new(path), CHECK_TIMEOUT).await
+}
+
 /// Detect appropriate test command based on project type
 fn detect_test_command(path: &str) -> adk_core::Result<String> {
     let path_buf = Path::new(path);
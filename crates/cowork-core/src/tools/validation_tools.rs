@@ -4,7 +4,8 @@ use adk_core::{Tool, ToolContext};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::Arc;
-use super::get_required_string_param;
+use super::{get_optional_string_param, get_required_string_param, load_or_build_code_index};
+use crate::traceability::{TraceabilityFormat, TraceabilityMatrix};
 
 // ============================================================================
 // CheckDataFormatTool
@@ -263,4 +264,94 @@ fn detect_cycle(graph: &std::collections::HashMap<String, Vec<String>>) -> bool
     }
 
     false
-}
\ No newline at end of file
+}
+// ============================================================================
+// CheckTraceabilityTool
+// ============================================================================
+
+/// Build the traceability matrix for the current iteration from its
+/// structured data and the workspace's indexed source files
+pub fn build_traceability_matrix() -> anyhow::Result<TraceabilityMatrix> {
+    let iteration_id = get_iteration_id()
+        .ok_or_else(|| anyhow::anyhow!("Iteration ID not set. Call set_iteration_id() first."))?;
+    let index = load_or_build_code_index(&iteration_id)?;
+    let workspace_files: Vec<String> = index.files.iter().map(|f| f.path.clone()).collect();
+
+    Ok(TraceabilityMatrix::build(
+        &iteration_id,
+        &load_requirements()?,
+        &load_feature_list()?,
+        &load_implementation_plan()?,
+        Some(&load_code_metadata()?),
+        &workspace_files,
+    ))
+}
+
+pub struct CheckTraceabilityTool;
+
+#[async_trait]
+impl Tool for CheckTraceabilityTool {
+    fn name(&self) -> &str {
+        "check_traceability"
+    }
+
+    fn description(&self) -> &str {
+        "Build the traceability matrix (requirement → feature → task → file → test) and report gaps: \
+         requirements with no tasks, features/tasks not linked to a requirement, workspace files no task \
+         owns, planned files that are missing and files without tests. Optionally exports the matrix \
+         as Markdown, CSV or JSON into the iteration artifacts."
+    }
+
+    fn parameters_schema(&self) -> Option<Value> {
+        Some(json!({
+            "type": "object",
+            "properties": {
+                "format": {
+                    "type": "string",
+                    "enum": ["markdown", "csv", "json"],
+                    "description": "Export format for the full matrix (default: markdown)"
+                },
+                "save": {
+                    "type": "boolean",
+                    "description": "Save the export as artifacts/traceability.<ext> (default: false)"
+                },
+                "include_matrix": {
+                    "type": "boolean",
+                    "description": "Include the rendered matrix in the result (default: false, gaps only)"
+                }
+            }
+        }))
+    }
+
+    async fn execute(&self, _ctx: Arc<dyn ToolContext>, args: Value) -> adk_core::Result<Value> {
+        let format_name = get_optional_string_param(&args, "format").unwrap_or_else(|| "markdown".to_string());
+        let format = TraceabilityFormat::parse(&format_name)
+            .ok_or_else(|| adk_core::AdkError::tool(format!("Unknown format: {}", format_name)))?;
+        let save = args.get("save").and_then(|v| v.as_bool()).unwrap_or(false);
+        let include_matrix = args.get("include_matrix").and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let matrix = build_traceability_matrix()
+            .map_err(|e| adk_core::AdkError::tool(format!("Failed to build traceability matrix: {}", e)))?;
+        let rendered = matrix.render(format);
+
+        let saved_path = if save {
+            let path = save_traceability_matrix(format.extension(), &rendered)
+                .map_err(|e| adk_core::AdkError::tool(e.to_string()))?;
+            Some(path.display().to_string())
+        } else {
+            None
+        };
+
+        let status = if matrix.orphans.is_empty() { "complete" } else { "gaps_found" };
+        let mut result = json!({
+            "status": status,
+            "summary": matrix.summary,
+            "orphans": matrix.orphans,
+            "saved_to": saved_path
+        });
+        if include_matrix {
+            result["matrix"] = json!(rendered);
+        }
+        Ok(result)
+    }
+}
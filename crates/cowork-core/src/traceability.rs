@@ -0,0 +1,696 @@
+// Traceability Matrix - REQ → FEAT → TASK → file → test
+//
+// Assembles the links already recorded in the structured data
+// (`Requirement.related_features`, `Feature.requirement_ids` /
+// `assigned_to_tasks`, `Task.feature_id` / `files_to_create`,
+// `FileMetadata.task_id`) into one graph per iteration, reports the gaps and
+// renders it as Markdown, CSV or JSON.
+//
+// Links are merged from both directions, so a feature listed only in
+// `Requirement.related_features` (or only in `Feature.requirement_ids`) is
+// still traced. Tests are matched to files by naming convention
+// (`foo.test.ts`, `test_foo.py`, `foo_test.go`, `tests/foo.rs`, ...).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+
+use crate::data::{CodeMetadata, FeatureList, ImplementationPlan, Requirements, TaskStatus};
+
+/// Output formats supported by [`TraceabilityMatrix::render`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceabilityFormat {
+    Markdown,
+    Csv,
+    Json,
+}
+
+impl TraceabilityFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "markdown" | "md" => Some(Self::Markdown),
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// A source file reached through a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTrace {
+    pub path: String,
+    /// Whether the file exists in the workspace
+    pub exists: bool,
+    /// Test files covering this file (by naming convention)
+    pub tests: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTrace {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub files: Vec<FileTrace>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureTrace {
+    pub id: String,
+    pub name: String,
+    pub tasks: Vec<TaskTrace>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementTrace {
+    pub id: String,
+    pub title: String,
+    pub features: Vec<FeatureTrace>,
+}
+
+/// Gaps in the traceability chain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TraceabilityOrphans {
+    /// Requirements that no task implements
+    pub requirements_without_tasks: Vec<String>,
+    /// Features not linked to any known requirement
+    pub features_without_requirements: Vec<String>,
+    /// Tasks whose feature is missing or not linked to a requirement
+    pub tasks_without_requirements: Vec<String>,
+    /// Workspace source files not claimed by any task
+    pub files_without_task: Vec<String>,
+    /// Files planned by a task that do not exist in the workspace
+    pub missing_files: Vec<String>,
+    /// Traced (non-test) files with no matching test file
+    pub untested_files: Vec<String>,
+}
+
+impl TraceabilityOrphans {
+    pub fn is_empty(&self) -> bool {
+        self.requirements_without_tasks.is_empty()
+            && self.features_without_requirements.is_empty()
+            && self.tasks_without_requirements.is_empty()
+            && self.files_without_task.is_empty()
+            && self.missing_files.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TraceabilitySummary {
+    pub requirements: usize,
+    pub requirements_traced: usize,
+    pub features: usize,
+    pub tasks: usize,
+    pub files: usize,
+    pub tested_files: usize,
+}
+
+/// Traceability graph for one iteration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceabilityMatrix {
+    pub iteration_id: String,
+    pub generated_at: DateTime<Utc>,
+    pub requirements: Vec<RequirementTrace>,
+    pub orphans: TraceabilityOrphans,
+    pub summary: TraceabilitySummary,
+}
+
+impl TraceabilityMatrix {
+    /// Build the matrix from the iteration's structured data.
+    ///
+    /// `workspace_files` are the source files present in the workspace
+    /// (relative, `/`-separated); they decide file existence, test matching
+    /// and which files are orphans.
+    pub fn build(
+        iteration_id: &str,
+        requirements: &Requirements,
+        features: &FeatureList,
+        plan: &ImplementationPlan,
+        code_metadata: Option<&CodeMetadata>,
+        workspace_files: &[String],
+    ) -> Self {
+        let workspace: BTreeSet<String> = workspace_files.iter().map(|f| normalize_path(f)).collect();
+        let test_files: Vec<&String> = workspace.iter().filter(|f| is_test_file(f)).collect();
+
+        // REQ ↔ FEAT (both directions)
+        let mut features_by_req: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for req in &requirements.requirements {
+            let entry = features_by_req.entry(req.id.as_str()).or_default();
+            entry.extend(req.related_features.iter().map(String::as_str));
+        }
+        for feat in &features.features {
+            for req_id in &feat.requirement_ids {
+                features_by_req
+                    .entry(req_id.as_str())
+                    .or_default()
+                    .insert(feat.id.as_str());
+            }
+        }
+
+        // FEAT ↔ TASK (both directions)
+        let mut tasks_by_feature: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for feat in &features.features {
+            let entry = tasks_by_feature.entry(feat.id.as_str()).or_default();
+            entry.extend(feat.assigned_to_tasks.iter().map(String::as_str));
+        }
+        for task in &plan.tasks {
+            tasks_by_feature
+                .entry(task.feature_id.as_str())
+                .or_default()
+                .insert(task.id.as_str());
+        }
+
+        // TASK → files (planned files plus recorded code metadata)
+        let mut files_by_task: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for task in &plan.tasks {
+            files_by_task
+                .entry(task.id.as_str())
+                .or_default()
+                .extend(task.files_to_create.iter().map(|f| normalize_path(f)));
+        }
+        if let Some(metadata) = code_metadata {
+            for file in &metadata.files {
+                files_by_task
+                    .entry(file.task_id.as_str())
+                    .or_default()
+                    .insert(normalize_path(&file.path));
+            }
+        }
+
+        let known_requirements: HashSet<&str> =
+            requirements.requirements.iter().map(|r| r.id.as_str()).collect();
+
+        let mut orphans = TraceabilityOrphans::default();
+        let mut claimed_files: HashSet<String> = HashSet::new();
+        let mut traced_files: BTreeSet<String> = BTreeSet::new();
+        let mut tested_files: BTreeSet<String> = BTreeSet::new();
+
+        let trace_task = |task_id: &str,
+                          claimed: &mut HashSet<String>,
+                          traced: &mut BTreeSet<String>,
+                          tested: &mut BTreeSet<String>|
+         -> Option<TaskTrace> {
+            let task = plan.tasks.iter().find(|t| t.id == task_id)?;
+            let files = files_by_task
+                .get(task_id)
+                .into_iter()
+                .flatten()
+                .map(|path| {
+                    claimed.insert(path.clone());
+                    let tests = tests_for(path, &test_files);
+                    if !is_test_file(path) {
+                        traced.insert(path.clone());
+                        if !tests.is_empty() {
+                            tested.insert(path.clone());
+                        }
+                    }
+                    // Test files claimed through their source file are covered too
+                    claimed.extend(tests.iter().cloned());
+                    FileTrace {
+                        path: path.clone(),
+                        exists: workspace.contains(path),
+                        tests,
+                    }
+                })
+                .collect();
+            Some(TaskTrace {
+                id: task.id.clone(),
+                title: task.title.clone(),
+                status: task.status,
+                files,
+            })
+        };
+
+        let mut requirement_traces = Vec::new();
+        for req in &requirements.requirements {
+            let mut feature_traces = Vec::new();
+            for feat_id in features_by_req.get(req.id.as_str()).into_iter().flatten() {
+                let Some(feat) = features.features.iter().find(|f| f.id == *feat_id) else {
+                    continue;
+                };
+                let tasks: Vec<TaskTrace> = tasks_by_feature
+                    .get(feat.id.as_str())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|task_id| {
+                        trace_task(task_id, &mut claimed_files, &mut traced_files, &mut tested_files)
+                    })
+                    .collect();
+                feature_traces.push(FeatureTrace {
+                    id: feat.id.clone(),
+                    name: feat.name.clone(),
+                    tasks,
+                });
+            }
+
+            if feature_traces.iter().all(|f| f.tasks.is_empty()) {
+                orphans.requirements_without_tasks.push(req.id.clone());
+            }
+            requirement_traces.push(RequirementTrace {
+                id: req.id.clone(),
+                title: req.title.clone(),
+                features: feature_traces,
+            });
+        }
+
+        let linked_features: HashSet<&str> = features_by_req
+            .iter()
+            .filter(|(req_id, _)| known_requirements.contains(*req_id))
+            .flat_map(|(_, feats)| feats.iter().copied())
+            .collect();
+        orphans.features_without_requirements = features
+            .features
+            .iter()
+            .filter(|f| !linked_features.contains(f.id.as_str()))
+            .map(|f| f.id.clone())
+            .collect();
+
+        // Tasks are reachable through any of their linked features
+        let reachable_tasks: HashSet<&str> = linked_features
+            .iter()
+            .filter_map(|feat_id| tasks_by_feature.get(feat_id))
+            .flatten()
+            .copied()
+            .collect();
+        orphans.tasks_without_requirements = plan
+            .tasks
+            .iter()
+            .filter(|t| !reachable_tasks.contains(t.id.as_str()))
+            .map(|t| t.id.clone())
+            .collect();
+
+        // Files of unreachable tasks still belong to a task
+        for task in &plan.tasks {
+            if !reachable_tasks.contains(task.id.as_str()) {
+                trace_task(&task.id, &mut claimed_files, &mut traced_files, &mut tested_files);
+            }
+        }
+
+        orphans.files_without_task = workspace
+            .iter()
+            .filter(|f| !claimed_files.contains(*f))
+            .cloned()
+            .collect();
+        orphans.missing_files = files_by_task
+            .values()
+            .flatten()
+            .filter(|f| !workspace.contains(*f))
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        orphans.untested_files = traced_files
+            .iter()
+            .filter(|f| workspace.contains(*f) && !tested_files.contains(*f))
+            .cloned()
+            .collect();
+
+        let summary = TraceabilitySummary {
+            requirements: requirements.requirements.len(),
+            requirements_traced: requirements.requirements.len()
+                - orphans.requirements_without_tasks.len(),
+            features: features.features.len(),
+            tasks: plan.tasks.len(),
+            files: traced_files.len(),
+            tested_files: tested_files.len(),
+        };
+
+        Self {
+            iteration_id: iteration_id.to_string(),
+            generated_at: Utc::now(),
+            requirements: requirement_traces,
+            orphans,
+            summary,
+        }
+    }
+
+    pub fn render(&self, format: TraceabilityFormat) -> String {
+        match format {
+            TraceabilityFormat::Markdown => self.to_markdown(),
+            TraceabilityFormat::Csv => self.to_csv(),
+            TraceabilityFormat::Json => self.to_json(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// One row per requirement/feature/task/file path through the graph.
+    /// Missing links leave the trailing columns empty.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("requirement_id,requirement_title,feature_id,task_id,task_status,file,file_exists,tests\n");
+        for row in self.rows() {
+            let fields = [
+                row.requirement_id.to_string(),
+                row.requirement_title.to_string(),
+                row.feature_id.unwrap_or_default().to_string(),
+                row.task_id.unwrap_or_default().to_string(),
+                row.task_status.map(task_status_label).unwrap_or_default().to_string(),
+                row.file.map(|f| f.path.clone()).unwrap_or_default(),
+                row.file.map(|f| f.exists.to_string()).unwrap_or_default(),
+                row.file.map(|f| f.tests.join(";")).unwrap_or_default(),
+            ];
+            let line: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+            out.push_str(&line.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let s = &self.summary;
+        let mut out = format!(
+            "# Traceability Matrix\n\n\
+             - Iteration: {}\n\
+             - Requirements traced to tasks: {}/{}\n\
+             - Features: {} · Tasks: {}\n\
+             - Files with tests: {}/{}\n\n",
+            self.iteration_id, s.requirements_traced, s.requirements, s.features, s.tasks, s.tested_files, s.files
+        );
+
+        out.push_str("| Requirement | Feature | Task | File | Tests |\n|---|---|---|---|---|\n");
+        for row in self.rows() {
+            let file = match row.file {
+                Some(f) if f.exists => format!("`{}`", f.path),
+                Some(f) => format!("`{}` (missing)", f.path),
+                None => "—".to_string(),
+            };
+            let tests = match row.file {
+                Some(f) if !f.tests.is_empty() => f
+                    .tests
+                    .iter()
+                    .map(|t| format!("`{}`", t))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                _ => "—".to_string(),
+            };
+            out.push_str(&format!(
+                "| {} {} | {} | {} | {} | {} |\n",
+                row.requirement_id,
+                md_escape(row.requirement_title),
+                row.feature_id.unwrap_or("—"),
+                row.task_id.unwrap_or("—"),
+                file,
+                tests
+            ));
+        }
+
+        out.push_str("\n## Gaps\n\n");
+        let o = &self.orphans;
+        let sections: [(&str, &Vec<String>); 6] = [
+            ("Requirements without tasks", &o.requirements_without_tasks),
+            ("Features without requirements", &o.features_without_requirements),
+            ("Tasks without requirements", &o.tasks_without_requirements),
+            ("Files without task", &o.files_without_task),
+            ("Planned files missing from workspace", &o.missing_files),
+            ("Files without tests", &o.untested_files),
+        ];
+        let mut any = false;
+        for (title, items) in sections {
+            if items.is_empty() {
+                continue;
+            }
+            any = true;
+            out.push_str(&format!("### {} ({})\n", title, items.len()));
+            for item in items {
+                out.push_str(&format!("- {}\n", item));
+            }
+            out.push('\n');
+        }
+        if !any {
+            out.push_str("No gaps found.\n");
+        }
+        out
+    }
+
+    fn rows(&self) -> Vec<MatrixRow<'_>> {
+        let mut rows = Vec::new();
+        for req in &self.requirements {
+            let base = MatrixRow {
+                requirement_id: &req.id,
+                requirement_title: &req.title,
+                feature_id: None,
+                task_id: None,
+                task_status: None,
+                file: None,
+            };
+            if req.features.is_empty() {
+                rows.push(base);
+                continue;
+            }
+            for feat in &req.features {
+                let feat_row = MatrixRow { feature_id: Some(&feat.id), ..base };
+                if feat.tasks.is_empty() {
+                    rows.push(feat_row);
+                    continue;
+                }
+                for task in &feat.tasks {
+                    let task_row = MatrixRow {
+                        task_id: Some(&task.id),
+                        task_status: Some(task.status),
+                        ..feat_row
+                    };
+                    if task.files.is_empty() {
+                        rows.push(task_row);
+                        continue;
+                    }
+                    for file in &task.files {
+                        rows.push(MatrixRow { file: Some(file), ..task_row });
+                    }
+                }
+            }
+        }
+        rows
+    }
+}
+
+#[derive(Clone, Copy)]
+struct MatrixRow<'a> {
+    requirement_id: &'a str,
+    requirement_title: &'a str,
+    feature_id: Option<&'a str>,
+    task_id: Option<&'a str>,
+    task_status: Option<TaskStatus>,
+    file: Option<&'a FileTrace>,
+}
+
+fn task_status_label(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => "pending",
+        TaskStatus::InProgress => "in_progress",
+        TaskStatus::Completed => "completed",
+        TaskStatus::Blocked => "blocked",
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    let path = path.trim().replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+/// Whether a path looks like a test file by common conventions
+pub fn is_test_file(path: &str) -> bool {
+    let path = normalize_path(path);
+    if path.split('/').any(|seg| matches!(seg, "tests" | "test" | "__tests__" | "spec")) {
+        return true;
+    }
+    let file_name = path.rsplit('/').next().unwrap_or(&path);
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    file_name.contains(".test.")
+        || file_name.contains(".spec.")
+        || stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_spec")
+}
+
+/// Reduce a file name to the name of the unit it tests (`test_foo.py` → `foo`)
+fn tested_unit_name(path: &str) -> String {
+    let file_name = Path::new(path)
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem = file_name.split('.').next().unwrap_or("").to_string();
+    let stem = stem.strip_prefix("test_").unwrap_or(&stem);
+    let stem = stem
+        .strip_suffix("_test")
+        .or_else(|| stem.strip_suffix("_spec"))
+        .unwrap_or(stem);
+    stem.to_lowercase()
+}
+
+fn tests_for(path: &str, test_files: &[&String]) -> Vec<String> {
+    if is_test_file(path) {
+        return Vec::new();
+    }
+    let unit = tested_unit_name(path);
+    if unit.is_empty() {
+        return Vec::new();
+    }
+    test_files
+        .iter()
+        .filter(|t| tested_unit_name(t) == unit)
+        .map(|t| t.to_string())
+        .collect()
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn md_escape(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::*;
+
+    fn sample() -> (Requirements, FeatureList, ImplementationPlan) {
+        let mut requirements = Requirements::new();
+        for (id, title, feats) in [
+            ("REQ-001", "Login", vec!["FEAT-001"]),
+            ("REQ-002", "Export", vec![]),
+        ] {
+            requirements.requirements.push(Requirement {
+                id: id.to_string(),
+                title: title.to_string(),
+                description: String::new(),
+                priority: Priority::High,
+                category: RequirementCategory::Functional,
+                acceptance_criteria: vec![],
+                related_features: feats.into_iter().map(String::from).collect(),
+            });
+        }
+
+        let mut features = FeatureList::new();
+        for (id, reqs) in [("FEAT-001", vec![]), ("FEAT-002", vec!["REQ-404"])] {
+            features.features.push(Feature {
+                id: id.to_string(),
+                name: id.to_lowercase(),
+                description: String::new(),
+                requirement_ids: reqs.into_iter().map(String::from).collect(),
+                status: FeatureStatus::Pending,
+                assigned_to_tasks: vec![],
+                completion_criteria: vec![],
+                created_at: Utc::now(),
+                completed_at: None,
+                metadata: FeatureMetadata::default(),
+            });
+        }
+
+        let mut plan = ImplementationPlan::new();
+        for (id, feat, files) in [
+            ("TASK-001", "FEAT-001", vec!["./src/auth.ts", "src/session.ts"]),
+            ("TASK-002", "FEAT-002", vec!["src/export.ts"]),
+        ] {
+            plan.tasks.push(Task {
+                id: id.to_string(),
+                title: id.to_string(),
+                description: String::new(),
+                feature_id: feat.to_string(),
+                component_id: String::new(),
+                status: TaskStatus::Completed,
+                dependencies: vec![],
+                estimated_effort: None,
+                files_to_create: files.into_iter().map(String::from).collect(),
+                acceptance_criteria: vec![],
+                created_at: Utc::now(),
+                started_at: None,
+                completed_at: None,
+            });
+        }
+        (requirements, features, plan)
+    }
+
+    fn workspace() -> Vec<String> {
+        ["src/auth.ts", "src/auth.test.ts", "src/export.ts", "src/util.ts"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_links_chain_and_reports_orphans() {
+        let (requirements, features, plan) = sample();
+        let matrix = TraceabilityMatrix::build("iter-1", &requirements, &features, &plan, None, &workspace());
+
+        let login = &matrix.requirements[0];
+        assert_eq!(login.features[0].id, "FEAT-001");
+        let task = &login.features[0].tasks[0];
+        assert_eq!(task.id, "TASK-001");
+        assert_eq!(task.files[0].path, "src/auth.ts");
+        assert_eq!(task.files[0].tests, vec!["src/auth.test.ts"]);
+        assert!(!task.files[1].exists);
+
+        let o = &matrix.orphans;
+        assert_eq!(o.requirements_without_tasks, vec!["REQ-002"]);
+        assert_eq!(o.features_without_requirements, vec!["FEAT-002"]);
+        assert_eq!(o.tasks_without_requirements, vec!["TASK-002"]);
+        assert_eq!(o.files_without_task, vec!["src/util.ts"]);
+        assert_eq!(o.missing_files, vec!["src/session.ts"]);
+        assert_eq!(o.untested_files, vec!["src/export.ts"]);
+        assert_eq!(matrix.summary.requirements_traced, 1);
+    }
+
+    #[test]
+    fn test_code_metadata_claims_files() {
+        let (requirements, features, plan) = sample();
+        let mut metadata = CodeMetadata::new();
+        metadata.files.push(FileMetadata {
+            path: "src/util.ts".to_string(),
+            task_id: "TASK-001".to_string(),
+            feature_id: None,
+            component_id: None,
+            created_at: Utc::now(),
+            last_modified: Utc::now(),
+            lines_of_code: 10,
+            test_coverage: 0.0,
+        });
+        let matrix =
+            TraceabilityMatrix::build("iter-1", &requirements, &features, &plan, Some(&metadata), &workspace());
+        assert!(matrix.orphans.files_without_task.is_empty());
+    }
+
+    #[test]
+    fn test_exports() {
+        let (requirements, features, plan) = sample();
+        let matrix = TraceabilityMatrix::build("iter-1", &requirements, &features, &plan, None, &workspace());
+
+        let csv = matrix.to_csv();
+        assert!(csv.starts_with("requirement_id,"));
+        assert!(csv.contains("REQ-001,Login,FEAT-001,TASK-001,completed,src/auth.ts,true,src/auth.test.ts"));
+        assert!(csv.contains("REQ-002,Export,,,,,,"));
+
+        let md = matrix.to_markdown();
+        assert!(md.contains("Requirements traced to tasks: 1/2"));
+        assert!(md.contains("### Requirements without tasks (1)"));
+
+        let parsed: TraceabilityMatrix = serde_json::from_str(&matrix.to_json()).unwrap();
+        assert_eq!(parsed.requirements.len(), 2);
+    }
+
+    #[test]
+    fn test_is_test_file() {
+        assert!(is_test_file("src/foo.test.ts"));
+        assert!(is_test_file("test_foo.py"));
+        assert!(is_test_file("pkg/foo_test.go"));
+        assert!(is_test_file("tests/integration.rs"));
+        assert!(!is_test_file("src/contest.rs"));
+        assert_eq!(tested_unit_name("tests/test_auth.py"), "auth");
+    }
+}
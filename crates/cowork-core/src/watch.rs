@@ -0,0 +1,383 @@
+// Watch an iteration workspace and re-run its checks when files change
+//
+// `WorkspaceWatcher` polls the workspace file tree (honouring `.gitignore`
+// through the `ignore` crate, so `target/` and `node_modules/` churn is not a
+// change) and hands back a batch of changed paths once edits have settled for
+// the debounce interval. `CheckRun` holds the outcome of the same
+// `check_tests`/`check_lint` commands the check agent uses, and
+// `CheckRun::regressions` lists what got worse since the previous run.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::tools::test_lint_tools::{run_lint, run_tests};
+
+/// How often the workspace is rescanned
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time and size of every watched file, by relative path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkspaceSnapshot {
+    files: BTreeMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl WorkspaceSnapshot {
+    pub fn capture(root: &Path) -> Self {
+        let mut files = BTreeMap::new();
+        let walker = ignore::WalkBuilder::new(root)
+            .hidden(false)
+            .require_git(false)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build();
+
+        for entry in walker.flatten() {
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(root) else {
+                continue;
+            };
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            files.insert(relative.to_path_buf(), (metadata.modified().ok(), metadata.len()));
+        }
+        Self { files }
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Paths added, modified or removed in `newer`
+    pub fn changed_paths(&self, newer: &WorkspaceSnapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = newer
+            .files
+            .iter()
+            .filter(|(path, stamp)| self.files.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(self.files.keys().filter(|p| !newer.files.contains_key(*p)).cloned());
+        changed.sort();
+        changed
+    }
+}
+
+/// Polls a workspace for changes
+pub struct WorkspaceWatcher {
+    root: PathBuf,
+    snapshot: WorkspaceSnapshot,
+    debounce: Duration,
+}
+
+impl WorkspaceWatcher {
+    pub fn new(root: &Path, debounce: Duration) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            snapshot: WorkspaceSnapshot::capture(root),
+            debounce,
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Number of files currently watched
+    pub fn file_count(&self) -> usize {
+        self.snapshot.len()
+    }
+
+    /// Wait for the next batch of changes: returns once at least one file
+    /// changed and nothing else changed for the debounce interval
+    pub async fn next_change(&mut self) -> Vec<PathBuf> {
+        let mut pending = BTreeSet::new();
+        let mut last_change = Instant::now();
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let root = self.root.clone();
+            let current = tokio::task::spawn_blocking(move || WorkspaceSnapshot::capture(&root))
+                .await
+                .unwrap_or_default();
+
+            let changed = self.snapshot.changed_paths(&current);
+            if !changed.is_empty() {
+                pending.extend(changed);
+                last_change = Instant::now();
+                self.snapshot = current;
+            } else if !pending.is_empty() && last_change.elapsed() >= self.debounce {
+                return pending.into_iter().collect();
+            }
+        }
+    }
+}
+
+/// Which checks to run, and overrides for the auto-detected commands
+#[derive(Debug, Clone)]
+pub struct CheckOptions {
+    pub tests: bool,
+    pub lint: bool,
+    pub test_command: Option<String>,
+    pub lint_command: Option<String>,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        Self {
+            tests: true,
+            lint: true,
+            test_command: None,
+            lint_command: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestOutcome {
+    pub command: String,
+    pub passed: bool,
+    pub tests_passed: u64,
+    pub tests_failed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintOutcome {
+    pub command: String,
+    pub clean: bool,
+    pub warnings: u64,
+    pub errors: u64,
+}
+
+/// Result of one run of `check_tests` and `check_lint`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    pub ran_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TestOutcome>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lint: Option<LintOutcome>,
+    /// Checks that could not run (undetected project type, missing tool)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+fn count(value: &Value, key: &str) -> u64 {
+    value.get(key).and_then(Value::as_u64).unwrap_or(0)
+}
+
+fn command(value: &Value) -> String {
+    value.get("command").and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+impl CheckRun {
+    /// Run the enabled checks in `workspace`
+    pub async fn run(workspace: &Path, options: &CheckOptions) -> Self {
+        let path = workspace.to_string_lossy().to_string();
+        let mut run = CheckRun {
+            ran_at: Utc::now(),
+            tests: None,
+            lint: None,
+            skipped: Vec::new(),
+        };
+
+        if options.tests {
+            match run_tests(&path, options.test_command.as_deref()).await {
+                Ok(result) => run.tests = Some(TestOutcome::from_result(&result)),
+                Err(e) => run.skipped.push(format!("tests: {}", e)),
+            }
+        }
+        if options.lint {
+            match run_lint(&path, options.lint_command.as_deref(), false).await {
+                Ok(result) => run.lint = Some(LintOutcome::from_result(&result)),
+                Err(e) => run.skipped.push(format!("lint: {}", e)),
+            }
+        }
+        run
+    }
+
+    /// Whether any check outcome differs from `previous`
+    pub fn differs_from(&self, previous: &CheckRun) -> bool {
+        self.tests != previous.tests || self.lint != previous.lint
+    }
+
+    /// What got worse since `previous`
+    pub fn regressions(&self, previous: &CheckRun) -> Vec<String> {
+        let mut regressions = Vec::new();
+        if let (Some(before), Some(now)) = (&previous.tests, &self.tests) {
+            if before.passed && !now.passed {
+                regressions.push(format!("Tests now fail (`{}`)", now.command));
+            }
+            if now.tests_failed > before.tests_failed {
+                regressions.push(format!(
+                    "Failing tests: {} → {}",
+                    before.tests_failed, now.tests_failed
+                ));
+            }
+        }
+        if let (Some(before), Some(now)) = (&previous.lint, &self.lint) {
+            if before.clean && !now.clean {
+                regressions.push(format!("Lint no longer clean (`{}`)", now.command));
+            }
+            if now.errors > before.errors {
+                regressions.push(format!("Lint errors: {} → {}", before.errors, now.errors));
+            }
+            if now.warnings > before.warnings {
+                regressions.push(format!("Lint warnings: {} → {}", before.warnings, now.warnings));
+            }
+        }
+        regressions
+    }
+
+    /// What got better since `previous` (the mirror of `regressions`)
+    pub fn improvements(&self, previous: &CheckRun) -> Vec<String> {
+        previous
+            .regressions(self)
+            .into_iter()
+            .map(|r| {
+                r.replace("Tests now fail", "Tests pass again")
+                    .replace("Lint no longer clean", "Lint clean again")
+            })
+            .collect()
+    }
+
+    /// One-line summary, e.g. `tests: 12 passed, 1 failed · lint: 0 errors, 3 warnings`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(t) = &self.tests {
+            parts.push(format!(
+                "tests {}: {} passed, {} failed",
+                if t.passed { "✅" } else { "❌" },
+                t.tests_passed,
+                t.tests_failed
+            ));
+        }
+        if let Some(l) = &self.lint {
+            parts.push(format!(
+                "lint {}: {} errors, {} warnings",
+                if l.clean { "✅" } else { "⚠️" },
+                l.errors,
+                l.warnings
+            ));
+        }
+        if parts.is_empty() {
+            "no checks ran".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
+}
+
+impl TestOutcome {
+    fn from_result(result: &Value) -> Self {
+        Self {
+            command: command(result),
+            passed: result.get("status").and_then(Value::as_str) == Some("passed"),
+            tests_passed: count(result, "tests_passed"),
+            tests_failed: count(result, "tests_failed"),
+        }
+    }
+}
+
+impl LintOutcome {
+    fn from_result(result: &Value) -> Self {
+        Self {
+            command: command(result),
+            clean: result.get("status").and_then(Value::as_str) == Some("clean"),
+            warnings: count(result, "warnings"),
+            errors: count(result, "errors"),
+        }
+    }
+}
+
+/// Feedback for the check agent describing how results changed
+pub fn check_agent_feedback(previous: &CheckRun, current: &CheckRun, changed_files: &[PathBuf]) -> String {
+    let mut feedback = format!(
+        "The workspace was edited while being watched ({} file(s) changed). Check results went from [{}] to [{}].",
+        changed_files.len(),
+        previous.summary(),
+        current.summary()
+    );
+    let regressions = current.regressions(previous);
+    if !regressions.is_empty() {
+        feedback.push_str("\nRegressions:\n");
+        for r in &regressions {
+            feedback.push_str(&format!("- {}\n", r));
+        }
+    }
+    feedback.push_str("\nRe-check the project and update the check report accordingly.");
+    feedback
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(tests_failed: u64, warnings: u64) -> CheckRun {
+        CheckRun {
+            ran_at: Utc::now(),
+            tests: Some(TestOutcome {
+                command: "cargo test".to_string(),
+                passed: tests_failed == 0,
+                tests_passed: 10,
+                tests_failed,
+            }),
+            lint: Some(LintOutcome {
+                command: "cargo clippy".to_string(),
+                clean: warnings == 0,
+                warnings,
+                errors: 0,
+            }),
+            skipped: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_regressions_and_improvements() {
+        let green = run(0, 0);
+        let red = run(2, 1);
+
+        assert!(green.regressions(&green).is_empty());
+        assert!(!red.differs_from(&red));
+        assert!(red.differs_from(&green));
+
+        let regressions = red.regressions(&green);
+        assert_eq!(regressions.len(), 4);
+        assert!(regressions[0].starts_with("Tests now fail"));
+
+        let improvements = green.improvements(&red);
+        assert!(improvements[0].starts_with("Tests pass again"));
+        assert!(green.improvements(&green).is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_respects_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+        std::fs::create_dir_all(dir.path().join("target")).unwrap();
+        std::fs::write(dir.path().join("target/out"), "bin").unwrap();
+
+        let before = WorkspaceSnapshot::capture(dir.path());
+        assert_eq!(before.len(), 2);
+
+        std::fs::write(dir.path().join("target/out"), "rebuilt binary").unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() { println!(); }").unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "").unwrap();
+        let after = WorkspaceSnapshot::capture(dir.path());
+
+        assert_eq!(
+            before.changed_paths(&after),
+            vec![PathBuf::from("lib.rs"), PathBuf::from("main.rs")]
+        );
+    }
+}
@@ -392,6 +392,45 @@ pub async fn get_memory_context(iteration_id: Option<String>) -> Result<serde_js
     }))
 }
 
+/// List global (cross-project) memory entries
+#[tauri::command]
+pub async fn query_global_memory() -> Result<serde_json::Value, String> {
+    let store = MemoryStore::new();
+    let global = store.load_global_memory().map_err(|e| e.to_string())?;
+
+    Ok(serde_json::json!({
+        "enabled": cowork_core::persistence::is_global_memory_enabled(),
+        "decisions": global.decisions,
+        "patterns": global.patterns,
+    }))
+}
+
+/// Promote a project decision or pattern into global memory
+#[tauri::command]
+pub async fn promote_to_global_memory(memory_id: String) -> Result<serde_json::Value, String> {
+    let store = MemoryStore::new();
+
+    if memory_id.starts_with("pat-") {
+        let pattern = store.promote_pattern_to_global(&memory_id).map_err(|e| e.to_string())?;
+        return Ok(serde_json::json!({ "message": format!("Pattern '{}' promoted to global memory", pattern.name), "memory_id": memory_id }));
+    }
+
+    if memory_id.starts_with("dec-") {
+        let decision = store.promote_decision_to_global(&memory_id).map_err(|e| e.to_string())?;
+        return Ok(serde_json::json!({ "message": format!("Decision '{}' promoted to global memory", decision.title), "memory_id": memory_id }));
+    }
+
+    Err(format!("Only project decisions and patterns can be promoted: {}", memory_id))
+}
+
+/// Remove an entry from global memory
+#[tauri::command]
+pub async fn remove_global_memory(memory_id: String) -> Result<bool, String> {
+    MemoryStore::new()
+        .remove_global_entry(&memory_id)
+        .map_err(|e| e.to_string())
+}
+
 /// Get available stages from memory data
 #[tauri::command]
 pub async fn get_available_stages(iteration_id: String) -> Result<serde_json::Value, String> {
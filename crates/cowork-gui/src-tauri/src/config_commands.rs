@@ -4,6 +4,7 @@ use cowork_core::config_definition::{AgentDefinition, FlowDefinition, StageDefin
 use cowork_core::instructions::*;
 use cowork_core::llm::config::{self, LlmConfig, McpConfig, ModelConfig};
 use cowork_core::skills::SkillManager;
+use cowork_core::tools::MCP_TOOL_PREFIX;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -748,7 +749,7 @@ pub struct ToolInfo {
 #[tauri::command]
 pub async fn gui_get_available_tools() -> Result<Vec<ToolInfo>, String> {
     // This list should match the tools supported in agent_factory.rs
-    let tools = vec![
+    let mut tools = vec![
         // Idea tools
         ToolInfo {
             id: "save_idea".to_string(),
@@ -830,6 +831,18 @@ pub async fn gui_get_available_tools() -> Result<Vec<ToolInfo>, String> {
             category: "File".to_string(),
             description: "Write content to a file in the workspace".to_string(),
         },
+        ToolInfo {
+            id: "edit_file".to_string(),
+            name: "Edit File".to_string(),
+            category: "File".to_string(),
+            description: "Replace a unique snippet in a workspace file".to_string(),
+        },
+        ToolInfo {
+            id: "apply_patch".to_string(),
+            name: "Apply Patch".to_string(),
+            category: "File".to_string(),
+            description: "Apply a unified diff to workspace files".to_string(),
+        },
         ToolInfo {
             id: "list_files".to_string(),
             name: "List Files".to_string(),
@@ -848,6 +861,49 @@ pub async fn gui_get_available_tools() -> Result<Vec<ToolInfo>, String> {
             category: "File".to_string(),
             description: "Read a file with intelligent truncation for large files".to_string(),
         },
+        ToolInfo {
+            id: "query_code_index".to_string(),
+            name: "Query Code Index".to_string(),
+            category: "File".to_string(),
+            description: "Look up symbols, file outlines and import dependencies in the workspace".to_string(),
+        },
+        ToolInfo {
+            id: "search_code".to_string(),
+            name: "Search Code".to_string(),
+            category: "File".to_string(),
+            description: "Regex search over workspace files with context lines".to_string(),
+        },
+        ToolInfo {
+            id: "find_files".to_string(),
+            name: "Find Files".to_string(),
+            category: "File".to_string(),
+            description: "Find workspace files matching a glob".to_string(),
+        },
+        // Process tools
+        ToolInfo {
+            id: "start_process".to_string(),
+            name: "Start Process".to_string(),
+            category: "Process".to_string(),
+            description: "Start a dev server in the background with a free port and readiness check".to_string(),
+        },
+        ToolInfo {
+            id: "read_process_output".to_string(),
+            name: "Read Process Output".to_string(),
+            category: "Process".to_string(),
+            description: "Read the output of a background process".to_string(),
+        },
+        ToolInfo {
+            id: "http_probe".to_string(),
+            name: "HTTP Probe".to_string(),
+            category: "Process".to_string(),
+            description: "Send an HTTP request to a locally running server".to_string(),
+        },
+        ToolInfo {
+            id: "stop_process".to_string(),
+            name: "Stop Process".to_string(),
+            category: "Process".to_string(),
+            description: "Stop a background process and everything it started".to_string(),
+        },
         // Document tools (Project Iteration Files)
         ToolInfo {
             id: "load_idea".to_string(),
@@ -942,6 +998,12 @@ pub async fn gui_get_available_tools() -> Result<Vec<ToolInfo>, String> {
             category: "Validation".to_string(),
             description: "Validate data format consistency".to_string(),
         },
+        ToolInfo {
+            id: "check_traceability".to_string(),
+            name: "Check Traceability".to_string(),
+            category: "Validation".to_string(),
+            description: "Trace requirements to features, tasks, files and tests".to_string(),
+        },
         // HITL tools
         ToolInfo {
             id: "provide_feedback".to_string(),
@@ -1033,6 +1095,24 @@ pub async fn gui_get_available_tools() -> Result<Vec<ToolInfo>, String> {
         },
     ];
 
+    // MCP servers connected at startup: the whole server, then each tool
+    for server in cowork_core::config_definition::agent_factory::get_mcp_toolsets() {
+        tools.push(ToolInfo {
+            id: format!("{}{}", MCP_TOOL_PREFIX, server.name),
+            name: format!("{} (all tools)", server.name),
+            category: format!("MCP: {}", server.name),
+            description: format!("Every tool from the {} MCP server", server.name),
+        });
+        for tool in &server.tools {
+            tools.push(ToolInfo {
+                id: format!("{}{}/{}", MCP_TOOL_PREFIX, server.name, tool.name),
+                name: tool.name.clone(),
+                category: format!("MCP: {}", server.name),
+                description: tool.description.clone(),
+            });
+        }
+    }
+
     Ok(tools)
 }
 
@@ -2,10 +2,11 @@
 
 use crate::AppState;
 use crate::TauriBackend;
+use cowork_core::data::IterationDataDiff;
 use cowork_core::domain::{Iteration, InheritanceMode, Project, IterationStatus};
 use cowork_core::llm::create_llm_client;
 use cowork_core::llm::config::load_config;
-use cowork_core::persistence::{IterationStore, ProjectStore, MemoryStore};
+use cowork_core::persistence::{resolve_artifact_name, ArtifactHistory, ArtifactRevision, IterationStore, ProjectStore, MemoryStore};
 use cowork_core::pipeline::IterationExecutor;
 use tauri::{Emitter, Manager, State, Window};
 use std::sync::Arc;
@@ -400,6 +401,85 @@ pub async fn gui_get_project_knowledge(
     }))
 }
 
+/// Structured diff of requirements/features/design/plan between two iterations
+#[tauri::command]
+pub async fn gui_diff_iterations(
+    from_iteration_id: String,
+    to_iteration_id: String,
+) -> Result<IterationDataDiff, String> {
+    let iteration_store = IterationStore::new();
+    for id in [&from_iteration_id, &to_iteration_id] {
+        if !iteration_store.exists(id) {
+            return Err(format!("Iteration '{}' not found", id));
+        }
+    }
+
+    IterationDataDiff::between(&from_iteration_id, &to_iteration_id)
+        .map_err(|e| format!("Failed to diff iterations: {}", e))
+}
+
+fn open_artifact_history(iteration_id: &str, artifact: &str) -> Result<ArtifactHistory, String> {
+    let filename = resolve_artifact_name(artifact).map_err(|e| e.to_string())?;
+    ArtifactHistory::open(iteration_id, filename).map_err(|e| e.to_string())
+}
+
+/// Revisions of an artifact (`idea`, `prd`, `design`, ...), oldest first
+#[tauri::command]
+pub async fn gui_get_artifact_revisions(
+    iteration_id: String,
+    artifact: String,
+) -> Result<Vec<ArtifactRevision>, String> {
+    open_artifact_history(&iteration_id, &artifact)?
+        .revisions()
+        .map_err(|e| format!("Failed to load revisions: {}", e))
+}
+
+/// Two revisions of an artifact for a side-by-side view
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactRevisionPair {
+    pub from: ArtifactRevision,
+    pub to: ArtifactRevision,
+    pub from_content: String,
+    pub to_content: String,
+}
+
+#[tauri::command]
+pub async fn gui_compare_artifact_revisions(
+    iteration_id: String,
+    artifact: String,
+    from_revision: u32,
+    to_revision: u32,
+) -> Result<ArtifactRevisionPair, String> {
+    let history = open_artifact_history(&iteration_id, &artifact)?;
+    let revisions = history.revisions().map_err(|e| e.to_string())?;
+    let find = |n: u32| {
+        revisions
+            .iter()
+            .find(|r| r.revision == n)
+            .cloned()
+            .ok_or_else(|| format!("{} has no revision {}", history.filename(), n))
+    };
+
+    Ok(ArtifactRevisionPair {
+        from: find(from_revision)?,
+        to: find(to_revision)?,
+        from_content: history.load(from_revision).map_err(|e| e.to_string())?,
+        to_content: history.load(to_revision).map_err(|e| e.to_string())?,
+    })
+}
+
+/// Make an earlier revision current again (recorded as a new `human` revision)
+#[tauri::command]
+pub async fn gui_restore_artifact_revision(
+    iteration_id: String,
+    artifact: String,
+    revision: u32,
+) -> Result<ArtifactRevision, String> {
+    open_artifact_history(&iteration_id, &artifact)?
+        .restore(revision)
+        .map_err(|e| format!("Failed to restore revision: {}", e))
+}
+
 #[tauri::command]
 pub async fn gui_regenerate_knowledge(
     iteration_id: String,
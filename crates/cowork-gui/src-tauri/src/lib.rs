@@ -847,6 +847,10 @@ pub fn run() {
             iteration_commands::gui_delete_iteration,
             iteration_commands::gui_get_project_knowledge,
             iteration_commands::gui_regenerate_knowledge,
+            iteration_commands::gui_diff_iterations,
+            iteration_commands::gui_get_artifact_revisions,
+            iteration_commands::gui_compare_artifact_revisions,
+            iteration_commands::gui_restore_artifact_revision,
             // File commands
             file::open_in_file_manager,
             file::get_iteration_artifacts,
@@ -872,6 +876,9 @@ pub fn run() {
             memory::promote_to_project_memory,
             memory::get_memory_context,
             memory::get_available_stages,
+            memory::query_global_memory,
+            memory::promote_to_global_memory,
+            memory::remove_global_memory,
             // Template commands
             template::get_templates,
             template::export_template,
@@ -7,6 +7,7 @@ use tokio::sync::mpsc;
 use tracing;
 
 use crate::commands::path_utils;
+use cowork_core::process::kill_process_tree;
 
 // Import PreviewInfo from gui_types
 use super::gui_types::PreviewInfo;
@@ -15,66 +16,6 @@ use super::gui_types::PreviewInfo;
 #[allow(unused_imports)]
 use std::os::windows::process::CommandExt;
 
-/// Kill a process and all its descendants (the entire process tree).
-///
-/// On Windows, uses `taskkill /T /F` which walks the process tree and force-kills
-/// all descendants. This is critical because `child.kill()` only terminates the
-/// direct child (e.g. `cmd.exe`), leaving the real dev server (`bun.exe`/`node.exe`)
-/// orphaned and still holding the port.
-///
-/// On Unix, kills the entire process group. The child must have been spawned with
-/// `process_group(0)` so that it leads its own group; the PGID equals the child PID.
-fn kill_process_tree(pid: u32) {
-    #[cfg(target_os = "windows")]
-    {
-        // /T = kill tree (all descendants), /F = force
-        let result = std::process::Command::new("taskkill")
-            .args(["/PID", &pid.to_string(), "/T", "/F"])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status();
-        match result {
-            Ok(status) if status.success() => {
-                tracing::info!("[Runner] taskkill succeeded for PID {}", pid);
-            }
-            Ok(status) => {
-                tracing::debug!(
-                    "[Runner] taskkill for PID {} exited with non-success status: {:?}",
-                    pid,
-                    status
-                );
-            }
-            Err(e) => {
-                tracing::warn!("[Runner] taskkill failed for PID {}: {}", pid, e);
-            }
-        }
-    }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        // Kill the process group (negative PID targets the whole group).
-        // SIGTERM first for graceful shutdown, then SIGKILL to force.
-        let pgid = format!("-{}", pid);
-        let _ = std::process::Command::new("kill")
-            .args(["-TERM", &pgid])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status();
-        std::thread::sleep(std::time::Duration::from_millis(100));
-        let _ = std::process::Command::new("kill")
-            .args(["-KILL", &pgid])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status();
-        // Fallback: kill the direct process too
-        let _ = std::process::Command::new("kill")
-            .args(["-KILL", &pid.to_string()])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status();
-    }
-}
-
 pub struct ProjectRunner {
     processes: Arc<Mutex<HashMap<String, ProjectProcess>>>,
     app_handle: Arc<Mutex<Option<tauri::AppHandle>>>,
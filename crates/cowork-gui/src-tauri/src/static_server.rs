@@ -4,11 +4,12 @@
 
 use std::collections::HashMap;
 use std::fs::File;
-use std::net::{SocketAddr, TcpListener};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use tauri::Emitter;
 
+use cowork_core::process::find_available_port;
+
 lazy_static::lazy_static! {
     static ref STATIC_SERVERS: Arc<Mutex<HashMap<String, StaticServerInstance>>> = Arc::new(Mutex::new(HashMap::new()));
     static ref FULLSTACK_PROCESSES: Arc<Mutex<HashMap<String, FullstackProcessInstance>>> = Arc::new(Mutex::new(HashMap::new()));
@@ -94,7 +95,7 @@ pub fn start_static_server(
     }
 
     // Find an available port
-    let port = find_available_port(preferred_port)?;
+    let port = find_available_port(preferred_port).map_err(|e| e.to_string())?;
 
     println!(
         "[StaticServer] Starting server on port {} for {}",
@@ -356,33 +357,3 @@ fn get_content_type(path: &Path) -> String {
         _ => "application/octet-stream".to_string(),
     }
 }
-
-/// Find an available port starting from preferred_port
-fn find_available_port(preferred_port: u16) -> Result<u16, String> {
-    // Try preferred port first
-    if is_port_available(preferred_port) {
-        return Ok(preferred_port);
-    }
-
-    // Try ports in range [preferred_port, preferred_port + 100]
-    for port in (preferred_port + 1)..=(preferred_port + 100) {
-        if is_port_available(port) {
-            return Ok(port);
-        }
-    }
-
-    // Try random ports in a larger range
-    for port in (8000..=9000).rev() {
-        if is_port_available(port) {
-            return Ok(port);
-        }
-    }
-
-    Err("No available port found in range 8000-9000".to_string())
-}
-
-/// Check if a port is available
-fn is_port_available(port: u16) -> bool {
-    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
-    TcpListener::bind(addr).is_ok()
-}
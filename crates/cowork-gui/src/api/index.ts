@@ -147,6 +147,46 @@ export interface TemplateVariable {
   required: boolean;
 }
 
+export interface IterationDiffItem {
+  id: string;
+  label: string;
+}
+
+export interface IterationDiffFieldChange {
+  field: string;
+  before: unknown;
+  after: unknown;
+}
+
+export interface IterationDiffCollection {
+  collection: string;
+  added: IterationDiffItem[];
+  removed: IterationDiffItem[];
+  modified: (IterationDiffItem & { changes: IterationDiffFieldChange[] })[];
+}
+
+export interface IterationDataDiff {
+  from_iteration_id: string;
+  to_iteration_id: string;
+  collections: IterationDiffCollection[];
+}
+
+export interface ArtifactRevision {
+  revision: number;
+  author: string;
+  feedback?: string;
+  restored_from?: number;
+  created_at: string;
+  size: number;
+}
+
+export interface ArtifactRevisionPair {
+  from: ArtifactRevision;
+  to: ArtifactRevision;
+  from_content: string;
+  to_content: string;
+}
+
 // API object
 const API = {
   project: {
@@ -165,6 +205,8 @@ const API = {
     retry: (iterationId: string): Promise<void> => invoke('gui_retry_iteration', { iterationId }),
     delete: (iterationId: string): Promise<void> => invoke('gui_delete_iteration', { iterationId }),
     regenerateKnowledge: (iterationId: string): Promise<void> => invoke('gui_regenerate_knowledge', { iterationId }),
+    diff: (fromIterationId: string, toIterationId: string): Promise<IterationDataDiff> =>
+      invoke('gui_diff_iterations', { fromIterationId, toIterationId }),
   },
   
   artifacts: {
@@ -176,6 +218,12 @@ const API = {
       invoke('save_iteration_file', { iterationId, filePath, content }),
     formatCode: (iterationId: string, filePath: string): Promise<string> => 
       invoke('format_code', { iterationId, filePath }),
+    revisions: (iterationId: string, artifact: string): Promise<ArtifactRevision[]> =>
+      invoke('gui_get_artifact_revisions', { iterationId, artifact }),
+    compareRevisions: (iterationId: string, artifact: string, fromRevision: number, toRevision: number): Promise<ArtifactRevisionPair> =>
+      invoke('gui_compare_artifact_revisions', { iterationId, artifact, fromRevision, toRevision }),
+    restoreRevision: (iterationId: string, artifact: string, revision: number): Promise<ArtifactRevision> =>
+      invoke('gui_restore_artifact_revision', { iterationId, artifact, revision }),
   },
   
   runner: {